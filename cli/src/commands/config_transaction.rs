@@ -0,0 +1,169 @@
+use anyhow::{anyhow, Result};
+use clap::Subcommand;
+use collectivex_multisig_sdk::state::ConfigAction;
+use collectivex_multisig_sdk::{accounts, instructions, pda};
+use solana_sdk::pubkey::Pubkey;
+
+use crate::commands::TransactionArgs;
+use crate::context::Context;
+use crate::output;
+use crate::parse;
+
+#[derive(Subcommand)]
+pub enum ConfigTransactionCommand {
+    /// Create a config transaction on an autonomous multisig.
+    Create {
+        #[arg(long, value_parser = parse::pubkey)]
+        multisig: Pubkey,
+        /// Repeat for every action, applied in order:
+        ///   add-member=KEY[:PERMISSIONS[:WEIGHT]]
+        ///   remove-member=KEY
+        ///   set-member-permissions=KEY:PERMISSIONS
+        ///   set-member-weight=KEY:WEIGHT
+        ///   change-threshold=THRESHOLD
+        ///   set-time-lock=SECONDS
+        ///   add-spending-limit=VAULT_INDEX:MINT:AMOUNT:PERIOD:MEMBER,..:DESTINATION,..
+        ///   remove-spending-limit=KEY
+        ///   set-rent-collector=KEY|none
+        ///   set-voting-period=SECONDS|none
+        #[arg(long = "action", required = true, value_parser = parse::config_action, verbatim_doc_comment)]
+        actions: Vec<ConfigAction>,
+    },
+    /// Apply an approved config transaction.
+    Execute(TransactionArgs),
+    /// Close a finished or stale config transaction and its proposal.
+    Close(TransactionArgs),
+    /// Print a config transaction.
+    Show(TransactionArgs),
+}
+
+pub fn run(ctx: &Context, command: ConfigTransactionCommand) -> Result<()> {
+    let payer = ctx.payer();
+
+    match command {
+        ConfigTransactionCommand::Create { multisig, actions } => {
+            let index = accounts::fetch_multisig(&ctx.client, &multisig)?.transaction_index + 1;
+            eprintln!("transaction index: {index}");
+
+            ctx.send(
+                &[instructions::config_transaction_create(
+                    multisig, payer, payer, index, actions,
+                )],
+                &[],
+            )
+        }
+        ConfigTransactionCommand::Execute(TransactionArgs { multisig, index }) => {
+            let transaction = accounts::fetch_config_transaction(&ctx.client, &multisig, index)?;
+            let rent_collector = accounts::fetch_multisig(&ctx.client, &multisig)?.rent_collector;
+            let (rent_payer, rent_collector) =
+                rent_accounts(&transaction.actions, rent_collector, payer);
+
+            ctx.send(
+                &[instructions::config_transaction_execute(
+                    multisig,
+                    payer,
+                    index,
+                    &transaction.actions,
+                    rent_payer,
+                    rent_collector,
+                )],
+                &[],
+            )
+        }
+        ConfigTransactionCommand::Close(TransactionArgs { multisig, index }) => {
+            let rent_collector = accounts::fetch_multisig(&ctx.client, &multisig)?
+                .rent_collector
+                .ok_or_else(|| anyhow!("rent reclamation is disabled for this multisig"))?;
+
+            ctx.send(
+                &[instructions::config_transaction_accounts_close(
+                    multisig,
+                    payer,
+                    index,
+                    rent_collector,
+                )],
+                &[],
+            )
+        }
+        ConfigTransactionCommand::Show(TransactionArgs { multisig, index }) => {
+            let transaction = accounts::fetch_config_transaction(&ctx.client, &multisig, index)?;
+            ctx.print(&output::config_transaction(
+                &pda::transaction(&multisig, index).0,
+                &transaction,
+            ))
+        }
+    }
+}
+
+/// The rent payer and rent collector `config_transaction_execute` takes for `actions`, on a
+/// multisig whose rent collector is currently `rent_collector`.
+fn rent_accounts(
+    actions: &[ConfigAction],
+    rent_collector: Option<Pubkey>,
+    payer: Pubkey,
+) -> (Option<Pubkey>, Option<Pubkey>) {
+    // The program checks the rent collector the actions result in.
+    let rent_collector = actions.iter().fold(rent_collector, |rent_collector, action| {
+        match action {
+            ConfigAction::SetRentCollector { new_rent_collector } => *new_rent_collector,
+            _ => rent_collector,
+        }
+    });
+
+    // Added spending limits need a rent payer for the accounts they create, and added members
+    // for the multisig account growing. Without a rent collector, the rent of removed
+    // spending limits goes back to the rent payer.
+    let rent_payer = actions
+        .iter()
+        .any(|action| match action {
+            ConfigAction::AddMember { .. } | ConfigAction::AddSpendingLimit { .. } => true,
+            ConfigAction::RemoveSpendingLimit { .. } => rent_collector.is_none(),
+            _ => false,
+        })
+        .then_some(payer);
+
+    (rent_payer, rent_collector)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rent_accounts_use_the_rent_collector_the_actions_result_in() {
+        let payer = Pubkey::new_unique();
+        let old_collector = Some(Pubkey::new_unique());
+        let new_collector = Some(Pubkey::new_unique());
+        let set = |new_rent_collector| ConfigAction::SetRentCollector { new_rent_collector };
+
+        assert_eq!(rent_accounts(&[], old_collector, payer), (None, old_collector));
+        assert_eq!(
+            rent_accounts(&[set(new_collector)], old_collector, payer),
+            (None, new_collector)
+        );
+        assert_eq!(rent_accounts(&[set(None)], old_collector, payer), (None, None));
+    }
+
+    #[test]
+    fn rent_accounts_pay_removed_spending_limits_back_without_a_rent_collector() {
+        let payer = Pubkey::new_unique();
+        let collector = Some(Pubkey::new_unique());
+        let spending_limit = Pubkey::new_unique();
+        let remove = || ConfigAction::RemoveSpendingLimit { spending_limit };
+
+        assert_eq!(rent_accounts(&[remove()], collector, payer), (None, collector));
+        assert_eq!(rent_accounts(&[remove()], None, payer), (Some(payer), None));
+        assert_eq!(
+            rent_accounts(
+                &[remove(), ConfigAction::SetRentCollector { new_rent_collector: None }],
+                collector,
+                payer,
+            ),
+            (Some(payer), None)
+        );
+        assert_eq!(
+            rent_accounts(&[ConfigAction::ChangeThreshold { new_threshold: 1 }], None, payer),
+            (None, None)
+        );
+    }
+}
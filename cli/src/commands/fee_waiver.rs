@@ -0,0 +1,77 @@
+use anyhow::Result;
+use clap::Subcommand;
+use collectivex_multisig_sdk::{accounts, instructions, pda};
+use solana_sdk::pubkey::Pubkey;
+
+use crate::context::Context;
+use crate::output;
+use crate::parse;
+
+#[derive(Subcommand)]
+pub enum FeeWaiverCommand {
+    /// Grant a creator a discount on the multisig creation fee.
+    Create {
+        #[arg(long, value_parser = parse::pubkey)]
+        creator: Pubkey,
+        /// Share of the creation fee waived, 100 for a full waiver.
+        #[arg(long, default_value_t = 100)]
+        discount_percent: u8,
+        /// Unix timestamp from which the waiver no longer applies. Never expires by default.
+        #[arg(long)]
+        expires_at: Option<i64>,
+        /// Number of multisig creations the waiver applies to. Unlimited by default.
+        #[arg(long)]
+        uses: Option<u32>,
+    },
+    /// Remove the fee waiver of a creator.
+    Remove {
+        #[arg(long, value_parser = parse::pubkey)]
+        creator: Pubkey,
+        /// Receives the rent of the fee waiver. Defaults to the signer.
+        #[arg(long, value_parser = parse::pubkey)]
+        rent_collector: Option<Pubkey>,
+    },
+    /// Print the fee waiver of a creator.
+    Show {
+        #[arg(long, value_parser = parse::pubkey)]
+        creator: Pubkey,
+    },
+}
+
+pub fn run(ctx: &Context, command: FeeWaiverCommand) -> Result<()> {
+    let payer = ctx.payer();
+
+    match command {
+        FeeWaiverCommand::Create {
+            creator,
+            discount_percent,
+            expires_at,
+            uses,
+        } => ctx.send(
+            &[instructions::fee_waiver_create(
+                payer,
+                payer,
+                creator,
+                discount_percent,
+                expires_at,
+                uses,
+            )],
+            &[],
+        ),
+        FeeWaiverCommand::Remove {
+            creator,
+            rent_collector,
+        } => ctx.send(
+            &[instructions::fee_waiver_remove(
+                payer,
+                creator,
+                rent_collector.unwrap_or(payer),
+            )],
+            &[],
+        ),
+        FeeWaiverCommand::Show { creator } => {
+            let waiver = accounts::fetch_fee_waiver(&ctx.client, &creator)?;
+            ctx.print(&output::fee_waiver(&pda::fee_waiver(&creator).0, &waiver))
+        }
+    }
+}
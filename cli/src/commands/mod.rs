@@ -0,0 +1,30 @@
+pub mod config_transaction;
+pub mod fee_waiver;
+pub mod multisig;
+pub mod program_config;
+pub mod proposal;
+pub mod spending_limit;
+pub mod vault_transaction;
+
+pub use config_transaction::ConfigTransactionCommand;
+pub use fee_waiver::FeeWaiverCommand;
+pub use multisig::MultisigCommand;
+pub use program_config::ProgramConfigCommand;
+pub use proposal::ProposalCommand;
+pub use spending_limit::SpendingLimitCommand;
+pub use vault_transaction::VaultTransactionCommand;
+
+use clap::Args;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::parse;
+
+/// Identifies a transaction, and its proposal, by multisig and index.
+#[derive(Args)]
+pub struct TransactionArgs {
+    #[arg(long, value_parser = parse::pubkey)]
+    pub multisig: Pubkey,
+    /// Index of the transaction.
+    #[arg(long)]
+    pub index: u64,
+}
@@ -0,0 +1,302 @@
+use std::path::PathBuf;
+
+use anyhow::Result;
+use clap::Subcommand;
+use collectivex_multisig_sdk::state::{Member, Permissions};
+use collectivex_multisig_sdk::{accounts, instructions, pda};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+
+use crate::context::{read_keypair, Context};
+use crate::output;
+use crate::parse;
+
+#[derive(Subcommand)]
+pub enum MultisigCommand {
+    /// Create a multisig and pay the creation fee.
+    Create {
+        /// Authority allowed to edit the multisig directly. Omit to create an
+        /// autonomous multisig, edited through config transactions only.
+        #[arg(long, value_parser = parse::pubkey)]
+        config_authority: Option<Pubkey>,
+        /// Combined weight of the approvals required.
+        #[arg(long)]
+        threshold: u16,
+        /// `KEY[:PERMISSIONS[:WEIGHT]]`, e.g. `KEY:initiate+vote:2`. Repeat for every member.
+        #[arg(long = "member", required = true, value_parser = parse::member)]
+        members: Vec<Member>,
+        /// Time lock in seconds between approval and execution.
+        #[arg(long, default_value_t = 0)]
+        time_lock: u32,
+        /// Keypair file of the create key. A new key is generated by default.
+        #[arg(long)]
+        create_key: Option<PathBuf>,
+        /// Token account paying a creation fee charged in SPL tokens. Defaults to the
+        /// associated token account of the payer.
+        #[arg(long, value_parser = parse::pubkey)]
+        fee_token_account: Option<Pubkey>,
+        /// Apply the fee waiver of the payer to the creation fee.
+        #[arg(long)]
+        fee_waiver: bool,
+    },
+    /// Add a member to a controlled multisig.
+    AddMember {
+        #[arg(long, value_parser = parse::pubkey)]
+        multisig: Pubkey,
+        /// `KEY[:PERMISSIONS[:WEIGHT]]`.
+        #[arg(long, value_parser = parse::member)]
+        member: Member,
+    },
+    /// Remove a member from a controlled multisig.
+    RemoveMember {
+        #[arg(long, value_parser = parse::pubkey)]
+        multisig: Pubkey,
+        #[arg(long, value_parser = parse::pubkey)]
+        member: Pubkey,
+    },
+    /// Set the time lock of a controlled multisig.
+    SetTimeLock {
+        #[arg(long, value_parser = parse::pubkey)]
+        multisig: Pubkey,
+        /// Time lock in seconds.
+        #[arg(long)]
+        time_lock: u32,
+    },
+    /// Set the threshold of a controlled multisig.
+    ChangeThreshold {
+        #[arg(long, value_parser = parse::pubkey)]
+        multisig: Pubkey,
+        /// Combined weight of the approvals required.
+        #[arg(long)]
+        threshold: u16,
+    },
+    /// Set the permissions of a member of a controlled multisig.
+    SetMemberPermissions {
+        #[arg(long, value_parser = parse::pubkey)]
+        multisig: Pubkey,
+        #[arg(long, value_parser = parse::pubkey)]
+        member: Pubkey,
+        /// A mask, or names joined by `+`, e.g. `initiate+vote`.
+        #[arg(long, value_parser = parse::permissions)]
+        permissions: Permissions,
+    },
+    /// Set the voting weight of a member of a controlled multisig.
+    SetMemberWeight {
+        #[arg(long, value_parser = parse::pubkey)]
+        multisig: Pubkey,
+        #[arg(long, value_parser = parse::pubkey)]
+        member: Pubkey,
+        #[arg(long)]
+        weight: u16,
+    },
+    /// Propose a new config authority for a controlled multisig, which must accept it.
+    ProposeConfigAuthority {
+        #[arg(long, value_parser = parse::pubkey)]
+        multisig: Pubkey,
+        #[arg(long, value_parser = parse::pubkey)]
+        config_authority: Pubkey,
+    },
+    /// Accept the config authority of a multisig, as its pending config authority.
+    AcceptConfigAuthority {
+        #[arg(long, value_parser = parse::pubkey)]
+        multisig: Pubkey,
+    },
+    /// Cancel the pending config authority transfer of a controlled multisig.
+    CancelConfigAuthority {
+        #[arg(long, value_parser = parse::pubkey)]
+        multisig: Pubkey,
+    },
+    /// Give up the config authority, making the multisig autonomous.
+    RenounceConfigAuthority {
+        #[arg(long, value_parser = parse::pubkey)]
+        multisig: Pubkey,
+    },
+    /// Set the rent collector of a controlled multisig.
+    SetRentCollector {
+        #[arg(long, value_parser = parse::pubkey)]
+        multisig: Pubkey,
+        /// Omit to disable rent reclamation.
+        #[arg(long, value_parser = parse::pubkey)]
+        rent_collector: Option<Pubkey>,
+    },
+    /// Set the voting period of a controlled multisig.
+    SetVotingPeriod {
+        #[arg(long, value_parser = parse::pubkey)]
+        multisig: Pubkey,
+        /// Voting period in seconds. Omit to let proposals stay open indefinitely.
+        #[arg(long)]
+        voting_period: Option<u32>,
+    },
+    /// Print a multisig and the address of its default vault.
+    Show {
+        #[arg(long, value_parser = parse::pubkey)]
+        multisig: Pubkey,
+    },
+    /// Upgrade a multisig from a legacy layout.
+    Migrate {
+        /// The key the multisig was created with, which legacy multisigs may not store.
+        #[arg(long, value_parser = parse::pubkey)]
+        create_key: Pubkey,
+    },
+}
+
+pub fn run(ctx: &Context, command: MultisigCommand) -> Result<()> {
+    let payer = ctx.payer();
+
+    match command {
+        MultisigCommand::Create {
+            config_authority,
+            threshold,
+            members,
+            time_lock,
+            create_key,
+            fee_token_account,
+            fee_waiver,
+        } => {
+            let create_key = match create_key {
+                Some(path) => read_keypair(&path)?,
+                None => Keypair::new(),
+            };
+            let program_config = accounts::fetch_program_config(&ctx.client)?;
+            let multisig = pda::multisig(&create_key.pubkey()).0;
+            eprintln!("multisig: {multisig}");
+
+            let instruction = match program_config.fee_mint {
+                Some(fee_mint) => instructions::multisig_create_with_token_fee(
+                    payer,
+                    create_key.pubkey(),
+                    program_config.treasury,
+                    fee_waiver,
+                    fee_mint,
+                    fee_token_account
+                        .unwrap_or_else(|| pda::associated_token_account(&payer, &fee_mint)),
+                    config_authority.unwrap_or_default(),
+                    threshold,
+                    members,
+                    time_lock,
+                ),
+                None => instructions::multisig_create(
+                    payer,
+                    create_key.pubkey(),
+                    program_config.treasury,
+                    fee_waiver,
+                    config_authority.unwrap_or_default(),
+                    threshold,
+                    members,
+                    time_lock,
+                ),
+            };
+
+            ctx.send(&[instruction], &[&create_key])
+        }
+        MultisigCommand::AddMember { multisig, member } => ctx.send(
+            &[instructions::multisig_add_member(multisig, payer, payer, member)],
+            &[],
+        ),
+        MultisigCommand::RemoveMember { multisig, member } => {
+            // The rent freed by the smaller account goes to the rent collector, if any.
+            let rent_collector = accounts::fetch_multisig(&ctx.client, &multisig)?.rent_collector;
+
+            ctx.send(
+                &[instructions::multisig_remove_member(
+                    multisig,
+                    payer,
+                    rent_collector,
+                    member,
+                )],
+                &[],
+            )
+        }
+        MultisigCommand::SetTimeLock {
+            multisig,
+            time_lock,
+        } => ctx.send(
+            &[instructions::multisig_set_time_lock(multisig, payer, time_lock)],
+            &[],
+        ),
+        MultisigCommand::ChangeThreshold {
+            multisig,
+            threshold,
+        } => ctx.send(
+            &[instructions::multisig_change_threshold(multisig, payer, threshold)],
+            &[],
+        ),
+        MultisigCommand::SetMemberPermissions {
+            multisig,
+            member,
+            permissions,
+        } => ctx.send(
+            &[instructions::multisig_set_member_permissions(
+                multisig,
+                payer,
+                member,
+                permissions,
+            )],
+            &[],
+        ),
+        MultisigCommand::SetMemberWeight {
+            multisig,
+            member,
+            weight,
+        } => ctx.send(
+            &[instructions::multisig_set_member_weight(multisig, payer, member, weight)],
+            &[],
+        ),
+        MultisigCommand::ProposeConfigAuthority {
+            multisig,
+            config_authority,
+        } => ctx.send(
+            &[instructions::multisig_propose_config_authority(
+                multisig,
+                payer,
+                config_authority,
+            )],
+            &[],
+        ),
+        MultisigCommand::AcceptConfigAuthority { multisig } => ctx.send(
+            &[instructions::multisig_accept_config_authority(multisig, payer)],
+            &[],
+        ),
+        MultisigCommand::CancelConfigAuthority { multisig } => ctx.send(
+            &[instructions::multisig_cancel_config_authority(multisig, payer)],
+            &[],
+        ),
+        MultisigCommand::RenounceConfigAuthority { multisig } => ctx.send(
+            &[instructions::multisig_renounce_config_authority(multisig, payer)],
+            &[],
+        ),
+        MultisigCommand::SetRentCollector {
+            multisig,
+            rent_collector,
+        } => ctx.send(
+            &[instructions::multisig_set_rent_collector(
+                multisig,
+                payer,
+                rent_collector,
+            )],
+            &[],
+        ),
+        MultisigCommand::SetVotingPeriod {
+            multisig,
+            voting_period,
+        } => ctx.send(
+            &[instructions::multisig_set_voting_period(
+                multisig,
+                payer,
+                voting_period,
+            )],
+            &[],
+        ),
+        MultisigCommand::Migrate { create_key } => {
+            let multisig = pda::multisig(&create_key).0;
+            ctx.send(&[instructions::migrate_multisig(multisig, create_key, payer)], &[])
+        }
+        MultisigCommand::Show { multisig } => {
+            let account = accounts::fetch_multisig(&ctx.client, &multisig)?;
+            let mut value = output::multisig(&multisig, &account);
+            value["vault"] = pda::vault(&multisig, 0).0.to_string().into();
+
+            ctx.print(&value)
+        }
+    }
+}
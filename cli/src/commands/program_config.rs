@@ -0,0 +1,113 @@
+use anyhow::Result;
+use clap::Subcommand;
+use collectivex_multisig_sdk::state::PausedCategories;
+use collectivex_multisig_sdk::{accounts, instructions, pda};
+use solana_sdk::pubkey::Pubkey;
+
+use crate::context::Context;
+use crate::output;
+use crate::parse;
+
+#[derive(Subcommand)]
+pub enum ProgramConfigCommand {
+    /// Initialize the program config, signed by the upgrade authority of the program.
+    Init {
+        #[arg(long, value_parser = parse::pubkey)]
+        authority: Pubkey,
+        /// Multisig creation fee in lamports.
+        #[arg(long)]
+        creation_fee: u64,
+        #[arg(long, value_parser = parse::pubkey)]
+        treasury: Pubkey,
+    },
+    /// Propose a new authority for the program config, which must accept it.
+    ProposeAuthority {
+        #[arg(long, value_parser = parse::pubkey)]
+        new_authority: Pubkey,
+    },
+    /// Accept the program config authority, as its pending authority.
+    AcceptAuthority,
+    /// Cancel the pending authority transfer.
+    CancelAuthority,
+    /// Set the multisig creation fee in lamports, or in base units of the fee mint.
+    SetCreationFee {
+        #[arg(long)]
+        new_creation_fee: u64,
+    },
+    /// Set the treasury receiving the creation fees.
+    SetTreasury {
+        #[arg(long, value_parser = parse::pubkey)]
+        new_treasury: Pubkey,
+    },
+    /// Set the SPL token mint the creation fee is charged in.
+    SetFeeMint {
+        /// Omit to charge the creation fee in SOL.
+        #[arg(long, value_parser = parse::pubkey)]
+        new_fee_mint: Option<Pubkey>,
+    },
+    /// Pause categories of instructions across every multisig, e.g. during an incident.
+    SetPaused {
+        /// `create`, `initiate`, `vote` and `execute` joined by `+`, or `none` to unpause.
+        #[arg(long, value_parser = parse::paused)]
+        paused: PausedCategories,
+    },
+    /// Print the program config.
+    Show,
+    /// Upgrade the program config from a legacy layout.
+    Migrate,
+}
+
+pub fn run(ctx: &Context, command: ProgramConfigCommand) -> Result<()> {
+    let payer = ctx.payer();
+
+    match command {
+        ProgramConfigCommand::Init {
+            authority,
+            creation_fee,
+            treasury,
+        } => ctx.send(
+            &[instructions::program_config_init(
+                payer,
+                authority,
+                creation_fee,
+                treasury,
+            )],
+            &[],
+        ),
+        ProgramConfigCommand::ProposeAuthority { new_authority } => ctx.send(
+            &[instructions::program_config_propose_authority(payer, new_authority)],
+            &[],
+        ),
+        ProgramConfigCommand::AcceptAuthority => ctx.send(
+            &[instructions::program_config_accept_authority(payer)],
+            &[],
+        ),
+        ProgramConfigCommand::CancelAuthority => ctx.send(
+            &[instructions::program_config_cancel_authority(payer)],
+            &[],
+        ),
+        ProgramConfigCommand::SetCreationFee { new_creation_fee } => ctx.send(
+            &[instructions::program_config_set_creation_fee(payer, new_creation_fee)],
+            &[],
+        ),
+        ProgramConfigCommand::SetTreasury { new_treasury } => ctx.send(
+            &[instructions::program_config_set_treasury(payer, new_treasury)],
+            &[],
+        ),
+        ProgramConfigCommand::SetFeeMint { new_fee_mint } => ctx.send(
+            &[instructions::program_config_set_fee_mint(payer, new_fee_mint)],
+            &[],
+        ),
+        ProgramConfigCommand::SetPaused { paused } => ctx.send(
+            &[instructions::program_config_set_paused(payer, paused)],
+            &[],
+        ),
+        ProgramConfigCommand::Migrate => {
+            ctx.send(&[instructions::migrate_program_config(payer)], &[])
+        }
+        ProgramConfigCommand::Show => {
+            let config = accounts::fetch_program_config(&ctx.client)?;
+            ctx.print(&output::program_config(&pda::program_config().0, &config))
+        }
+    }
+}
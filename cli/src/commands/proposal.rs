@@ -0,0 +1,80 @@
+use anyhow::Result;
+use clap::{Args, Subcommand};
+use collectivex_multisig_sdk::{accounts, instructions, pda};
+
+use crate::commands::TransactionArgs;
+use crate::context::Context;
+use crate::output;
+
+#[derive(Subcommand)]
+pub enum ProposalCommand {
+    /// Create the proposal of a transaction.
+    Create {
+        #[command(flatten)]
+        transaction: TransactionArgs,
+        /// Create the proposal as a draft, to be activated before voting.
+        #[arg(long)]
+        draft: bool,
+    },
+    /// Open a draft proposal for voting.
+    Activate(TransactionArgs),
+    /// Approve a proposal.
+    Approve(VoteArgs),
+    /// Reject a proposal.
+    Reject(VoteArgs),
+    /// Cancel an approved proposal.
+    Cancel(VoteArgs),
+    /// Mark an active proposal whose voting period has passed as expired.
+    Expire(TransactionArgs),
+    /// Print a proposal.
+    Show(TransactionArgs),
+    /// Upgrade a proposal from a legacy layout.
+    Migrate(TransactionArgs),
+}
+
+#[derive(Args)]
+pub struct VoteArgs {
+    #[command(flatten)]
+    transaction: TransactionArgs,
+    /// Reason for the vote, emitted with the vote event.
+    #[arg(long)]
+    memo: Option<String>,
+}
+
+pub fn run(ctx: &Context, command: ProposalCommand) -> Result<()> {
+    let payer = ctx.payer();
+
+    let instruction = match command {
+        ProposalCommand::Create {
+            transaction: TransactionArgs { multisig, index },
+            draft,
+        } => instructions::proposal_create(multisig, payer, payer, index, draft),
+        ProposalCommand::Activate(TransactionArgs { multisig, index }) => {
+            instructions::proposal_activate(multisig, payer, index)
+        }
+        ProposalCommand::Approve(VoteArgs {
+            transaction: TransactionArgs { multisig, index },
+            memo,
+        }) => instructions::proposal_approve(multisig, payer, index, memo),
+        ProposalCommand::Reject(VoteArgs {
+            transaction: TransactionArgs { multisig, index },
+            memo,
+        }) => instructions::proposal_reject(multisig, payer, index, memo),
+        ProposalCommand::Cancel(VoteArgs {
+            transaction: TransactionArgs { multisig, index },
+            memo,
+        }) => instructions::proposal_cancel(multisig, payer, index, memo),
+        ProposalCommand::Expire(TransactionArgs { multisig, index }) => {
+            instructions::proposal_expire(multisig, payer, index)
+        }
+        ProposalCommand::Migrate(TransactionArgs { multisig, index }) => {
+            instructions::migrate_proposal(multisig, index, payer)
+        }
+        ProposalCommand::Show(TransactionArgs { multisig, index }) => {
+            let proposal = accounts::fetch_proposal(&ctx.client, &multisig, index)?;
+            return ctx.print(&output::proposal(&pda::proposal(&multisig, index).0, &proposal));
+        }
+    };
+
+    ctx.send(&[instruction], &[])
+}
@@ -0,0 +1,174 @@
+use anyhow::{bail, Result};
+use clap::Subcommand;
+use collectivex_multisig_sdk::state::Period;
+use collectivex_multisig_sdk::{accounts, instructions, pda};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+
+use crate::context::Context;
+use crate::output;
+use crate::parse;
+
+#[derive(Subcommand)]
+pub enum SpendingLimitCommand {
+    /// Add a spending limit to a controlled multisig.
+    Add {
+        #[arg(long, value_parser = parse::pubkey)]
+        multisig: Pubkey,
+        #[arg(long, default_value_t = 0)]
+        vault_index: u8,
+        /// `sol` or the address of an SPL token mint.
+        #[arg(long, default_value = "sol", value_parser = parse::mint)]
+        mint: Pubkey,
+        /// Amount allowed per period, in lamports or base token units.
+        #[arg(long)]
+        amount: u64,
+        /// `one-time`, `day`, `week`, `month` or a number of seconds.
+        #[arg(long, value_parser = parse::period)]
+        period: Period,
+        /// Member allowed to use the limit. Repeat for every member.
+        #[arg(long = "member", required = true, value_parser = parse::pubkey)]
+        members: Vec<Pubkey>,
+        /// Allowed destination. Repeat for every destination.
+        #[arg(long = "destination", required = true, value_parser = parse::pubkey)]
+        destinations: Vec<Pubkey>,
+    },
+    /// Remove a spending limit from a controlled multisig.
+    Remove {
+        #[arg(long, value_parser = parse::pubkey)]
+        spending_limit: Pubkey,
+        /// Receives the rent of the spending limit. Defaults to the signer.
+        #[arg(long, value_parser = parse::pubkey)]
+        rent_collector: Option<Pubkey>,
+        #[arg(long)]
+        memo: Option<String>,
+    },
+    /// Transfer from a vault within the bounds of a spending limit.
+    Use {
+        #[arg(long, value_parser = parse::pubkey)]
+        spending_limit: Pubkey,
+        #[arg(long)]
+        amount: u64,
+        /// Destination wallet.
+        #[arg(long, value_parser = parse::pubkey)]
+        destination: Pubkey,
+        /// Token account of the vault. Required for SPL token limits.
+        #[arg(long, value_parser = parse::pubkey)]
+        vault_token_account: Option<Pubkey>,
+        /// Token account of the destination. Required for SPL token limits.
+        #[arg(long, value_parser = parse::pubkey)]
+        destination_token_account: Option<Pubkey>,
+    },
+    /// Print a spending limit.
+    Show {
+        #[arg(long, value_parser = parse::pubkey)]
+        spending_limit: Pubkey,
+    },
+    /// Upgrade a spending limit from a legacy layout.
+    Migrate {
+        #[arg(long, value_parser = parse::pubkey)]
+        spending_limit: Pubkey,
+    },
+}
+
+pub fn run(ctx: &Context, command: SpendingLimitCommand) -> Result<()> {
+    let payer = ctx.payer();
+
+    match command {
+        SpendingLimitCommand::Add {
+            multisig,
+            vault_index,
+            mint,
+            amount,
+            period,
+            members,
+            destinations,
+        } => {
+            let create_key = Keypair::new();
+            eprintln!(
+                "spending limit: {}",
+                pda::spending_limit(&multisig, &create_key.pubkey()).0
+            );
+
+            ctx.send(
+                &[instructions::multisig_add_spending_limit(
+                    multisig,
+                    payer,
+                    create_key.pubkey(),
+                    vault_index,
+                    mint,
+                    amount,
+                    period,
+                    members,
+                    destinations,
+                )],
+                &[&create_key],
+            )
+        }
+        SpendingLimitCommand::Remove {
+            spending_limit,
+            rent_collector,
+            memo,
+        } => {
+            let limit = accounts::fetch_spending_limit(&ctx.client, &spending_limit)?;
+
+            ctx.send(
+                &[instructions::multisig_remove_spending_limit(
+                    limit.multisig,
+                    payer,
+                    spending_limit,
+                    rent_collector.unwrap_or(payer),
+                    memo,
+                )],
+                &[],
+            )
+        }
+        SpendingLimitCommand::Use {
+            spending_limit,
+            amount,
+            destination,
+            vault_token_account,
+            destination_token_account,
+        } => {
+            let limit = accounts::fetch_spending_limit(&ctx.client, &spending_limit)?;
+
+            let instruction = if limit.mint == Pubkey::default() {
+                instructions::spending_limit_use_sol(
+                    limit.multisig,
+                    payer,
+                    spending_limit,
+                    limit.vault_index,
+                    destination,
+                    amount,
+                )
+            } else {
+                let (Some(vault_token_account), Some(destination_token_account)) =
+                    (vault_token_account, destination_token_account)
+                else {
+                    bail!("--vault-token-account and --destination-token-account are required for SPL token limits");
+                };
+
+                instructions::spending_limit_use_token(
+                    limit.multisig,
+                    payer,
+                    spending_limit,
+                    limit.vault_index,
+                    limit.mint,
+                    destination,
+                    vault_token_account,
+                    destination_token_account,
+                    amount,
+                )
+            };
+
+            ctx.send(&[instruction], &[])
+        }
+        SpendingLimitCommand::Migrate { spending_limit } => {
+            ctx.send(&[instructions::migrate_spending_limit(spending_limit, payer)], &[])
+        }
+        SpendingLimitCommand::Show { spending_limit } => {
+            let limit = accounts::fetch_spending_limit(&ctx.client, &spending_limit)?;
+            ctx.print(&output::spending_limit(&spending_limit, &limit))
+        }
+    }
+}
@@ -0,0 +1,104 @@
+use anyhow::Result;
+use clap::Subcommand;
+use collectivex_multisig_sdk::state::{
+    vault_transaction::{VaultAccountMeta, VaultInstruction},
+    VaultTransactionMessage,
+};
+use collectivex_multisig_sdk::{accounts, instructions, pda};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::system_instruction;
+
+use crate::commands::TransactionArgs;
+use crate::context::Context;
+use crate::output;
+use crate::parse;
+
+#[derive(Subcommand)]
+pub enum VaultTransactionCommand {
+    /// Create a vault transaction transferring SOL out of a vault.
+    Create {
+        #[arg(long, value_parser = parse::pubkey)]
+        multisig: Pubkey,
+        #[arg(long, default_value_t = 0)]
+        vault_index: u8,
+        /// `DESTINATION:LAMPORTS`. Repeat for every transfer.
+        #[arg(long = "transfer", required = true, value_parser = parse::transfer)]
+        transfers: Vec<(Pubkey, u64)>,
+    },
+    /// Execute an approved vault transaction.
+    Execute(TransactionArgs),
+    /// Print a vault transaction.
+    Show(TransactionArgs),
+}
+
+pub fn run(ctx: &Context, command: VaultTransactionCommand) -> Result<()> {
+    let payer = ctx.payer();
+
+    match command {
+        VaultTransactionCommand::Create {
+            multisig,
+            vault_index,
+            transfers,
+        } => {
+            let index = accounts::fetch_multisig(&ctx.client, &multisig)?.transaction_index + 1;
+            let vault = pda::vault(&multisig, vault_index).0;
+            eprintln!("transaction index: {index}");
+
+            let message = VaultTransactionMessage {
+                instructions: transfers
+                    .into_iter()
+                    .map(|(destination, lamports)| {
+                        let instruction = system_instruction::transfer(&vault, &destination, lamports);
+
+                        VaultInstruction {
+                            program_id: instruction.program_id,
+                            accounts: instruction
+                                .accounts
+                                .into_iter()
+                                .map(|meta| VaultAccountMeta {
+                                    pubkey: meta.pubkey,
+                                    is_signer: meta.is_signer,
+                                    is_writable: meta.is_writable,
+                                })
+                                .collect(),
+                            data: instruction.data,
+                        }
+                    })
+                    .collect(),
+            };
+
+            ctx.send(
+                &[instructions::vault_transaction_create(
+                    multisig,
+                    payer,
+                    payer,
+                    index,
+                    vault_index,
+                    message,
+                )],
+                &[],
+            )
+        }
+        VaultTransactionCommand::Execute(TransactionArgs { multisig, index }) => {
+            let transaction = accounts::fetch_vault_transaction(&ctx.client, &multisig, index)?;
+
+            ctx.send(
+                &[instructions::vault_transaction_execute(
+                    multisig,
+                    payer,
+                    index,
+                    transaction.vault_index,
+                    &transaction.message,
+                )],
+                &[],
+            )
+        }
+        VaultTransactionCommand::Show(TransactionArgs { multisig, index }) => {
+            let transaction = accounts::fetch_vault_transaction(&ctx.client, &multisig, index)?;
+            ctx.print(&output::vault_transaction(
+                &pda::transaction(&multisig, index).0,
+                &transaction,
+            ))
+        }
+    }
+}
@@ -0,0 +1,93 @@
+use std::path::PathBuf;
+
+use anyhow::{anyhow, Context as _, Result};
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use serde_json::Value;
+use solana_rpc_client::rpc_client::RpcClient;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{read_keypair_file, Keypair, Signer};
+use solana_sdk::transaction::Transaction;
+
+use crate::output::{self, OutputFormat};
+
+/// Connection, signer and output settings shared by every command.
+pub struct Context {
+    pub client: RpcClient,
+    pub payer: Keypair,
+    pub dry_run: bool,
+    pub output: OutputFormat,
+}
+
+impl Context {
+    pub fn new(
+        url: String,
+        keypair: Option<PathBuf>,
+        dry_run: bool,
+        output: OutputFormat,
+    ) -> Result<Self> {
+        let keypair = match keypair {
+            Some(path) => path,
+            None => default_keypair_path()?,
+        };
+
+        Ok(Self {
+            client: RpcClient::new_with_commitment(url, CommitmentConfig::confirmed()),
+            payer: read_keypair(&keypair)?,
+            dry_run,
+            output,
+        })
+    }
+
+    pub fn payer(&self) -> Pubkey {
+        self.payer.pubkey()
+    }
+
+    /// Signs the instructions with the payer and `signers`, then sends the
+    /// transaction, or prints it when `--dry-run` is set.
+    pub fn send(&self, instructions: &[Instruction], signers: &[&Keypair]) -> Result<()> {
+        let blockhash = self
+            .client
+            .get_latest_blockhash()
+            .context("failed to fetch the latest blockhash")?;
+
+        let mut all_signers: Vec<&Keypair> = vec![&self.payer];
+        all_signers.extend_from_slice(signers);
+
+        let transaction = Transaction::new_signed_with_payer(
+            instructions,
+            Some(&self.payer()),
+            &all_signers,
+            blockhash,
+        );
+
+        if self.dry_run {
+            println!("{}", BASE64.encode(bincode::serialize(&transaction)?));
+            return Ok(());
+        }
+
+        let signature = self
+            .client
+            .send_and_confirm_transaction(&transaction)
+            .context("failed to send the transaction")?;
+        println!("{signature}");
+
+        Ok(())
+    }
+
+    pub fn print(&self, value: &Value) -> Result<()> {
+        output::print(self.output, value)
+    }
+}
+
+pub fn read_keypair(path: &PathBuf) -> Result<Keypair> {
+    read_keypair_file(path).map_err(|err| anyhow!("failed to read keypair {}: {err}", path.display()))
+}
+
+fn default_keypair_path() -> Result<PathBuf> {
+    let home = std::env::var_os("HOME").ok_or_else(|| anyhow!("HOME is not set, pass --keypair"))?;
+
+    Ok(PathBuf::from(home).join(".config/solana/id.json"))
+}
@@ -0,0 +1,86 @@
+mod commands;
+mod context;
+mod output;
+mod parse;
+
+use std::path::PathBuf;
+
+use anyhow::Result;
+use clap::{Parser, Subcommand};
+
+use commands::{
+    ConfigTransactionCommand, FeeWaiverCommand, MultisigCommand, ProgramConfigCommand,
+    ProposalCommand, SpendingLimitCommand, VaultTransactionCommand,
+};
+use context::Context;
+use output::OutputFormat;
+
+/// Operate collectivex_multisig multisigs.
+#[derive(Parser)]
+#[command(name = "collectivex", version)]
+struct Cli {
+    /// RPC endpoint of the cluster.
+    #[arg(
+        long,
+        short = 'u',
+        global = true,
+        env = "COLLECTIVEX_RPC_URL",
+        default_value = "http://127.0.0.1:8899"
+    )]
+    url: String,
+
+    /// Keypair file of the signer and fee payer. Defaults to `~/.config/solana/id.json`.
+    #[arg(long, short = 'k', global = true, env = "COLLECTIVEX_KEYPAIR")]
+    keypair: Option<PathBuf>,
+
+    /// Sign and print the base64-encoded transaction instead of sending it.
+    #[arg(long, global = true)]
+    dry_run: bool,
+
+    /// Format of the printed account state.
+    #[arg(long, short = 'o', global = true, value_enum, default_value_t = OutputFormat::Text)]
+    output: OutputFormat,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Initialize and edit the global program config.
+    #[command(subcommand)]
+    ProgramConfig(ProgramConfigCommand),
+    /// Grant and remove creation fee waivers.
+    #[command(subcommand)]
+    FeeWaiver(FeeWaiverCommand),
+    /// Create, edit and inspect multisigs.
+    #[command(subcommand)]
+    Multisig(MultisigCommand),
+    /// Add, remove and use spending limits.
+    #[command(subcommand)]
+    SpendingLimit(SpendingLimitCommand),
+    /// Create, execute and close config transactions of autonomous multisigs.
+    #[command(subcommand)]
+    ConfigTransaction(ConfigTransactionCommand),
+    /// Create and execute vault transactions.
+    #[command(subcommand)]
+    VaultTransaction(VaultTransactionCommand),
+    /// Create, vote on and expire proposals.
+    #[command(subcommand)]
+    Proposal(ProposalCommand),
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+    let ctx = Context::new(cli.url, cli.keypair, cli.dry_run, cli.output)?;
+
+    match cli.command {
+        Command::ProgramConfig(command) => commands::program_config::run(&ctx, command),
+        Command::FeeWaiver(command) => commands::fee_waiver::run(&ctx, command),
+        Command::Multisig(command) => commands::multisig::run(&ctx, command),
+        Command::SpendingLimit(command) => commands::spending_limit::run(&ctx, command),
+        Command::ConfigTransaction(command) => commands::config_transaction::run(&ctx, command),
+        Command::VaultTransaction(command) => commands::vault_transaction::run(&ctx, command),
+        Command::Proposal(command) => commands::proposal::run(&ctx, command),
+    }
+}
@@ -0,0 +1,411 @@
+//! Human-readable and JSON rendering of the program accounts.
+
+use anyhow::Result;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use clap::ValueEnum;
+use collectivex_multisig_sdk::state::{
+    ConfigAction, ConfigTransaction, FeeWaiver, Member, Multisig, PauseCategory, PausedCategories,
+    Period, Permission, Permissions, ProgramConfig, Proposal, ProposalStatus, SpendingLimit,
+    VaultTransaction,
+};
+use serde_json::{json, Value};
+use solana_sdk::pubkey::Pubkey;
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum OutputFormat {
+    Text,
+    Json,
+}
+
+pub fn print(format: OutputFormat, value: &Value) -> Result<()> {
+    match format {
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(value)?),
+        OutputFormat::Text => {
+            let mut out = String::new();
+            render(value, 0, &mut out);
+            print!("{out}");
+        }
+    }
+
+    Ok(())
+}
+
+/// Renders a value as indented `key: value` lines.
+fn render(value: &Value, indent: usize, out: &mut String) {
+    let pad = " ".repeat(indent);
+
+    match value {
+        Value::Object(map) => {
+            for (key, value) in map {
+                match value {
+                    Value::Object(_) | Value::Array(_) if !is_empty(value) => {
+                        out.push_str(&format!("{pad}{key}:\n"));
+                        render(value, indent + 2, out);
+                    }
+                    _ => out.push_str(&format!("{pad}{key}: {}\n", scalar(value))),
+                }
+            }
+        }
+        Value::Array(items) => {
+            for item in items {
+                match item {
+                    Value::Object(_) | Value::Array(_) if !is_empty(item) => {
+                        out.push_str(&format!("{pad}-\n"));
+                        render(item, indent + 2, out);
+                    }
+                    _ => out.push_str(&format!("{pad}- {}\n", scalar(item))),
+                }
+            }
+        }
+        _ => out.push_str(&format!("{pad}{}\n", scalar(value))),
+    }
+}
+
+fn is_empty(value: &Value) -> bool {
+    match value {
+        Value::Object(map) => map.is_empty(),
+        Value::Array(items) => items.is_empty(),
+        _ => false,
+    }
+}
+
+fn scalar(value: &Value) -> String {
+    match value {
+        Value::Null => "none".to_string(),
+        Value::String(s) => s.clone(),
+        Value::Array(_) => "[]".to_string(),
+        Value::Object(_) => "{}".to_string(),
+        _ => value.to_string(),
+    }
+}
+
+fn pubkeys(keys: &[Pubkey]) -> Value {
+    keys.iter().map(|key| Value::String(key.to_string())).collect()
+}
+
+fn optional_pubkey(key: &Option<Pubkey>) -> Value {
+    key.map_or(Value::Null, |key| Value::String(key.to_string()))
+}
+
+fn mint(mint: &Pubkey) -> Value {
+    if *mint == Pubkey::default() {
+        json!("sol")
+    } else {
+        json!(mint.to_string())
+    }
+}
+
+fn permissions(permissions: &Permissions) -> Value {
+    let names: Vec<&str> = [
+        (Permission::Initiate, "initiate"),
+        (Permission::Vote, "vote"),
+        (Permission::Execute, "execute"),
+    ]
+    .into_iter()
+    .filter(|(permission, _)| permissions.has(*permission))
+    .map(|(_, name)| name)
+    .collect();
+
+    json!(names.join("+"))
+}
+
+fn paused(paused: &PausedCategories) -> Value {
+    let names: Vec<&str> = [
+        (PauseCategory::Create, "create"),
+        (PauseCategory::Initiate, "initiate"),
+        (PauseCategory::Vote, "vote"),
+        (PauseCategory::Execute, "execute"),
+    ]
+    .into_iter()
+    .filter(|(category, _)| paused.has(*category))
+    .map(|(_, name)| name)
+    .collect();
+
+    json!(names.join("+"))
+}
+
+fn member(member: &Member) -> Value {
+    json!({
+        "key": member.key.to_string(),
+        "permissions": permissions(&member.permissions),
+        "weight": member.weight,
+    })
+}
+
+fn period(period: &Period) -> Value {
+    match period {
+        Period::OneTime => json!("one-time"),
+        Period::Day => json!("day"),
+        Period::Week => json!("week"),
+        Period::Month => json!("month"),
+        Period::Custom { seconds } => json!(format!("{seconds}s")),
+    }
+}
+
+fn status(status: &ProposalStatus) -> Value {
+    let (name, timestamp) = match status {
+        ProposalStatus::Draft { timestamp } => ("draft", timestamp),
+        ProposalStatus::Active { timestamp } => ("active", timestamp),
+        ProposalStatus::Approved { timestamp } => ("approved", timestamp),
+        ProposalStatus::Rejected { timestamp } => ("rejected", timestamp),
+        ProposalStatus::Cancelled { timestamp } => ("cancelled", timestamp),
+        ProposalStatus::Executing { timestamp } => ("executing", timestamp),
+        ProposalStatus::Executed { timestamp } => ("executed", timestamp),
+        ProposalStatus::Expired { timestamp } => ("expired", timestamp),
+    };
+
+    json!({ "status": name, "timestamp": timestamp })
+}
+
+fn action(action: &ConfigAction) -> Value {
+    match action {
+        ConfigAction::AddMember { new_member } => json!({ "add_member": member(new_member) }),
+        ConfigAction::RemoveMember { old_member } => {
+            json!({ "remove_member": old_member.to_string() })
+        }
+        ConfigAction::SetMemberPermissions {
+            member,
+            permissions: mask,
+        } => json!({
+            "set_member_permissions": {
+                "member": member.to_string(),
+                "permissions": permissions(mask),
+            }
+        }),
+        ConfigAction::SetMemberWeight { member, weight } => json!({
+            "set_member_weight": { "member": member.to_string(), "weight": weight }
+        }),
+        ConfigAction::ChangeThreshold { new_threshold } => {
+            json!({ "change_threshold": new_threshold })
+        }
+        ConfigAction::SetTimeLock { new_time_lock } => json!({ "set_time_lock": new_time_lock }),
+        ConfigAction::AddSpendingLimit {
+            create_key,
+            vault_index,
+            mint: limit_mint,
+            amount,
+            period: reset,
+            members,
+            destinations,
+        } => json!({
+            "add_spending_limit": {
+                "create_key": create_key.to_string(),
+                "vault_index": vault_index,
+                "mint": mint(limit_mint),
+                "amount": amount,
+                "period": period(reset),
+                "members": pubkeys(members),
+                "destinations": pubkeys(destinations),
+            }
+        }),
+        ConfigAction::RemoveSpendingLimit { spending_limit } => {
+            json!({ "remove_spending_limit": spending_limit.to_string() })
+        }
+        ConfigAction::SetRentCollector { new_rent_collector } => {
+            json!({ "set_rent_collector": optional_pubkey(new_rent_collector) })
+        }
+        ConfigAction::SetVotingPeriod { new_voting_period } => {
+            json!({ "set_voting_period": new_voting_period })
+        }
+    }
+}
+
+pub fn program_config(address: &Pubkey, config: &ProgramConfig) -> Value {
+    json!({
+        "address": address.to_string(),
+        "version": config.version,
+        "authority": config.authority.to_string(),
+        "creation_fee": config.creation_fee,
+        "treasury": config.treasury.to_string(),
+        "fee_mint": optional_pubkey(&config.fee_mint),
+        "pending_authority": optional_pubkey(&config.pending_authority),
+        "paused": paused(&config.paused),
+    })
+}
+
+pub fn fee_waiver(address: &Pubkey, waiver: &FeeWaiver) -> Value {
+    json!({
+        "address": address.to_string(),
+        "version": waiver.version,
+        "creator": waiver.creator.to_string(),
+        "discount_percent": waiver.discount_percent,
+        "expires_at": waiver.expires_at,
+        "remaining_uses": waiver.remaining_uses,
+    })
+}
+
+pub fn multisig(address: &Pubkey, multisig: &Multisig) -> Value {
+    json!({
+        "address": address.to_string(),
+        "version": multisig.version,
+        "mode": if multisig.is_autonomous() { "autonomous" } else { "controlled" },
+        "create_key": multisig.create_key.to_string(),
+        "config_authority": multisig.config_authority.to_string(),
+        "pending_config_authority": optional_pubkey(&multisig.pending_config_authority),
+        "threshold": multisig.threshold,
+        "voting_weight": multisig.voting_weight(),
+        "members": multisig.members.iter().map(member).collect::<Vec<_>>(),
+        "time_lock": multisig.time_lock,
+        "transaction_index": multisig.transaction_index,
+        "stale_transaction_index": multisig.stale_transaction_index,
+        "rent_collector": optional_pubkey(&multisig.rent_collector),
+        "voting_period": multisig.voting_period,
+    })
+}
+
+pub fn spending_limit(address: &Pubkey, limit: &SpendingLimit) -> Value {
+    json!({
+        "address": address.to_string(),
+        "version": limit.version,
+        "multisig": limit.multisig.to_string(),
+        "create_key": limit.create_key.to_string(),
+        "vault_index": limit.vault_index,
+        "mint": mint(&limit.mint),
+        "amount": limit.amount,
+        "period": period(&limit.period),
+        "remaining_amount": limit.remaining_amount,
+        "last_reset": limit.last_reset,
+        "members": pubkeys(&limit.members),
+        "destinations": pubkeys(&limit.destinations),
+    })
+}
+
+pub fn config_transaction(address: &Pubkey, transaction: &ConfigTransaction) -> Value {
+    json!({
+        "address": address.to_string(),
+        "multisig": transaction.multisig.to_string(),
+        "creator": transaction.creator.to_string(),
+        "index": transaction.index,
+        "actions": transaction.actions.iter().map(action).collect::<Vec<_>>(),
+    })
+}
+
+pub fn vault_transaction(address: &Pubkey, transaction: &VaultTransaction) -> Value {
+    let instructions: Vec<Value> = transaction
+        .message
+        .instructions
+        .iter()
+        .map(|instruction| {
+            json!({
+                "program_id": instruction.program_id.to_string(),
+                "accounts": instruction.accounts.iter().map(|meta| json!({
+                    "pubkey": meta.pubkey.to_string(),
+                    "is_signer": meta.is_signer,
+                    "is_writable": meta.is_writable,
+                })).collect::<Vec<_>>(),
+                "data": BASE64.encode(&instruction.data),
+            })
+        })
+        .collect();
+
+    json!({
+        "address": address.to_string(),
+        "multisig": transaction.multisig.to_string(),
+        "creator": transaction.creator.to_string(),
+        "index": transaction.index,
+        "vault_index": transaction.vault_index,
+        "instructions": instructions,
+    })
+}
+
+pub fn proposal(address: &Pubkey, proposal: &Proposal) -> Value {
+    json!({
+        "address": address.to_string(),
+        "version": proposal.version,
+        "multisig": proposal.multisig.to_string(),
+        "transaction_index": proposal.transaction_index,
+        "status": status(&proposal.status),
+        "approved": pubkeys(&proposal.approved),
+        "rejected": pubkeys(&proposal.rejected),
+        "cancelled": pubkeys(&proposal.cancelled),
+        "expires_at": proposal.expires_at,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text(value: &Value) -> String {
+        let mut out = String::new();
+        render(value, 0, &mut out);
+        out
+    }
+
+    #[test]
+    fn scalar_renders_null_and_empty_collections() {
+        assert_eq!(scalar(&Value::Null), "none");
+        assert_eq!(scalar(&json!("sol")), "sol");
+        assert_eq!(scalar(&json!(42)), "42");
+        assert_eq!(scalar(&json!(true)), "true");
+        assert_eq!(scalar(&json!([])), "[]");
+        assert_eq!(scalar(&json!({})), "{}");
+    }
+
+    #[test]
+    fn render_indents_nested_objects_and_arrays() {
+        let value = json!({
+            "address": "abc",
+            "rent_collector": null,
+            "members": [
+                { "key": "m1", "weight": 1 },
+                { "key": "m2", "weight": 2 },
+            ],
+            "approved": [],
+            "status": { "status": "active", "timestamp": 7 },
+            "actions": [{ "set_time_lock": 60 }, { "set_voting_period": null }],
+            "destinations": ["d1", "d2"],
+            "extra": {},
+        });
+
+        assert_eq!(
+            text(&value),
+            concat!(
+                "address: abc\n",
+                "rent_collector: none\n",
+                "members:\n",
+                "  -\n",
+                "    key: m1\n",
+                "    weight: 1\n",
+                "  -\n",
+                "    key: m2\n",
+                "    weight: 2\n",
+                "approved: []\n",
+                "status:\n",
+                "  status: active\n",
+                "  timestamp: 7\n",
+                "actions:\n",
+                "  -\n",
+                "    set_time_lock: 60\n",
+                "  -\n",
+                "    set_voting_period: none\n",
+                "destinations:\n",
+                "  - d1\n",
+                "  - d2\n",
+                "extra: {}\n",
+            )
+        );
+    }
+
+    #[test]
+    fn render_handles_top_level_scalars_and_arrays() {
+        assert_eq!(text(&Value::Null), "none\n");
+        assert_eq!(text(&json!([[], {}, null])), "- []\n- {}\n- none\n");
+        assert_eq!(text(&json!([["a"]])), "-\n  - a\n");
+    }
+
+    #[test]
+    fn permissions_and_paused_render_names() {
+        assert_eq!(permissions(&Permissions::all()), json!("initiate+vote+execute"));
+        assert_eq!(
+            permissions(&Permissions::from_vec(&[Permission::Vote])),
+            json!("vote")
+        );
+        assert_eq!(paused(&PausedCategories::default()), json!(""));
+        assert_eq!(
+            paused(&PausedCategories::from_vec(&[PauseCategory::Create, PauseCategory::Execute])),
+            json!("create+execute")
+        );
+    }
+}
@@ -0,0 +1,385 @@
+//! Value parsers for the command-line arguments.
+
+use std::str::FromStr;
+
+use collectivex_multisig_sdk::state::{
+    ConfigAction, Member, PauseCategory, PausedCategories, Period, Permission, Permissions,
+};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+
+pub fn pubkey(s: &str) -> Result<Pubkey, String> {
+    Pubkey::from_str(s).map_err(|err| format!("invalid public key `{s}`: {err}"))
+}
+
+fn number<T: FromStr>(s: &str) -> Result<T, String> {
+    s.parse().map_err(|_| format!("invalid number `{s}`"))
+}
+
+/// `sol` or the address of an SPL token mint.
+pub fn mint(s: &str) -> Result<Pubkey, String> {
+    match s {
+        "sol" => Ok(Pubkey::default()),
+        _ => pubkey(s),
+    }
+}
+
+/// A numeric mask, or permission names joined by `+`, e.g. `initiate+vote`.
+pub fn permissions(s: &str) -> Result<Permissions, String> {
+    if let Ok(mask) = s.parse() {
+        return Ok(Permissions { mask });
+    }
+
+    let permissions = s
+        .split('+')
+        .map(|name| match name {
+            "initiate" => Ok(Permission::Initiate),
+            "vote" => Ok(Permission::Vote),
+            "execute" => Ok(Permission::Execute),
+            _ => Err(format!("unknown permission `{name}`")),
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(Permissions::from_vec(&permissions))
+}
+
+/// `none`, a numeric mask, or category names joined by `+`, e.g. `create+initiate`.
+pub fn paused(s: &str) -> Result<PausedCategories, String> {
+    if s == "none" {
+        return Ok(PausedCategories::default());
+    }
+    if let Ok(mask) = s.parse() {
+        return Ok(PausedCategories { mask });
+    }
+
+    let categories = s
+        .split('+')
+        .map(|name| match name {
+            "create" => Ok(PauseCategory::Create),
+            "initiate" => Ok(PauseCategory::Initiate),
+            "vote" => Ok(PauseCategory::Vote),
+            "execute" => Ok(PauseCategory::Execute),
+            _ => Err(format!("unknown pause category `{name}`")),
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(PausedCategories::from_vec(&categories))
+}
+
+/// `KEY[:PERMISSIONS[:WEIGHT]]`, with all permissions and a weight of 1 by default.
+pub fn member(s: &str) -> Result<Member, String> {
+    let mut parts = s.split(':');
+    let key = pubkey(parts.next().unwrap_or_default())?;
+    let permissions = parts
+        .next()
+        .map(permissions)
+        .transpose()?
+        .unwrap_or_else(Permissions::all);
+    let weight = parts.next().map(number).transpose()?.unwrap_or(1);
+
+    if parts.next().is_some() {
+        return Err(format!("invalid member `{s}`, expected KEY[:PERMISSIONS[:WEIGHT]]"));
+    }
+
+    Ok(Member {
+        key,
+        permissions,
+        weight,
+    })
+}
+
+/// `one-time`, `day`, `week`, `month` or a number of seconds.
+pub fn period(s: &str) -> Result<Period, String> {
+    match s {
+        "one-time" => Ok(Period::OneTime),
+        "day" => Ok(Period::Day),
+        "week" => Ok(Period::Week),
+        "month" => Ok(Period::Month),
+        _ => Ok(Period::Custom {
+            seconds: number(s)?,
+        }),
+    }
+}
+
+fn optional<T>(s: &str, parse: impl Fn(&str) -> Result<T, String>) -> Result<Option<T>, String> {
+    match s {
+        "none" => Ok(None),
+        _ => parse(s).map(Some),
+    }
+}
+
+fn pubkeys(s: &str) -> Result<Vec<Pubkey>, String> {
+    s.split(',').filter(|key| !key.is_empty()).map(pubkey).collect()
+}
+
+/// `NAME=VALUE`, see the help of `config-transaction create` for the supported actions.
+pub fn config_action(s: &str) -> Result<ConfigAction, String> {
+    let (name, value) = s
+        .split_once('=')
+        .ok_or_else(|| format!("invalid action `{s}`, expected NAME=VALUE"))?;
+    let parts: Vec<&str> = value.split(':').collect();
+
+    let action = match (name, parts.as_slice()) {
+        ("add-member", _) => ConfigAction::AddMember {
+            new_member: member(value)?,
+        },
+        ("remove-member", [key]) => ConfigAction::RemoveMember {
+            old_member: pubkey(key)?,
+        },
+        ("set-member-permissions", [key, mask]) => ConfigAction::SetMemberPermissions {
+            member: pubkey(key)?,
+            permissions: permissions(mask)?,
+        },
+        ("set-member-weight", [key, weight]) => ConfigAction::SetMemberWeight {
+            member: pubkey(key)?,
+            weight: number(weight)?,
+        },
+        ("change-threshold", [threshold]) => ConfigAction::ChangeThreshold {
+            new_threshold: number(threshold)?,
+        },
+        ("set-time-lock", [time_lock]) => ConfigAction::SetTimeLock {
+            new_time_lock: number(time_lock)?,
+        },
+        ("add-spending-limit", [vault_index, mint_key, amount, reset, members, destinations]) => {
+            ConfigAction::AddSpendingLimit {
+                // Spending limits created by config transactions are signed
+                // for by the program, so any unique key will do.
+                create_key: Keypair::new().pubkey(),
+                vault_index: number(vault_index)?,
+                mint: mint(mint_key)?,
+                amount: number(amount)?,
+                period: period(reset)?,
+                members: pubkeys(members)?,
+                destinations: pubkeys(destinations)?,
+            }
+        }
+        ("remove-spending-limit", [key]) => ConfigAction::RemoveSpendingLimit {
+            spending_limit: pubkey(key)?,
+        },
+        ("set-rent-collector", [key]) => ConfigAction::SetRentCollector {
+            new_rent_collector: optional(key, pubkey)?,
+        },
+        ("set-voting-period", [seconds]) => ConfigAction::SetVotingPeriod {
+            new_voting_period: optional(seconds, number)?,
+        },
+        _ => return Err(format!("invalid action `{s}`")),
+    };
+
+    Ok(action)
+}
+
+/// `DESTINATION:LAMPORTS`.
+pub fn transfer(s: &str) -> Result<(Pubkey, u64), String> {
+    let (destination, lamports) = s
+        .split_once(':')
+        .ok_or_else(|| format!("invalid transfer `{s}`, expected DESTINATION:LAMPORTS"))?;
+
+    Ok((pubkey(destination)?, number(lamports)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn new_key() -> (Pubkey, String) {
+        let key = Pubkey::new_unique();
+        (key, key.to_string())
+    }
+
+    #[test]
+    fn member_defaults_to_all_permissions_and_a_weight_of_one() {
+        let (key, s) = new_key();
+
+        assert_eq!(
+            member(&s).unwrap(),
+            Member {
+                key,
+                permissions: Permissions::all(),
+                weight: 1,
+            }
+        );
+        assert_eq!(
+            member(&format!("{s}:vote")).unwrap(),
+            Member {
+                key,
+                permissions: Permissions::from_vec(&[Permission::Vote]),
+                weight: 1,
+            }
+        );
+        assert_eq!(
+            member(&format!("{s}:initiate+vote:3")).unwrap(),
+            Member {
+                key,
+                permissions: Permissions::from_vec(&[Permission::Initiate, Permission::Vote]),
+                weight: 3,
+            }
+        );
+    }
+
+    #[test]
+    fn member_rejects_invalid_input() {
+        let (_, s) = new_key();
+
+        assert!(member(&format!("{s}:vote:1:extra"))
+            .unwrap_err()
+            .contains("expected KEY[:PERMISSIONS[:WEIGHT]]"));
+        assert!(member("not-a-key").unwrap_err().contains("invalid public key"));
+        assert!(member(&format!("{s}:vote:heavy")).unwrap_err().contains("invalid number"));
+        assert!(member(&format!("{s}:admin")).unwrap_err().contains("unknown permission"));
+    }
+
+    #[test]
+    fn permissions_accept_a_mask_or_names() {
+        assert_eq!(permissions("5").unwrap(), Permissions { mask: 5 });
+        assert_eq!(
+            permissions("initiate+execute").unwrap(),
+            Permissions::from_vec(&[Permission::Initiate, Permission::Execute])
+        );
+        assert_eq!(permissions("initiate+vote+execute").unwrap(), Permissions::all());
+        assert_eq!(
+            permissions("vote+admin").unwrap_err(),
+            "unknown permission `admin`"
+        );
+        assert!(permissions("").is_err());
+    }
+
+    #[test]
+    fn paused_accepts_none_a_mask_or_names() {
+        assert_eq!(paused("none").unwrap(), PausedCategories::default());
+        assert_eq!(paused("9").unwrap(), PausedCategories { mask: 9 });
+        assert_eq!(
+            paused("create+vote").unwrap(),
+            PausedCategories::from_vec(&[PauseCategory::Create, PauseCategory::Vote])
+        );
+        assert_eq!(
+            paused("create+initiate+vote+execute").unwrap(),
+            PausedCategories::all()
+        );
+        assert_eq!(
+            paused("create+withdraw").unwrap_err(),
+            "unknown pause category `withdraw`"
+        );
+    }
+
+    #[test]
+    fn mint_accepts_sol_or_a_key() {
+        let (key, s) = new_key();
+
+        assert_eq!(mint("sol").unwrap(), Pubkey::default());
+        assert_eq!(mint(&s).unwrap(), key);
+        assert!(mint("usdc").is_err());
+    }
+
+    #[test]
+    fn period_accepts_names_or_seconds() {
+        assert_eq!(period("one-time").unwrap(), Period::OneTime);
+        assert_eq!(period("day").unwrap(), Period::Day);
+        assert_eq!(period("week").unwrap(), Period::Week);
+        assert_eq!(period("month").unwrap(), Period::Month);
+        assert_eq!(period("3600").unwrap(), Period::Custom { seconds: 3600 });
+        assert_eq!(period("year").unwrap_err(), "invalid number `year`");
+    }
+
+    #[test]
+    fn config_action_parses_every_action() {
+        let (key, s) = new_key();
+        let (other, o) = new_key();
+
+        assert!(matches!(
+            config_action(&format!("add-member={s}:vote:2")).unwrap(),
+            ConfigAction::AddMember { new_member }
+                if new_member.key == key && new_member.weight == 2
+        ));
+        assert!(matches!(
+            config_action(&format!("remove-member={s}")).unwrap(),
+            ConfigAction::RemoveMember { old_member } if old_member == key
+        ));
+        assert!(matches!(
+            config_action(&format!("set-member-permissions={s}:initiate+vote")).unwrap(),
+            ConfigAction::SetMemberPermissions { member, permissions }
+                if member == key && permissions.mask == 3
+        ));
+        assert!(matches!(
+            config_action(&format!("set-member-weight={s}:4")).unwrap(),
+            ConfigAction::SetMemberWeight { member, weight } if member == key && weight == 4
+        ));
+        assert!(matches!(
+            config_action("change-threshold=2").unwrap(),
+            ConfigAction::ChangeThreshold { new_threshold: 2 }
+        ));
+        assert!(matches!(
+            config_action("set-time-lock=60").unwrap(),
+            ConfigAction::SetTimeLock { new_time_lock: 60 }
+        ));
+        assert!(matches!(
+            config_action(&format!("add-spending-limit=1:sol:500:day:{s},{o}:{o}")).unwrap(),
+            ConfigAction::AddSpendingLimit {
+                vault_index: 1,
+                mint,
+                amount: 500,
+                period: Period::Day,
+                members,
+                destinations,
+                ..
+            } if mint == Pubkey::default() && members == [key, other] && destinations == [other]
+        ));
+        assert!(matches!(
+            config_action(&format!("add-spending-limit=0:{s}:1:week::")).unwrap(),
+            ConfigAction::AddSpendingLimit { mint, members, destinations, .. }
+                if mint == key && members.is_empty() && destinations.is_empty()
+        ));
+        assert!(matches!(
+            config_action(&format!("remove-spending-limit={s}")).unwrap(),
+            ConfigAction::RemoveSpendingLimit { spending_limit } if spending_limit == key
+        ));
+        assert!(matches!(
+            config_action(&format!("set-rent-collector={s}")).unwrap(),
+            ConfigAction::SetRentCollector { new_rent_collector: Some(collector) }
+                if collector == key
+        ));
+        assert!(matches!(
+            config_action("set-rent-collector=none").unwrap(),
+            ConfigAction::SetRentCollector { new_rent_collector: None }
+        ));
+        assert!(matches!(
+            config_action("set-voting-period=3600").unwrap(),
+            ConfigAction::SetVotingPeriod { new_voting_period: Some(3600) }
+        ));
+        assert!(matches!(
+            config_action("set-voting-period=none").unwrap(),
+            ConfigAction::SetVotingPeriod { new_voting_period: None }
+        ));
+    }
+
+    #[test]
+    fn config_action_rejects_the_wrong_arity() {
+        let (_, s) = new_key();
+
+        for action in [
+            format!("remove-member={s}:{s}"),
+            format!("set-member-permissions={s}"),
+            format!("set-member-weight={s}:1:2"),
+            "change-threshold=1:2".to_string(),
+            "set-time-lock=1:2".to_string(),
+            format!("add-spending-limit=0:sol:500:day:{s}"),
+            format!("remove-spending-limit={s}:{s}"),
+            format!("set-rent-collector={s}:{s}"),
+            "set-voting-period=1:2".to_string(),
+            "rename=x".to_string(),
+        ] {
+            assert_eq!(
+                config_action(&action).err(),
+                Some(format!("invalid action `{action}`")),
+            );
+        }
+
+        assert_eq!(
+            config_action("change-threshold").err(),
+            Some("invalid action `change-threshold`, expected NAME=VALUE".to_string()),
+        );
+        assert_eq!(
+            config_action("change-threshold=two").err(),
+            Some("invalid number `two`".to_string()),
+        );
+    }
+}
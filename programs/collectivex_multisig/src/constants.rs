@@ -7,4 +7,19 @@ pub const MULTISIG_SEED: &[u8] = b"multisig";
 pub const PROGRAM_CONFIG_SEED: &[u8] = b"program_config";
 pub const SPENDING_LIMIT_SEED: &[u8] = b"spending_limit";
 pub const TRANSACTION_SEED: &[u8] = b"transaction";
-pub const PROPOSAL_SEED: &[u8] = b"proposal";
\ No newline at end of file
+pub const PROPOSAL_SEED: &[u8] = b"proposal";
+pub const VAULT_SEED: &[u8] = b"vault";
+pub const FEE_WAIVER_SEED: &[u8] = b"fee_waiver";
+/// The SPL associated token account program.
+pub const ASSOCIATED_TOKEN_PROGRAM_ID: Pubkey = pubkey!("ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL");
+/// Maximum length of a memo, in bytes.
+pub const MAX_MEMO_LEN: usize = 256;
+/// Maximum number of members of a multisig.
+pub const MAX_MEMBERS: usize = 50;
+/// Maximum number of members of a spending limit. Its members and destinations are set in
+/// one instruction, so together they must fit in a transaction.
+pub const MAX_SPENDING_LIMIT_MEMBERS: usize = 10;
+/// Maximum number of destinations of a spending limit.
+pub const MAX_SPENDING_LIMIT_DESTINATIONS: usize = 10;
+/// Maximum time lock of a multisig, in seconds (90 days).
+pub const MAX_TIME_LOCK: u32 = 90 * 24 * 60 * 60;
@@ -14,22 +14,78 @@ pub enum ErrorCode {
     RemoveLastMember,
     #[msg("The specified member is not part of the multisig.")]
     NotAMember,
-    #[msg("Threshold exceeds the number of members.")]
+    #[msg("Threshold exceeds the total weight of the voting members.")]
     InvalidThreshold,
     #[msg("The specified spending limit is invalid.")]
     InvalidSpendingLimit,
     #[msg("No actions provided for the config transaction.")]
     NoActions,
-    #[msg("Member already approved the transaction")]
-    AlreadyApproved,
-    #[msg("Member already rejected the transaction")]
-    AlreadyRejected,
-    #[msg("Member already cancelled the transaction")]
-    AlreadyCancelled,
     #[msg("The signer does not have the required permissions.")]
     Unauthorized,
     #[msg("Proposal is in an invalid status.")]
     InvalidProposalStatus,
     #[msg("The proposal is stale.")]
     StaleProposal,
+    #[msg("The time lock has not been released yet.")]
+    TimeLockNotReleased,
+    #[msg("A required account was not provided.")]
+    MissingAccount,
+    #[msg("The transaction message is invalid.")]
+    InvalidTransactionMessage,
+    #[msg("An account referenced by the transaction was not provided.")]
+    InvalidAccount,
+    #[msg("The destination is not allowed by the spending limit.")]
+    InvalidDestination,
+    #[msg("The amount must be greater than zero.")]
+    InvalidAmount,
+    #[msg("The amount exceeds the remaining spending limit.")]
+    SpendingLimitExceeded,
+    #[msg("The mint does not match the spending limit or the program config fee mint.")]
+    InvalidMint,
+    #[msg("The spending limit period is invalid.")]
+    InvalidPeriod,
+    #[msg("The member permissions are invalid.")]
+    InvalidPermissions,
+    #[msg("Rent reclamation is disabled for this multisig.")]
+    RentReclamationDisabled,
+    #[msg("The rent collector does not match the multisig.")]
+    InvalidRentCollector,
+    #[msg("The instruction is not supported for autonomous multisigs.")]
+    NotSupportedForAutonomous,
+    #[msg("The instruction is not supported for controlled multisigs.")]
+    NotSupportedForControlled,
+    #[msg("The member weight must be greater than zero.")]
+    InvalidWeight,
+    #[msg("The voting period must be greater than zero.")]
+    InvalidVotingPeriod,
+    #[msg("The proposal voting period has expired.")]
+    ProposalExpired,
+    #[msg("The proposal voting period has not expired yet.")]
+    ProposalNotExpired,
+    #[msg("The memo exceeds the maximum length.")]
+    MemoTooLong,
+    #[msg("The transaction does not exist.")]
+    InvalidTransactionIndex,
+    #[msg("The threshold must be greater than zero.")]
+    ZeroThreshold,
+    #[msg("The time lock exceeds the maximum.")]
+    TimeLockTooLong,
+    #[msg("The number of destinations exceeds the maximum.")]
+    ExceedsMaxDestinations,
+    #[msg("The account already has the current layout.")]
+    AlreadyMigrated,
+    #[msg("The account is not a legacy account of this type.")]
+    NotMigratable,
+    #[msg("The discount must be between 1 and 100 percent.")]
+    InvalidDiscount,
+    #[msg("The fee waiver has expired.")]
+    FeeWaiverExpired,
+    #[msg("The fee waiver has no uses left.")]
+    FeeWaiverUsedUp,
+    #[msg("No authority transfer is pending.")]
+    NoPendingAuthority,
+    #[msg("The instruction is paused by the program config authority.")]
+    Paused,
+    #[msg("The paused categories are invalid.")]
+    InvalidPausedCategories,
 }
\ No newline at end of file
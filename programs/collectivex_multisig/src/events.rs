@@ -0,0 +1,260 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{Period, ProposalStatus};
+
+#[event]
+pub struct ProgramConfigInitialized {
+    pub actor: Pubkey,
+    pub authority: Pubkey,
+    pub creation_fee: u64,
+    pub treasury: Pubkey,
+}
+
+/// Emitted whenever the program config authority, pending authority, creation fee, treasury,
+/// fee mint or paused categories change.
+#[event]
+pub struct ProgramConfigUpdated {
+    pub actor: Pubkey,
+    pub authority: Pubkey,
+    pub creation_fee: u64,
+    pub treasury: Pubkey,
+    pub fee_mint: Option<Pubkey>,
+    pub pending_authority: Option<Pubkey>,
+    pub paused: u8,
+}
+
+#[event]
+pub struct FeeWaiverCreated {
+    pub actor: Pubkey,
+    pub fee_waiver: Pubkey,
+    pub creator: Pubkey,
+    pub discount_percent: u8,
+    pub expires_at: Option<i64>,
+    pub remaining_uses: Option<u32>,
+}
+
+#[event]
+pub struct FeeWaiverRemoved {
+    pub actor: Pubkey,
+    pub fee_waiver: Pubkey,
+    pub creator: Pubkey,
+}
+
+/// Emitted when `multisig_create` applies a fee waiver.
+#[event]
+pub struct FeeWaiverUsed {
+    pub actor: Pubkey,
+    pub fee_waiver: Pubkey,
+    pub multisig: Pubkey,
+    pub creation_fee: u64,
+    pub remaining_uses: Option<u32>,
+}
+
+#[event]
+pub struct MultisigCreated {
+    pub actor: Pubkey,
+    pub multisig: Pubkey,
+    pub create_key: Pubkey,
+    pub config_authority: Pubkey,
+    pub threshold: u16,
+    pub members: Vec<Pubkey>,
+    pub time_lock: u32,
+}
+
+#[event]
+pub struct MemberAdded {
+    pub actor: Pubkey,
+    pub multisig: Pubkey,
+    pub member: Pubkey,
+    pub permissions: u8,
+    pub weight: u16,
+}
+
+#[event]
+pub struct MemberRemoved {
+    pub actor: Pubkey,
+    pub multisig: Pubkey,
+    pub member: Pubkey,
+}
+
+#[event]
+pub struct MemberPermissionsChanged {
+    pub actor: Pubkey,
+    pub multisig: Pubkey,
+    pub member: Pubkey,
+    pub permissions: u8,
+}
+
+#[event]
+pub struct MemberWeightChanged {
+    pub actor: Pubkey,
+    pub multisig: Pubkey,
+    pub member: Pubkey,
+    pub weight: u16,
+}
+
+#[event]
+pub struct ThresholdChanged {
+    pub actor: Pubkey,
+    pub multisig: Pubkey,
+    pub threshold: u16,
+}
+
+#[event]
+pub struct TimeLockChanged {
+    pub actor: Pubkey,
+    pub multisig: Pubkey,
+    pub time_lock: u32,
+}
+
+/// Emitted when a config authority is proposed, with `None` when the proposal is cancelled.
+#[event]
+pub struct PendingConfigAuthorityChanged {
+    pub actor: Pubkey,
+    pub multisig: Pubkey,
+    pub pending_config_authority: Option<Pubkey>,
+}
+
+#[event]
+pub struct ConfigAuthorityChanged {
+    pub actor: Pubkey,
+    pub multisig: Pubkey,
+    pub config_authority: Pubkey,
+}
+
+#[event]
+pub struct RentCollectorChanged {
+    pub actor: Pubkey,
+    pub multisig: Pubkey,
+    pub rent_collector: Option<Pubkey>,
+}
+
+#[event]
+pub struct VotingPeriodChanged {
+    pub actor: Pubkey,
+    pub multisig: Pubkey,
+    pub voting_period: Option<u32>,
+}
+
+#[event]
+pub struct SpendingLimitAdded {
+    pub actor: Pubkey,
+    pub multisig: Pubkey,
+    pub spending_limit: Pubkey,
+    pub vault_index: u8,
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub period: Period,
+}
+
+#[event]
+pub struct SpendingLimitRemoved {
+    pub actor: Pubkey,
+    pub multisig: Pubkey,
+    pub spending_limit: Pubkey,
+    pub memo: Option<String>,
+}
+
+#[event]
+pub struct SpendingLimitUsed {
+    pub actor: Pubkey,
+    pub multisig: Pubkey,
+    pub spending_limit: Pubkey,
+    pub vault_index: u8,
+    pub mint: Pubkey,
+    pub destination: Pubkey,
+    pub amount: u64,
+    pub remaining_amount: u64,
+}
+
+#[event]
+pub struct ConfigTransactionCreated {
+    pub actor: Pubkey,
+    pub multisig: Pubkey,
+    pub transaction_index: u64,
+}
+
+#[event]
+pub struct VaultTransactionCreated {
+    pub actor: Pubkey,
+    pub multisig: Pubkey,
+    pub transaction_index: u64,
+    pub vault_index: u8,
+}
+
+/// Emitted when a config transaction and its proposal are closed.
+#[event]
+pub struct ConfigTransactionClosed {
+    pub actor: Pubkey,
+    pub multisig: Pubkey,
+    pub transaction_index: u64,
+    pub rent_collector: Pubkey,
+}
+
+#[event]
+pub struct ProposalCreated {
+    pub actor: Pubkey,
+    pub multisig: Pubkey,
+    pub transaction_index: u64,
+    pub status: ProposalStatus,
+}
+
+#[event]
+pub struct ProposalActivated {
+    pub actor: Pubkey,
+    pub multisig: Pubkey,
+    pub transaction_index: u64,
+    pub expires_at: Option<i64>,
+}
+
+/// Emitted for every approval vote. `status` is the status of the proposal after the vote.
+#[event]
+pub struct ProposalApproved {
+    pub actor: Pubkey,
+    pub multisig: Pubkey,
+    pub transaction_index: u64,
+    pub status: ProposalStatus,
+    pub memo: Option<String>,
+}
+
+/// Emitted for every rejection vote. `status` is the status of the proposal after the vote.
+#[event]
+pub struct ProposalRejected {
+    pub actor: Pubkey,
+    pub multisig: Pubkey,
+    pub transaction_index: u64,
+    pub status: ProposalStatus,
+    pub memo: Option<String>,
+}
+
+/// Emitted for every cancellation vote. `status` is the status of the proposal after the vote.
+#[event]
+pub struct ProposalCancelled {
+    pub actor: Pubkey,
+    pub multisig: Pubkey,
+    pub transaction_index: u64,
+    pub status: ProposalStatus,
+    pub memo: Option<String>,
+}
+
+#[event]
+pub struct ProposalExecuted {
+    pub actor: Pubkey,
+    pub multisig: Pubkey,
+    pub transaction_index: u64,
+}
+
+#[event]
+pub struct ProposalExpired {
+    pub actor: Pubkey,
+    pub multisig: Pubkey,
+    pub transaction_index: u64,
+}
+
+/// Emitted when an account is upgraded from a legacy layout.
+#[event]
+pub struct AccountMigrated {
+    pub actor: Pubkey,
+    pub account: Pubkey,
+    pub version: u8,
+}
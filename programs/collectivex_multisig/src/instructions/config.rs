@@ -1,7 +1,9 @@
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::bpf_loader_upgradeable;
 
 use crate::error::ErrorCode;
-use crate::state::ProgramConfig;
+use crate::events::{ProgramConfigInitialized, ProgramConfigUpdated};
+use crate::state::{PausedCategories, ProgramConfig};
 use crate::constants::{DISCRIMINATOR, PROGRAM_CONFIG_SEED};
 
 #[derive(Accounts)]
@@ -15,9 +17,18 @@ pub struct ProgramConfigInit<'info> {
     )]
     pub program_config: Account<'info, ProgramConfig>,
 
+    /// Must be the upgrade authority of the program.
     #[account(mut)]
     pub initializer: Signer<'info>,
 
+    /// The program data account of this program, holding its upgrade authority.
+    #[account(
+        seeds = [crate::ID.as_ref()],
+        bump,
+        seeds::program = bpf_loader_upgradeable::ID,
+    )]
+    pub program_data: Account<'info, ProgramData>,
+
     pub system_program: Program<'info, System>,
 }
 
@@ -28,22 +39,40 @@ impl <'info> ProgramConfigInit<'info> {
         creation_fee: u64,
         treasury: Pubkey
     ) -> Result<()> {
+        // Only the deployer may claim the config, so nobody can front-run it with their treasury.
         require!(
-            self.program_config.authority != Pubkey::default(),
+            self.program_data.upgrade_authority_address == Some(self.initializer.key()),
+            ErrorCode::Unauthorized
+        );
+
+        require!(
+            authority != Pubkey::default(),
             ErrorCode::InvalidAuthority
         );
 
         require!(
-            self.program_config.treasury != Pubkey::default(),
+            treasury != Pubkey::default(),
             ErrorCode::InvalidTreasury
         );
 
         self.program_config.set_inner(ProgramConfig {
+            version: ProgramConfig::VERSION,
+            authority,
+            creation_fee,
+            treasury,
+            fee_mint: None,
+            pending_authority: None,
+            paused: PausedCategories::default(),
+            reserved: [0; 63],
+        });
+
+        emit!(ProgramConfigInitialized {
+            actor: self.initializer.key(),
             authority,
             creation_fee,
-            treasury
+            treasury,
         });
-        
+
         Ok(())
     }
 }
@@ -71,11 +100,42 @@ impl <'info> ProgramConfigEdit<'info> {
         Ok(())
     }
 
-    pub fn set_program_config_authority(
+    pub fn set_program_config_paused(
+        &mut self,
+        new_paused: PausedCategories
+    ) -> Result<()> {
+        require!(new_paused.is_valid(), ErrorCode::InvalidPausedCategories);
+
+        self.program_config.paused = new_paused;
+        self.emit_program_config_updated();
+
+        Ok(())
+    }
+
+    /// Proposes `new_authority` to take over the config, once it accepts.
+    pub fn propose_program_config_authority(
         &mut self,
         new_authority: Pubkey
     ) -> Result<()> {
-        self.program_config.authority = new_authority;
+        require!(
+            new_authority != Pubkey::default(),
+            ErrorCode::InvalidAuthority
+        );
+
+        self.program_config.pending_authority = Some(new_authority);
+        self.emit_program_config_updated();
+
+        Ok(())
+    }
+
+    pub fn cancel_program_config_authority(&mut self) -> Result<()> {
+        require!(
+            self.program_config.pending_authority.is_some(),
+            ErrorCode::NoPendingAuthority
+        );
+
+        self.program_config.pending_authority = None;
+        self.emit_program_config_updated();
 
         Ok(())
     }
@@ -85,6 +145,7 @@ impl <'info> ProgramConfigEdit<'info> {
         new_creation_fee: u64
     ) -> Result<()> {
         self.program_config.creation_fee = new_creation_fee;
+        self.emit_program_config_updated();
 
         Ok(())
     }
@@ -99,7 +160,73 @@ impl <'info> ProgramConfigEdit<'info> {
         );
 
         self.program_config.treasury = new_treasury;
+        self.emit_program_config_updated();
 
         Ok(())
     }
+
+    pub fn set_program_config_fee_mint(
+        &mut self,
+        new_fee_mint: Option<Pubkey>
+    ) -> Result<()> {
+        require!(
+            new_fee_mint != Some(Pubkey::default()),
+            ErrorCode::InvalidMint
+        );
+
+        self.program_config.fee_mint = new_fee_mint;
+        self.emit_program_config_updated();
+
+        Ok(())
+    }
+
+    fn emit_program_config_updated(&self) {
+        emit_program_config_updated(&self.program_config, self.current_authority.key());
+    }
+}
+
+#[derive(Accounts)]
+pub struct ProgramConfigAcceptAuthority<'info> {
+    #[account(
+        mut,
+        seeds = [PROGRAM_CONFIG_SEED],
+        bump,
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    /// The pending authority of the program config.
+    pub new_authority: Signer<'info>,
+}
+
+impl <'info> ProgramConfigAcceptAuthority<'info> {
+    pub fn accept_program_config_authority(&mut self) -> Result<()> {
+        let pending_authority = self
+            .program_config
+            .pending_authority
+            .ok_or(ErrorCode::NoPendingAuthority)?;
+
+        require_keys_eq!(
+            pending_authority,
+            self.new_authority.key(),
+            ErrorCode::InvalidAuthority
+        );
+
+        self.program_config.authority = pending_authority;
+        self.program_config.pending_authority = None;
+        emit_program_config_updated(&self.program_config, self.new_authority.key());
+
+        Ok(())
+    }
+}
+
+fn emit_program_config_updated(program_config: &ProgramConfig, actor: Pubkey) {
+    emit!(ProgramConfigUpdated {
+        actor,
+        authority: program_config.authority,
+        creation_fee: program_config.creation_fee,
+        treasury: program_config.treasury,
+        fee_mint: program_config.fee_mint,
+        pending_authority: program_config.pending_authority,
+        paused: program_config.paused.mask,
+    });
 }
\ No newline at end of file
@@ -0,0 +1,123 @@
+use anchor_lang::prelude::*;
+
+use crate::error::ErrorCode;
+use crate::events::ConfigTransactionClosed;
+use crate::state::{ConfigTransaction, Multisig, Proposal, ProposalStatus};
+use crate::utils::close_account;
+use crate::constants::{MULTISIG_SEED, PROGRAM_CONFIG_SEED, PROPOSAL_SEED, TRANSACTION_SEED};
+
+#[derive(Accounts)]
+pub struct ConfigTransactionAccountsClose<'info> {
+    #[account(
+        seeds = [PROGRAM_CONFIG_SEED, MULTISIG_SEED, multisig.create_key.as_ref()],
+        bump,
+    )]
+    pub multisig: Account<'info, Multisig>,
+
+    /// The proposal of the config transaction, closed along with it. Stale config
+    /// transactions may never have had one, in which case the address holds no proposal.
+    /// CHECK: the address is checked by the seeds, and read as a proposal when the program owns it.
+    #[account(
+        mut,
+        seeds = [
+            PROGRAM_CONFIG_SEED,
+            multisig.key().as_ref(),
+            TRANSACTION_SEED,
+            &transaction.index.to_le_bytes(),
+            PROPOSAL_SEED,
+        ],
+        bump,
+    )]
+    pub proposal: AccountInfo<'info>,
+
+    /// The config transaction to close.
+    #[account(
+        mut,
+        close = rent_collector,
+        seeds = [
+            PROGRAM_CONFIG_SEED,
+            multisig.key().as_ref(),
+            TRANSACTION_SEED,
+            &transaction.index.to_le_bytes(),
+        ],
+        bump = transaction.bump,
+    )]
+    pub transaction: Account<'info, ConfigTransaction>,
+
+    /// The rent collector of the multisig.
+    /// CHECK: validated against `multisig.rent_collector`.
+    #[account(mut)]
+    pub rent_collector: AccountInfo<'info>,
+
+    /// Anyone can close the accounts once they are no longer needed.
+    pub caller: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> ConfigTransactionAccountsClose<'info> {
+    /// Validates that the accounts can be closed and the rent goes to the rent collector.
+    pub fn validate(&self) -> Result<()> {
+        let Self {
+            multisig,
+            transaction,
+            rent_collector,
+            ..
+        } = self;
+
+        // Rent reclamation must be enabled on the multisig.
+        let multisig_rent_collector = multisig
+            .rent_collector
+            .ok_or(ErrorCode::RentReclamationDisabled)?;
+
+        require_keys_eq!(
+            rent_collector.key(),
+            multisig_rent_collector,
+            ErrorCode::InvalidRentCollector
+        );
+
+        // Stale config transactions can never be executed, whatever their status.
+        let is_stale = transaction.index <= multisig.stale_transaction_index;
+
+        // Otherwise the proposal must be in a final state.
+        let is_final = self.proposal()?.is_some_and(|proposal| {
+            matches!(
+                proposal.status,
+                ProposalStatus::Executed { .. }
+                    | ProposalStatus::Rejected { .. }
+                    | ProposalStatus::Cancelled { .. }
+                    | ProposalStatus::Expired { .. }
+            )
+        });
+        require!(is_stale || is_final, ErrorCode::InvalidProposalStatus);
+
+        Ok(())
+    }
+
+    /// The proposal of the transaction, if one was created.
+    fn proposal(&self) -> Result<Option<Proposal>> {
+        if self.proposal.owner != &crate::ID {
+            return Ok(None);
+        }
+
+        let data = self.proposal.try_borrow_data()?;
+        Ok(Some(Proposal::try_deserialize(&mut &data[..])?))
+    }
+
+    /// Closes the proposal, if any, and records the closing of the accounts. Anchor closes
+    /// the transaction on exit.
+    pub fn config_transaction_accounts_close(&self) -> Result<()> {
+        if self.proposal.owner == &crate::ID {
+            close_account(&self.proposal, &self.rent_collector)?;
+        }
+
+        emit!(ConfigTransactionClosed {
+            actor: self.caller.key(),
+            multisig: self.multisig.key(),
+            transaction_index: self.transaction.index,
+            rent_collector: self.rent_collector.key(),
+        });
+
+        Ok(())
+    }
+}
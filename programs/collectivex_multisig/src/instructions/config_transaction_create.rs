@@ -1,7 +1,10 @@
 use anchor_lang::prelude::*;
 use crate::error::ErrorCode;
-use crate::state::{Multisig, ConfigTransaction, ConfigAction};
-use crate::constants::{DISCRIMINATOR, PROGRAM_CONFIG_SEED, MULTISIG_SEED, TRANSACTION_SEED};
+use crate::events::ConfigTransactionCreated;
+use crate::state::{
+    Multisig, ConfigTransaction, ConfigAction, Permission, PauseCategory, ProgramConfig,
+};
+use crate::constants::{PROGRAM_CONFIG_SEED, MULTISIG_SEED, TRANSACTION_SEED};
 
 #[derive(Accounts)]
 #[instruction(actions: Vec<ConfigAction>)]
@@ -13,6 +16,9 @@ pub struct ConfigTransactionCreate<'info> {
     )]
     pub multisig: Account<'info, Multisig>,
 
+    #[account(seeds = [PROGRAM_CONFIG_SEED], bump)]
+    pub program_config: Account<'info, ProgramConfig>,
+
     #[account(
         init,
         payer = rent_payer,
@@ -21,7 +27,7 @@ pub struct ConfigTransactionCreate<'info> {
             PROGRAM_CONFIG_SEED,
             multisig.key().as_ref(),
             TRANSACTION_SEED,
-            &multisig.transaction_index.to_le_bytes()
+            &(multisig.transaction_index + 1).to_le_bytes()
         ],
         bump
     )]
@@ -38,12 +44,26 @@ pub struct ConfigTransactionCreate<'info> {
 impl<'info> ConfigTransactionCreate<'info> {
     /// Validates the inputs and signer for creating a config transaction.
     pub fn validate(&self, actions: &[ConfigAction]) -> Result<()> {
+        self.program_config.check_not_paused(PauseCategory::Initiate)?;
+
+        // Controlled multisigs are changed directly by their config authority.
+        require!(
+            self.multisig.is_autonomous(),
+            ErrorCode::NotSupportedForControlled
+        );
+
         // Ensure the creator is a member of the multisig.
         require!(
-            self.multisig.members.contains(&self.creator.key()),
+            self.multisig.is_member(self.creator.key()).is_some(),
             ErrorCode::NotAMember
         );
 
+        // Ensure the creator is allowed to initiate transactions.
+        require!(
+            self.multisig.has_permission(self.creator.key(), Permission::Initiate),
+            ErrorCode::Unauthorized
+        );
+
         // Ensure at least one action is provided.
         require!(!actions.is_empty(), ErrorCode::NoActions);
 
@@ -54,6 +74,7 @@ impl<'info> ConfigTransactionCreate<'info> {
     pub fn create_config_transaction(
         &mut self,
         actions: Vec<ConfigAction>,
+        bumps: &ConfigTransactionCreateBumps,
     ) -> Result<()> {
         let multisig = &mut self.multisig;
         let transaction = &mut self.transaction;
@@ -65,8 +86,15 @@ impl<'info> ConfigTransactionCreate<'info> {
         transaction.multisig = multisig.key();
         transaction.creator = self.creator.key();
         transaction.index = multisig.transaction_index;
+        transaction.bump = bumps.transaction;
         transaction.actions = actions;
 
+        emit!(ConfigTransactionCreated {
+            actor: self.creator.key(),
+            multisig: multisig.key(),
+            transaction_index: multisig.transaction_index,
+        });
+
         Ok(())
     }
 }
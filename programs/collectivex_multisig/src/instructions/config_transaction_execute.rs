@@ -0,0 +1,460 @@
+use anchor_lang::prelude::*;
+
+use crate::error::ErrorCode;
+use crate::events::{
+    MemberAdded, MemberPermissionsChanged, MemberRemoved, MemberWeightChanged, ProposalExecuted,
+    RentCollectorChanged, SpendingLimitAdded, SpendingLimitRemoved, ThresholdChanged,
+    TimeLockChanged, VotingPeriodChanged,
+};
+use crate::state::{
+    ConfigAction, ConfigTransaction, Member, Multisig, PauseCategory, Permission, Permissions,
+    ProgramConfig, Proposal, ProposalStatus, SpendingLimit,
+};
+use crate::constants::{
+    MAX_SPENDING_LIMIT_DESTINATIONS, MAX_SPENDING_LIMIT_MEMBERS, MULTISIG_SEED,
+    PROGRAM_CONFIG_SEED, PROPOSAL_SEED, SPENDING_LIMIT_SEED, TRANSACTION_SEED,
+};
+use crate::utils::create_program_account;
+
+#[derive(Accounts)]
+pub struct ConfigTransactionExecute<'info> {
+    #[account(
+        mut,
+        seeds = [PROGRAM_CONFIG_SEED, MULTISIG_SEED, multisig.create_key.as_ref()],
+        bump,
+    )]
+    pub multisig: Account<'info, Multisig>,
+
+    #[account(seeds = [PROGRAM_CONFIG_SEED], bump)]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    /// The member of the multisig executing the transaction.
+    pub member: Signer<'info>,
+
+    /// The proposal that must be approved for the transaction to be executed.
+    #[account(
+        mut,
+        seeds = [
+            PROGRAM_CONFIG_SEED,
+            multisig.key().as_ref(),
+            TRANSACTION_SEED,
+            &transaction.index.to_le_bytes(),
+            PROPOSAL_SEED,
+        ],
+        bump = proposal.bump,
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    /// The config transaction holding the actions to apply.
+    #[account(
+        seeds = [
+            PROGRAM_CONFIG_SEED,
+            multisig.key().as_ref(),
+            TRANSACTION_SEED,
+            &transaction.index.to_le_bytes(),
+        ],
+        bump = transaction.bump,
+    )]
+    pub transaction: Account<'info, ConfigTransaction>,
+
+    /// Pays for the spending limit accounts created by `AddSpendingLimit` actions and for
+    /// the multisig account growing. Gets the rent of removed spending limits back when
+    /// rent reclamation is disabled.
+    #[account(mut)]
+    pub rent_payer: Option<Signer<'info>>,
+
+    /// Receives the rent freed when members are removed, and the rent of the spending
+    /// limits closed by `RemoveSpendingLimit` actions.
+    /// CHECK: validated against `multisig.rent_collector` once the actions are applied.
+    #[account(mut)]
+    pub rent_collector: Option<AccountInfo<'info>>,
+
+    pub system_program: Option<Program<'info, System>>,
+    // `remaining_accounts` must contain the spending limit accounts
+    // referenced by `AddSpendingLimit` and `RemoveSpendingLimit` actions.
+}
+
+impl<'info> ConfigTransactionExecute<'info> {
+    /// Validates whether the config transaction can be executed.
+    pub fn validate(&self) -> Result<()> {
+        self.program_config.check_not_paused(PauseCategory::Execute)?;
+
+        let Self {
+            multisig,
+            proposal,
+            member,
+            ..
+        } = self;
+
+        // Controlled multisigs are changed directly by their config authority.
+        require!(
+            multisig.is_autonomous(),
+            ErrorCode::NotSupportedForControlled
+        );
+
+        // Check if the signer is a valid member of the multisig.
+        require!(
+            multisig.is_member(member.key()).is_some(),
+            ErrorCode::NotAMember
+        );
+
+        // Check if the member has the permission to execute.
+        require!(
+            multisig.has_permission(member.key(), Permission::Execute),
+            ErrorCode::Unauthorized
+        );
+
+        // The proposal must be approved and the time lock must have elapsed.
+        match proposal.status {
+            ProposalStatus::Approved { timestamp } => {
+                require!(
+                    Clock::get()?.unix_timestamp - timestamp >= i64::from(multisig.time_lock),
+                    ErrorCode::TimeLockNotReleased
+                );
+            }
+            _ => return err!(ErrorCode::InvalidProposalStatus),
+        }
+
+        // Config transactions approved under an outdated configuration cannot be executed.
+        require!(
+            proposal.transaction_index > multisig.stale_transaction_index,
+            ErrorCode::StaleProposal
+        );
+
+        Ok(())
+    }
+
+    /// Applies the config actions to the multisig and marks the proposal as executed.
+    pub fn config_transaction_execute(
+        &mut self,
+        remaining_accounts: &'info [AccountInfo<'info>],
+        program_id: &Pubkey,
+    ) -> Result<()> {
+        let actions = self.transaction.actions.clone();
+        let mut config_changed = false;
+        let mut removed_spending_limits = Vec::new();
+
+        for action in actions.iter() {
+            config_changed |= !matches!(
+                action,
+                ConfigAction::AddSpendingLimit { .. }
+                    | ConfigAction::RemoveSpendingLimit { .. }
+                    | ConfigAction::SetRentCollector { .. }
+                    | ConfigAction::SetVotingPeriod { .. }
+            );
+
+            match action {
+                ConfigAction::AddMember { new_member } => {
+                    self.add_member(*new_member)?;
+                }
+                ConfigAction::RemoveMember { old_member } => {
+                    self.remove_member(*old_member)?;
+                }
+                ConfigAction::SetMemberPermissions { member, permissions } => {
+                    self.set_member_permissions(*member, *permissions)?;
+                }
+                ConfigAction::SetMemberWeight { member, weight } => {
+                    self.set_member_weight(*member, *weight)?;
+                }
+                ConfigAction::ChangeThreshold { new_threshold } => {
+                    self.multisig.threshold = *new_threshold;
+
+                    emit!(ThresholdChanged {
+                        actor: self.member.key(),
+                        multisig: self.multisig.key(),
+                        threshold: *new_threshold,
+                    });
+                }
+                ConfigAction::SetTimeLock { new_time_lock } => {
+                    self.multisig.time_lock = *new_time_lock;
+
+                    emit!(TimeLockChanged {
+                        actor: self.member.key(),
+                        multisig: self.multisig.key(),
+                        time_lock: *new_time_lock,
+                    });
+                }
+                ConfigAction::AddSpendingLimit {
+                    create_key,
+                    vault_index,
+                    mint,
+                    amount,
+                    period,
+                    members,
+                    destinations,
+                } => {
+                    self.add_spending_limit(
+                        remaining_accounts,
+                        program_id,
+                        SpendingLimit {
+                            version: SpendingLimit::VERSION,
+                            multisig: self.multisig.key(),
+                            create_key: *create_key,
+                            vault_index: *vault_index,
+                            mint: *mint,
+                            amount: *amount,
+                            period: *period,
+                            remaining_amount: *amount,
+                            last_reset: Clock::get()?.unix_timestamp,
+                            bump: 0,
+                            members: members.clone(),
+                            destinations: destinations.clone(),
+                            reserved: [0; 64],
+                        },
+                    )?;
+                }
+                ConfigAction::RemoveSpendingLimit { spending_limit } => {
+                    removed_spending_limits
+                        .push(self.remove_spending_limit(remaining_accounts, *spending_limit)?);
+                }
+                ConfigAction::SetRentCollector { new_rent_collector } => {
+                    self.multisig.rent_collector = *new_rent_collector;
+
+                    emit!(RentCollectorChanged {
+                        actor: self.member.key(),
+                        multisig: self.multisig.key(),
+                        rent_collector: *new_rent_collector,
+                    });
+                }
+                ConfigAction::SetVotingPeriod { new_voting_period } => {
+                    self.multisig.voting_period = *new_voting_period;
+
+                    emit!(VotingPeriodChanged {
+                        actor: self.member.key(),
+                        multisig: self.multisig.key(),
+                        voting_period: *new_voting_period,
+                    });
+                }
+            }
+        }
+
+        // The resulting configuration must be valid as a whole.
+        self.multisig.invariant()?;
+
+        // The rent collector is checked against the configuration the actions result in.
+        if let Some(rent_collector) = &self.rent_collector {
+            require!(
+                self.multisig.rent_collector == Some(rent_collector.key()),
+                ErrorCode::InvalidRentCollector
+            );
+        }
+
+        Multisig::realloc(
+            self.multisig.to_account_info(),
+            self.multisig.members.len(),
+            self.rent_payer.as_ref().map(|payer| payer.to_account_info()),
+            self.rent_collector.clone(),
+            self.system_program.as_ref().map(|program| program.to_account_info()),
+        )?;
+
+        // Closed after the realloc, so no lamports moved by hand are pending when it
+        // calls the system program.
+        // The rent of removed spending limits goes to the rent collector of the multisig,
+        // or back to the rent payer when rent reclamation is disabled.
+        if !removed_spending_limits.is_empty() {
+            let recipient = match self.multisig.rent_collector {
+                Some(_) => self.rent_collector.clone(),
+                None => self.rent_payer.as_ref().map(|payer| payer.to_account_info()),
+            }
+            .ok_or(ErrorCode::MissingAccount)?;
+
+            for spending_limit in removed_spending_limits {
+                spending_limit.close(recipient.clone())?;
+            }
+        }
+
+        // Proposals created under the previous configuration can no longer be approved.
+        if config_changed {
+            self.multisig.invalidate_prior_transactions();
+        }
+
+        self.proposal.status = ProposalStatus::Executed {
+            timestamp: Clock::get()?.unix_timestamp,
+        };
+
+        emit!(ProposalExecuted {
+            actor: self.member.key(),
+            multisig: self.multisig.key(),
+            transaction_index: self.transaction.index,
+        });
+
+        Ok(())
+    }
+
+    fn add_member(&mut self, new_member: Member) -> Result<()> {
+        self.multisig.members.push(new_member);
+
+        emit!(MemberAdded {
+            actor: self.member.key(),
+            multisig: self.multisig.key(),
+            member: new_member.key,
+            permissions: new_member.permissions.mask,
+            weight: new_member.weight,
+        });
+
+        Ok(())
+    }
+
+    fn remove_member(&mut self, old_member: Pubkey) -> Result<()> {
+        require!(
+            self.multisig.members.len() > 1,
+            ErrorCode::RemoveLastMember
+        );
+
+        let index = self.multisig
+            .is_member(old_member)
+            .ok_or(ErrorCode::NotAMember)?;
+
+        self.multisig.members.remove(index);
+
+        emit!(MemberRemoved {
+            actor: self.member.key(),
+            multisig: self.multisig.key(),
+            member: old_member,
+        });
+
+        Ok(())
+    }
+
+    fn set_member_permissions(&mut self, member: Pubkey, permissions: Permissions) -> Result<()> {
+        let index = self.multisig
+            .is_member(member)
+            .ok_or(ErrorCode::NotAMember)?;
+
+        self.multisig.members[index].permissions = permissions;
+
+        emit!(MemberPermissionsChanged {
+            actor: self.member.key(),
+            multisig: self.multisig.key(),
+            member,
+            permissions: permissions.mask,
+        });
+
+        Ok(())
+    }
+
+    fn set_member_weight(&mut self, member: Pubkey, weight: u16) -> Result<()> {
+        let index = self.multisig
+            .is_member(member)
+            .ok_or(ErrorCode::NotAMember)?;
+
+        self.multisig.members[index].weight = weight;
+
+        emit!(MemberWeightChanged {
+            actor: self.member.key(),
+            multisig: self.multisig.key(),
+            member,
+            weight,
+        });
+
+        Ok(())
+    }
+
+    fn add_spending_limit(
+        &self,
+        remaining_accounts: &'info [AccountInfo<'info>],
+        program_id: &Pubkey,
+        mut spending_limit: SpendingLimit,
+    ) -> Result<()> {
+        let rent_payer = self
+            .rent_payer
+            .as_ref()
+            .ok_or(ErrorCode::MissingAccount)?;
+        let system_program = self
+            .system_program
+            .as_ref()
+            .ok_or(ErrorCode::MissingAccount)?;
+
+        require!(
+            spending_limit.members.len() <= MAX_SPENDING_LIMIT_MEMBERS,
+            ErrorCode::ExceedsMaxMembers
+        );
+
+        require!(
+            spending_limit.destinations.len() <= MAX_SPENDING_LIMIT_DESTINATIONS,
+            ErrorCode::ExceedsMaxDestinations
+        );
+
+        require!(spending_limit.period.is_valid(), ErrorCode::InvalidPeriod);
+
+        let multisig_key = self.multisig.key();
+        let (spending_limit_key, bump) = Pubkey::find_program_address(
+            &[
+                PROGRAM_CONFIG_SEED,
+                multisig_key.as_ref(),
+                SPENDING_LIMIT_SEED,
+                spending_limit.create_key.as_ref(),
+            ],
+            program_id,
+        );
+
+        let spending_limit_info = remaining_accounts
+            .iter()
+            .find(|account| account.key == &spending_limit_key)
+            .ok_or(ErrorCode::InvalidSpendingLimit)?;
+
+        let space = SpendingLimit::size(
+            spending_limit.members.len(),
+            spending_limit.destinations.len(),
+        );
+        let signer_seeds: &[&[u8]] = &[
+            PROGRAM_CONFIG_SEED,
+            multisig_key.as_ref(),
+            SPENDING_LIMIT_SEED,
+            spending_limit.create_key.as_ref(),
+            &[bump],
+        ];
+
+        create_program_account(
+            spending_limit_info.clone(),
+            space,
+            rent_payer.to_account_info(),
+            system_program.to_account_info(),
+            signer_seeds,
+        )?;
+
+        spending_limit.bump = bump;
+        spending_limit.try_serialize(&mut &mut spending_limit_info.data.borrow_mut()[..])?;
+
+        emit!(SpendingLimitAdded {
+            actor: self.member.key(),
+            multisig: multisig_key,
+            spending_limit: spending_limit_key,
+            vault_index: spending_limit.vault_index,
+            mint: spending_limit.mint,
+            amount: spending_limit.amount,
+            period: spending_limit.period,
+        });
+
+        Ok(())
+    }
+
+    /// Checks a spending limit to remove. It is closed once every action is applied.
+    fn remove_spending_limit(
+        &self,
+        remaining_accounts: &'info [AccountInfo<'info>],
+        spending_limit_key: Pubkey,
+    ) -> Result<Account<'info, SpendingLimit>> {
+        let spending_limit_info = remaining_accounts
+            .iter()
+            .find(|account| account.key == &spending_limit_key)
+            .ok_or(ErrorCode::InvalidSpendingLimit)?;
+
+        let spending_limit = Account::<SpendingLimit>::try_from(spending_limit_info)?;
+
+        require_keys_eq!(
+            spending_limit.multisig,
+            self.multisig.key(),
+            ErrorCode::InvalidSpendingLimit
+        );
+
+        emit!(SpendingLimitRemoved {
+            actor: self.member.key(),
+            multisig: self.multisig.key(),
+            spending_limit: spending_limit_key,
+            memo: None,
+        });
+
+        Ok(spending_limit)
+    }
+}
@@ -0,0 +1,136 @@
+use anchor_lang::prelude::*;
+
+use crate::error::ErrorCode;
+use crate::events::{FeeWaiverCreated, FeeWaiverRemoved};
+use crate::state::{FeeWaiver, ProgramConfig};
+use crate::constants::{DISCRIMINATOR, FEE_WAIVER_SEED, PROGRAM_CONFIG_SEED};
+
+#[derive(Accounts)]
+#[instruction(creator: Pubkey)]
+pub struct FeeWaiverCreate<'info> {
+    #[account(seeds = [PROGRAM_CONFIG_SEED], bump)]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    #[account(
+        init,
+        payer = rent_payer,
+        space = DISCRIMINATOR + FeeWaiver::INIT_SPACE,
+        seeds = [PROGRAM_CONFIG_SEED, FEE_WAIVER_SEED, creator.as_ref()],
+        bump
+    )]
+    pub fee_waiver: Account<'info, FeeWaiver>,
+
+    /// Authority of the program config.
+    pub authority: Signer<'info>,
+
+    /// Pays the rent of the FeeWaiver account.
+    #[account(mut)]
+    pub rent_payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> FeeWaiverCreate<'info> {
+    pub fn validate(
+        &self,
+        discount_percent: u8,
+        expires_at: Option<i64>,
+        remaining_uses: Option<u32>,
+    ) -> Result<()> {
+        require_keys_eq!(
+            self.program_config.authority,
+            self.authority.key(),
+            ErrorCode::InvalidAuthority
+        );
+
+        require!(
+            (1..=100).contains(&discount_percent),
+            ErrorCode::InvalidDiscount
+        );
+
+        if let Some(expires_at) = expires_at {
+            require!(
+                expires_at > Clock::get()?.unix_timestamp,
+                ErrorCode::FeeWaiverExpired
+            );
+        }
+
+        require!(remaining_uses != Some(0), ErrorCode::FeeWaiverUsedUp);
+
+        Ok(())
+    }
+
+    pub fn fee_waiver_create(
+        &mut self,
+        creator: Pubkey,
+        discount_percent: u8,
+        expires_at: Option<i64>,
+        remaining_uses: Option<u32>,
+        bumps: &FeeWaiverCreateBumps,
+    ) -> Result<()> {
+        self.fee_waiver.set_inner(FeeWaiver {
+            version: FeeWaiver::VERSION,
+            creator,
+            discount_percent,
+            expires_at,
+            remaining_uses,
+            bump: bumps.fee_waiver,
+            reserved: [0; 64],
+        });
+
+        emit!(FeeWaiverCreated {
+            actor: self.authority.key(),
+            fee_waiver: self.fee_waiver.key(),
+            creator,
+            discount_percent,
+            expires_at,
+            remaining_uses,
+        });
+
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct FeeWaiverRemove<'info> {
+    #[account(seeds = [PROGRAM_CONFIG_SEED], bump)]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    #[account(
+        mut,
+        close = rent_collector,
+        seeds = [PROGRAM_CONFIG_SEED, FEE_WAIVER_SEED, fee_waiver.creator.as_ref()],
+        bump = fee_waiver.bump,
+    )]
+    pub fee_waiver: Account<'info, FeeWaiver>,
+
+    /// Authority of the program config.
+    pub authority: Signer<'info>,
+
+    /// Receives the rent of the closed FeeWaiver account.
+    /// CHECK: This account will receive the rent.
+    #[account(mut)]
+    pub rent_collector: AccountInfo<'info>,
+}
+
+impl<'info> FeeWaiverRemove<'info> {
+    pub fn check_current_authority(&self) -> Result<()> {
+        require_keys_eq!(
+            self.program_config.authority,
+            self.authority.key(),
+            ErrorCode::InvalidAuthority
+        );
+
+        Ok(())
+    }
+
+    pub fn fee_waiver_remove(&mut self) -> Result<()> {
+        emit!(FeeWaiverRemoved {
+            actor: self.authority.key(),
+            fee_waiver: self.fee_waiver.key(),
+            creator: self.fee_waiver.creator,
+        });
+
+        Ok(())
+    }
+}
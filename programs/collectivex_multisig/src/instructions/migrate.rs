@@ -0,0 +1,200 @@
+use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
+
+use crate::constants::{
+    DISCRIMINATOR, MULTISIG_SEED, PROGRAM_CONFIG_SEED, PROPOSAL_SEED, SPENDING_LIMIT_SEED,
+    TRANSACTION_SEED,
+};
+use crate::error::ErrorCode;
+use crate::events::AccountMigrated;
+use crate::state::legacy::{MultisigV0, ProgramConfigV0, ProposalV0, SpendingLimitV0};
+use crate::state::{Multisig, ProgramConfig, Proposal, SpendingLimit};
+use crate::utils::realloc;
+
+#[derive(Accounts)]
+pub struct Migrate<'info> {
+    /// The account to upgrade to the current layout.
+    /// CHECK: owner, discriminator and address are validated against the legacy layout.
+    #[account(mut)]
+    pub account: AccountInfo<'info>,
+
+    /// Pays for the account growing.
+    #[account(mut)]
+    pub rent_payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> Migrate<'info> {
+    pub fn migrate_program_config(&self) -> Result<()> {
+        let address = Pubkey::find_program_address(&[PROGRAM_CONFIG_SEED], &crate::ID).0;
+        require_keys_eq!(self.account.key(), address, ErrorCode::NotMigratable);
+
+        // The legacy layout has no variable-length fields, so its size identifies it.
+        let is_legacy = self.account.data_len() == ProgramConfigV0::SIZE;
+
+        self.upgrade(
+            |config: &ProgramConfig| config.version == ProgramConfig::VERSION,
+            |_: &ProgramConfigV0| is_legacy,
+            ProgramConfig::from,
+            |_| DISCRIMINATOR + ProgramConfig::INIT_SPACE,
+        )?;
+
+        self.emit(ProgramConfig::VERSION);
+
+        Ok(())
+    }
+
+    pub fn migrate_multisig(&self, create_key: Pubkey) -> Result<()> {
+        let address = Pubkey::find_program_address(
+            &[PROGRAM_CONFIG_SEED, MULTISIG_SEED, create_key.as_ref()],
+            &crate::ID,
+        )
+        .0;
+        require_keys_eq!(address, self.account.key(), ErrorCode::NotMigratable);
+
+        self.upgrade(
+            |multisig: &Multisig| {
+                multisig.version == Multisig::VERSION && multisig.create_key == create_key
+            },
+            // The original program left `create_key` zeroed until it started storing it.
+            |legacy: &MultisigV0| {
+                legacy.create_key == Pubkey::default() || legacy.create_key == create_key
+            },
+            |legacy| Multisig {
+                create_key,
+                ..Multisig::from(legacy)
+            },
+            |multisig| Multisig::size(multisig.members.len()),
+        )?;
+
+        self.emit(Multisig::VERSION);
+
+        Ok(())
+    }
+
+    pub fn migrate_spending_limit(&self) -> Result<()> {
+        let address = |multisig: &Pubkey, create_key: &Pubkey, bump: u8| {
+            Pubkey::create_program_address(
+                &[
+                    PROGRAM_CONFIG_SEED,
+                    multisig.as_ref(),
+                    SPENDING_LIMIT_SEED,
+                    create_key.as_ref(),
+                    &[bump],
+                ],
+                &crate::ID,
+            )
+            .ok()
+        };
+
+        self.upgrade(
+            |limit: &SpendingLimit| {
+                limit.version == SpendingLimit::VERSION
+                    && address(&limit.multisig, &limit.create_key, limit.bump)
+                        == Some(self.account.key())
+            },
+            |legacy: &SpendingLimitV0| {
+                address(&legacy.multisig, &legacy.create_key, legacy.bump)
+                    == Some(self.account.key())
+            },
+            SpendingLimit::from,
+            |limit| SpendingLimit::size(limit.members.len(), limit.destinations.len()),
+        )?;
+
+        self.emit(SpendingLimit::VERSION);
+
+        Ok(())
+    }
+
+    pub fn migrate_proposal(&self) -> Result<()> {
+        let address = |multisig: &Pubkey, transaction_index: u64, bump: u8| {
+            Pubkey::create_program_address(
+                &[
+                    PROGRAM_CONFIG_SEED,
+                    multisig.as_ref(),
+                    TRANSACTION_SEED,
+                    &transaction_index.to_le_bytes(),
+                    PROPOSAL_SEED,
+                    &[bump],
+                ],
+                &crate::ID,
+            )
+            .ok()
+        };
+
+        let now = Clock::get()?.unix_timestamp;
+
+        self.upgrade(
+            |proposal: &Proposal| {
+                proposal.version == Proposal::VERSION
+                    && address(&proposal.multisig, proposal.transaction_index, proposal.bump)
+                        == Some(self.account.key())
+            },
+            |legacy: &ProposalV0| {
+                address(&legacy.multisig, legacy.transaction_index, legacy.bump)
+                    == Some(self.account.key())
+            },
+            |legacy| legacy.upgrade(now),
+            // Keeps the room left for the votes of every member, plus the new fields.
+            |_| self.account.data_len() + Proposal::size(0) - ProposalV0::SIZE,
+        )?;
+
+        self.emit(Proposal::VERSION);
+
+        Ok(())
+    }
+
+    /// Rewrites the account from the legacy layout `L` to the current layout `T`.
+    ///
+    /// `is_current` recognizes accounts already migrated, and `is_legacy` checks that the
+    /// legacy fields belong to the account, so an account is never upgraded twice.
+    fn upgrade<T, L>(
+        &self,
+        is_current: impl Fn(&T) -> bool,
+        is_legacy: impl Fn(&L) -> bool,
+        convert: impl FnOnce(L) -> T,
+        space: impl Fn(&T) -> usize,
+    ) -> Result<()>
+    where
+        T: AccountSerialize + AccountDeserialize + Discriminator,
+        L: AnchorDeserialize,
+    {
+        require_keys_eq!(*self.account.owner, crate::ID, ErrorCode::NotMigratable);
+
+        let legacy = {
+            let data = self.account.try_borrow_data()?;
+            require!(data.starts_with(&T::DISCRIMINATOR), ErrorCode::NotMigratable);
+
+            if T::try_deserialize(&mut &data[..]).is_ok_and(|current| is_current(&current)) {
+                return err!(ErrorCode::AlreadyMigrated);
+            }
+
+            // Accounts may be larger than their legacy fields, so trailing bytes are ignored.
+            L::deserialize(&mut &data[DISCRIMINATOR..])
+                .ok()
+                .filter(|legacy| is_legacy(legacy))
+                .ok_or(ErrorCode::NotMigratable)?
+        };
+
+        let current = convert(legacy);
+        realloc(
+            self.account.clone(),
+            space(&current),
+            Some(self.rent_payer.to_account_info()),
+            None,
+            Some(self.system_program.to_account_info()),
+        )?;
+        current.try_serialize(&mut &mut self.account.try_borrow_mut_data()?[..])?;
+
+        Ok(())
+    }
+
+    fn emit(&self, version: u8) {
+        emit!(AccountMigrated {
+            actor: self.rent_payer.key(),
+            account: self.account.key(),
+            version,
+        });
+    }
+}
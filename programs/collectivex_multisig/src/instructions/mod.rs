@@ -3,15 +3,31 @@ pub mod multisig;
 pub mod multisig_add_spending_limit;
 pub mod multisig_remove_spending_limit;
 pub mod config_transaction_create;
+pub mod config_transaction_execute;
+pub mod config_transaction_accounts_close;
 pub mod proposal_create;
 pub mod proposal_activate;
 pub mod proposal_voting;
+pub mod proposal_expire;
+pub mod spending_limit_use;
+pub mod vault_transaction_create;
+pub mod vault_transaction_execute;
+pub mod migrate;
+pub mod fee_waiver;
 
 pub use config::*;
 pub use multisig::*;
 pub use multisig_add_spending_limit::*;
 pub use multisig_remove_spending_limit::*;
 pub use config_transaction_create::*;
+pub use config_transaction_execute::*;
+pub use config_transaction_accounts_close::*;
 pub use proposal_create::*;
 pub use proposal_activate::*;
 pub use proposal_voting::*;
+pub use proposal_expire::*;
+pub use spending_limit_use::*;
+pub use vault_transaction_create::*;
+pub use vault_transaction_execute::*;
+pub use migrate::*;
+pub use fee_waiver::*;
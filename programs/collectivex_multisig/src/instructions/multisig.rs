@@ -1,16 +1,24 @@
 use anchor_lang::prelude::*;
 use anchor_lang::system_program::{transfer, Transfer};
+use anchor_spl::token::{transfer_checked, Mint, Token, TokenAccount, TransferChecked};
 
 use crate::error::ErrorCode;
-use crate::state::{ProgramConfig, Multisig};
-use crate::constants::{DISCRIMINATOR, PROGRAM_CONFIG_SEED, MULTISIG_SEED};
+use crate::events::{
+    ConfigAuthorityChanged, FeeWaiverUsed, MemberAdded, MemberPermissionsChanged, MemberRemoved,
+    MemberWeightChanged, MultisigCreated, PendingConfigAuthorityChanged, RentCollectorChanged,
+    ThresholdChanged, TimeLockChanged, VotingPeriodChanged,
+};
+use crate::state::{FeeWaiver, ProgramConfig, Member, Multisig, PauseCategory, Permissions};
+use crate::constants::{FEE_WAIVER_SEED, PROGRAM_CONFIG_SEED, MULTISIG_SEED};
+use crate::utils::associated_token_address;
 
 #[derive(Accounts)]
+#[instruction(config_authority: Pubkey, threshold: u16, members: Vec<Member>)]
 pub struct MultisigCreate<'info> {
     #[account(
         init,
         payer = creator,
-        space = DISCRIMINATOR + Multisig::INIT_SPACE,
+        space = Multisig::size(members.len()),
         seeds = [PROGRAM_CONFIG_SEED, MULTISIG_SEED, create_key.key().as_ref()],
         bump
     )]
@@ -30,6 +38,36 @@ pub struct MultisigCreate<'info> {
     pub creator: Signer<'info>,
 
     pub system_program: Program<'info, System>,
+
+    /// Mint of the creation fee. Required when the program config sets a fee mint.
+    pub fee_mint: Option<Account<'info, Mint>>,
+
+    /// Creator token account the fee is paid from. Required when the program config sets a fee mint.
+    #[account(
+        mut,
+        token::mint = fee_mint,
+        token::authority = creator,
+    )]
+    pub creator_token_account: Option<Account<'info, TokenAccount>>,
+
+    /// Associated token account of the treasury. Required when the program config sets a fee mint.
+    #[account(
+        mut,
+        token::mint = fee_mint,
+        token::authority = treasury,
+    )]
+    pub treasury_token_account: Option<Account<'info, TokenAccount>>,
+
+    /// Required when the program config sets a fee mint.
+    pub token_program: Option<Program<'info, Token>>,
+
+    /// Fee waiver of the creator, applied to the creation fee when passed.
+    #[account(
+        mut,
+        seeds = [PROGRAM_CONFIG_SEED, FEE_WAIVER_SEED, creator.key().as_ref()],
+        bump = fee_waiver.bump,
+    )]
+    pub fee_waiver: Option<Account<'info, FeeWaiver>>,
 }
 
 impl <'info> MultisigCreate<'info> {
@@ -37,22 +75,66 @@ impl <'info> MultisigCreate<'info> {
         &mut self,
         config_authority: Pubkey,
         threshold: u16,
-        members: Vec<Pubkey>,
+        members: Vec<Member>,
         time_lock: u32
     ) -> Result<()> {
+        self.program_config.check_not_paused(PauseCategory::Create)?;
+
         require_keys_eq!(
             self.treasury.key(),
             self.program_config.treasury,
             ErrorCode::InvalidTreasury
         );
 
+        self.multisig.version = Multisig::VERSION;
+        self.multisig.create_key = self.create_key.key();
         self.multisig.config_authority = config_authority;
         self.multisig.threshold = threshold;
         self.multisig.time_lock = time_lock;
         self.multisig.members = members;
+        self.multisig.invariant()?;
+
+        let mut creation_fee = self.program_config.creation_fee;
+
+        // A use of the waiver is only spent when it lowers the fee, not while fees are off.
+        if let Some(fee_waiver) = self
+            .fee_waiver
+            .as_mut()
+            .filter(|fee_waiver| fee_waiver.discounted_fee(creation_fee) < creation_fee)
+        {
+            fee_waiver.consume(Clock::get()?.unix_timestamp)?;
+            creation_fee = fee_waiver.discounted_fee(creation_fee);
+
+            emit!(FeeWaiverUsed {
+                actor: self.creator.key(),
+                fee_waiver: fee_waiver.key(),
+                multisig: self.multisig.key(),
+                creation_fee,
+                remaining_uses: fee_waiver.remaining_uses,
+            });
+        }
 
         // Transfer the creation fee to the treasury
-        if self.program_config.creation_fee > 0 {
+        if creation_fee > 0 {
+            self.collect_creation_fee(creation_fee)?;
+        }
+
+        emit!(MultisigCreated {
+            actor: self.creator.key(),
+            multisig: self.multisig.key(),
+            create_key: self.multisig.create_key,
+            config_authority: self.multisig.config_authority,
+            threshold: self.multisig.threshold,
+            members: self.multisig.members.iter().map(|m| m.key).collect(),
+            time_lock: self.multisig.time_lock,
+        });
+
+        Ok(())
+    }
+
+    /// Pays `creation_fee` in SOL, or in `program_config.fee_mint` tokens when one is set.
+    fn collect_creation_fee(&self, creation_fee: u64) -> Result<()> {
+        let Some(fee_mint) = self.program_config.fee_mint else {
             let cpi_accounts = Transfer {
                 from: self.creator.to_account_info(),
                 to: self.treasury.to_account_info(),
@@ -60,10 +142,41 @@ impl <'info> MultisigCreate<'info> {
 
             let cpi_program = self.system_program.to_account_info();
             let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
-            transfer(cpi_ctx, self.program_config.creation_fee)?;
-        }
+            return transfer(cpi_ctx, creation_fee);
+        };
 
-        Ok(())
+        let mint = self.fee_mint.as_ref().ok_or(ErrorCode::MissingAccount)?;
+        let creator_token_account = self
+            .creator_token_account
+            .as_ref()
+            .ok_or(ErrorCode::MissingAccount)?;
+        let treasury_token_account = self
+            .treasury_token_account
+            .as_ref()
+            .ok_or(ErrorCode::MissingAccount)?;
+        let token_program = self
+            .token_program
+            .as_ref()
+            .ok_or(ErrorCode::MissingAccount)?;
+
+        require_keys_eq!(mint.key(), fee_mint, ErrorCode::InvalidMint);
+
+        // The fee always lands in the canonical token account of the treasury.
+        require_keys_eq!(
+            treasury_token_account.key(),
+            associated_token_address(&self.treasury.key(), &fee_mint),
+            ErrorCode::InvalidTreasury
+        );
+
+        let cpi_accounts = TransferChecked {
+            from: creator_token_account.to_account_info(),
+            mint: mint.to_account_info(),
+            to: treasury_token_account.to_account_info(),
+            authority: self.creator.to_account_info(),
+        };
+
+        let cpi_ctx = CpiContext::new(token_program.to_account_info(), cpi_accounts);
+        transfer_checked(cpi_ctx, creation_fee, mint.decimals)
     }
 }
 
@@ -79,35 +192,59 @@ pub struct MultisigEdit<'info> {
     #[account(mut)]
     pub config_authority: Signer<'info>,
 
+    /// Pays for the multisig account growing when a member is added.
+    #[account(mut)]
+    pub rent_payer: Option<Signer<'info>>,
+
+    /// Receives the rent freed when a member is removed.
+    /// CHECK: validated against `multisig.rent_collector`.
+    #[account(mut)]
+    pub rent_collector: Option<AccountInfo<'info>>,
+
     pub system_program: Program<'info, System>,
 }
 
 impl <'info> MultisigEdit<'info> {
     pub fn check_current_authority(&self) -> Result<()> {
-        require_keys_eq!(
-            self.multisig.config_authority,
-            self.config_authority.key(),
-            ErrorCode::InvalidAuthority
-        );
+        self.multisig.check_config_authority(self.config_authority.key())?;
+
+        if let Some(rent_collector) = &self.rent_collector {
+            require!(
+                self.multisig.rent_collector == Some(rent_collector.key()),
+                ErrorCode::InvalidRentCollector
+            );
+        }
 
         Ok(())
     }
 
+    /// Resizes the multisig account to fit its members.
+    fn realloc(&self) -> Result<()> {
+        Multisig::realloc(
+            self.multisig.to_account_info(),
+            self.multisig.members.len(),
+            self.rent_payer.as_ref().map(|payer| payer.to_account_info()),
+            self.rent_collector.clone(),
+            Some(self.system_program.to_account_info()),
+        )
+    }
+
     pub fn add_multisig_member(
         &mut self,
-        new_member: Pubkey
+        new_member: Member
     ) -> Result<()> {
-        require!(
-            !self.multisig.members.contains(&new_member),
-            ErrorCode::DuplicateMember
-        );
-
-        require!(
-            self.multisig.members.len() > 9,
-            ErrorCode::ExceedsMaxMembers
-        );
-
         self.multisig.members.push(new_member);
+        self.multisig.invariant()?;
+        self.multisig.invalidate_prior_transactions();
+        self.realloc()?;
+
+        emit!(MemberAdded {
+            actor: self.config_authority.key(),
+            multisig: self.multisig.key(),
+            member: new_member.key,
+            permissions: new_member.permissions.mask,
+            weight: new_member.weight,
+        });
 
         Ok(())
     }
@@ -117,12 +254,24 @@ impl <'info> MultisigEdit<'info> {
         old_member: Pubkey
     ) -> Result<()> {
         let index = self.multisig
-            .members
-            .iter()
-            .position(|&x| x == old_member)
+            .is_member(old_member)
             .ok_or(ErrorCode::NotAMember)?;
 
+        require!(
+            self.multisig.members.len() > 1,
+            ErrorCode::RemoveLastMember
+        );
+
         self.multisig.members.remove(index);
+        self.multisig.invariant()?;
+        self.multisig.invalidate_prior_transactions();
+        self.realloc()?;
+
+        emit!(MemberRemoved {
+            actor: self.config_authority.key(),
+            multisig: self.multisig.key(),
+            member: old_member,
+        });
 
         Ok(())
     }
@@ -132,16 +281,205 @@ impl <'info> MultisigEdit<'info> {
         new_time_lock: u32
     ) -> Result<()> {
         self.multisig.time_lock = new_time_lock;
+        self.multisig.invariant()?;
+        self.multisig.invalidate_prior_transactions();
+
+        emit!(TimeLockChanged {
+            actor: self.config_authority.key(),
+            multisig: self.multisig.key(),
+            time_lock: new_time_lock,
+        });
+
+        Ok(())
+    }
+
+    pub fn change_multisig_threshold(
+        &mut self,
+        new_threshold: u16
+    ) -> Result<()> {
+        self.multisig.threshold = new_threshold;
+        self.multisig.invariant()?;
+        self.multisig.invalidate_prior_transactions();
+
+        emit!(ThresholdChanged {
+            actor: self.config_authority.key(),
+            multisig: self.multisig.key(),
+            threshold: new_threshold,
+        });
+
+        Ok(())
+    }
+
+    pub fn set_multisig_member_permissions(
+        &mut self,
+        member: Pubkey,
+        permissions: Permissions
+    ) -> Result<()> {
+        let index = self.multisig
+            .is_member(member)
+            .ok_or(ErrorCode::NotAMember)?;
+
+        self.multisig.members[index].permissions = permissions;
+        self.multisig.invariant()?;
+        self.multisig.invalidate_prior_transactions();
+
+        emit!(MemberPermissionsChanged {
+            actor: self.config_authority.key(),
+            multisig: self.multisig.key(),
+            member,
+            permissions: permissions.mask,
+        });
+
+        Ok(())
+    }
+
+    pub fn set_multisig_member_weight(
+        &mut self,
+        member: Pubkey,
+        weight: u16
+    ) -> Result<()> {
+        let index = self.multisig
+            .is_member(member)
+            .ok_or(ErrorCode::NotAMember)?;
+
+        self.multisig.members[index].weight = weight;
+        self.multisig.invariant()?;
+        self.multisig.invalidate_prior_transactions();
+
+        emit!(MemberWeightChanged {
+            actor: self.config_authority.key(),
+            multisig: self.multisig.key(),
+            member,
+            weight,
+        });
 
         Ok(())
     }
 
-    pub fn set_multisig_config_authority(
+    /// Proposes `new_config_authority` to take over the multisig, once it accepts.
+    pub fn propose_multisig_config_authority(
         &mut self,
         new_config_authority: Pubkey
     ) -> Result<()> {
-        self.multisig.config_authority = new_config_authority;
+        // Handing the multisig to its members goes through `renounce_multisig_config_authority`.
+        require!(
+            new_config_authority != Pubkey::default(),
+            ErrorCode::InvalidAuthority
+        );
+
+        self.multisig.pending_config_authority = Some(new_config_authority);
+
+        emit!(PendingConfigAuthorityChanged {
+            actor: self.config_authority.key(),
+            multisig: self.multisig.key(),
+            pending_config_authority: self.multisig.pending_config_authority,
+        });
 
         Ok(())
     }
-}
\ No newline at end of file
+
+    pub fn cancel_multisig_config_authority(&mut self) -> Result<()> {
+        require!(
+            self.multisig.pending_config_authority.is_some(),
+            ErrorCode::NoPendingAuthority
+        );
+
+        self.multisig.pending_config_authority = None;
+
+        emit!(PendingConfigAuthorityChanged {
+            actor: self.config_authority.key(),
+            multisig: self.multisig.key(),
+            pending_config_authority: None,
+        });
+
+        Ok(())
+    }
+
+    /// Makes the multisig autonomous, so its configuration only changes through config
+    /// transactions.
+    pub fn renounce_multisig_config_authority(&mut self) -> Result<()> {
+        self.multisig.config_authority = Pubkey::default();
+        self.multisig.pending_config_authority = None;
+        self.multisig.invariant()?;
+
+        emit!(ConfigAuthorityChanged {
+            actor: self.config_authority.key(),
+            multisig: self.multisig.key(),
+            config_authority: Pubkey::default(),
+        });
+
+        Ok(())
+    }
+
+    pub fn set_multisig_rent_collector(
+        &mut self,
+        new_rent_collector: Option<Pubkey>
+    ) -> Result<()> {
+        self.multisig.rent_collector = new_rent_collector;
+        self.multisig.invariant()?;
+
+        emit!(RentCollectorChanged {
+            actor: self.config_authority.key(),
+            multisig: self.multisig.key(),
+            rent_collector: new_rent_collector,
+        });
+
+        Ok(())
+    }
+
+    pub fn set_multisig_voting_period(
+        &mut self,
+        new_voting_period: Option<u32>
+    ) -> Result<()> {
+        self.multisig.voting_period = new_voting_period;
+        self.multisig.invariant()?;
+
+        emit!(VotingPeriodChanged {
+            actor: self.config_authority.key(),
+            multisig: self.multisig.key(),
+            voting_period: new_voting_period,
+        });
+
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct MultisigAcceptConfigAuthority<'info> {
+    #[account(
+        mut,
+        seeds = [PROGRAM_CONFIG_SEED, MULTISIG_SEED, multisig.create_key.as_ref()],
+        bump,
+    )]
+    pub multisig: Account<'info, Multisig>,
+
+    /// The pending config authority of the multisig.
+    pub new_config_authority: Signer<'info>,
+}
+
+impl <'info> MultisigAcceptConfigAuthority<'info> {
+    pub fn accept_multisig_config_authority(&mut self) -> Result<()> {
+        let pending_config_authority = self
+            .multisig
+            .pending_config_authority
+            .ok_or(ErrorCode::NoPendingAuthority)?;
+
+        require_keys_eq!(
+            pending_config_authority,
+            self.new_config_authority.key(),
+            ErrorCode::InvalidAuthority
+        );
+
+        self.multisig.config_authority = pending_config_authority;
+        self.multisig.pending_config_authority = None;
+        self.multisig.invariant()?;
+
+        emit!(ConfigAuthorityChanged {
+            actor: self.new_config_authority.key(),
+            multisig: self.multisig.key(),
+            config_authority: pending_config_authority,
+        });
+
+        Ok(())
+    }
+}
@@ -1,11 +1,22 @@
 use anchor_lang::prelude::*;
-use anchor_lang::system_program::{transfer, Transfer};
 
 use crate::error::ErrorCode;
-use crate::state::{ProgramConfig, Multisig, SpendingLimit};
-use crate::constants::{DISCRIMINATOR, PROGRAM_CONFIG_SEED, MULTISIG_SEED, SPENDING_LIMIT_SEED};
+use crate::events::SpendingLimitAdded;
+use crate::state::{Multisig, Period, SpendingLimit};
+use crate::constants::{
+    MAX_SPENDING_LIMIT_DESTINATIONS, MAX_SPENDING_LIMIT_MEMBERS, MULTISIG_SEED,
+    PROGRAM_CONFIG_SEED, SPENDING_LIMIT_SEED,
+};
 
 #[derive(Accounts)]
+#[instruction(
+    vault_index: u8,
+    mint: Pubkey,
+    amount: u64,
+    period: Period,
+    members: Vec<Pubkey>,
+    destinations: Vec<Pubkey>,
+)]
 pub struct MultisigAddSpendingLimit<'info> {
     #[account(
         mut,
@@ -25,7 +36,7 @@ pub struct MultisigAddSpendingLimit<'info> {
     #[account(
         init,
         payer = config_authority,
-        space = DISCRIMINATOR + SpendingLimit::INIT_SPACE,
+        space = SpendingLimit::size(members.len(), destinations.len()),
         seeds = [
             PROGRAM_CONFIG_SEED,
             multisig.key().as_ref(),
@@ -41,42 +52,57 @@ pub struct MultisigAddSpendingLimit<'info> {
 
 impl<'info> MultisigAddSpendingLimit<'info> {
     pub fn check_current_authority(&self) -> Result<()> {
-        require_keys_eq!(
-            self.multisig.config_authority,
-            self.config_authority.key(),
-            ErrorCode::InvalidAuthority
-        );
-
-        Ok(())
+        self.multisig.check_config_authority(self.config_authority.key())
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub fn multisig_add_spending_limit(
         &mut self,
         bumps: &MultisigAddSpendingLimitBumps,
         vault_index: u8,
         mint: Pubkey,
         amount: u64,
+        period: Period,
         members: Vec<Pubkey>,
         destinations: Vec<Pubkey>,
     ) -> Result<()> {
         // Validate spending limit members size
         require!(
-            members.len() <= 10,
+            members.len() <= MAX_SPENDING_LIMIT_MEMBERS,
             ErrorCode::ExceedsMaxMembers
         );
 
+        require!(
+            destinations.len() <= MAX_SPENDING_LIMIT_DESTINATIONS,
+            ErrorCode::ExceedsMaxDestinations
+        );
+
+        require!(period.is_valid(), ErrorCode::InvalidPeriod);
+
         // Initialize spending limit account
+        self.spending_limit.version = SpendingLimit::VERSION;
         self.spending_limit.multisig = self.multisig.key();
         self.spending_limit.create_key = self.create_key.key();
         self.spending_limit.vault_index = vault_index;
         self.spending_limit.mint = mint;
         self.spending_limit.amount = amount;
+        self.spending_limit.period = period;
         self.spending_limit.remaining_amount = amount; // Start with full amount
         self.spending_limit.last_reset = Clock::get()?.unix_timestamp;
         self.spending_limit.bump = bumps.spending_limit;
         self.spending_limit.members = members;
         self.spending_limit.destinations = destinations;
 
+        emit!(SpendingLimitAdded {
+            actor: self.config_authority.key(),
+            multisig: self.multisig.key(),
+            spending_limit: self.spending_limit.key(),
+            vault_index,
+            mint,
+            amount,
+            period,
+        });
+
         Ok(())
     }
 }
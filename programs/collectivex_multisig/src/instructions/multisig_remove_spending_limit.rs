@@ -1,7 +1,8 @@
 use anchor_lang::prelude::*;
 use crate::error::ErrorCode;
+use crate::events::SpendingLimitRemoved;
 use crate::state::{Multisig, SpendingLimit};
-use crate::constants::{PROGRAM_CONFIG_SEED, MULTISIG_SEED, SPENDING_LIMIT_SEED};
+use crate::constants::{PROGRAM_CONFIG_SEED, MULTISIG_SEED, MAX_MEMO_LEN};
 
 #[derive(Accounts)]
 pub struct MultisigRemoveSpendingLimit<'info> {
@@ -33,18 +34,25 @@ pub struct MultisigRemoveSpendingLimit<'info> {
 impl<'info> MultisigRemoveSpendingLimit<'info> {
     /// Validates the authority of the current signer.
     pub fn check_current_authority(&self) -> Result<()> {
-        require_keys_eq!(
-            self.multisig.config_authority,
-            self.config_authority.key(),
-            ErrorCode::InvalidAuthority
-        );
-        Ok(())
+        self.multisig.check_config_authority(self.config_authority.key())
     }
 
     pub fn multisig_remove_spending_limit(
         &mut self,
-        _memo: Option<String>,
+        memo: Option<String>,
     ) -> Result<()> {
+        require!(
+            memo.as_ref().map_or(0, String::len) <= MAX_MEMO_LEN,
+            ErrorCode::MemoTooLong
+        );
+
+        emit!(SpendingLimitRemoved {
+            actor: self.config_authority.key(),
+            multisig: self.multisig.key(),
+            spending_limit: self.spending_limit.key(),
+            memo,
+        });
+
         msg!("Spending limit removed successfully.");
         Ok(())
     }
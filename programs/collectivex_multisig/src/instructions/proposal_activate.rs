@@ -1,6 +1,7 @@
-use crate::constants::{DISCRIMINATOR, MULTISIG_SEED, PROGRAM_CONFIG_SEED, TRANSACTION_SEED, PROPOSAL_SEED};
-use crate::state::{Multisig, Proposal, ProposalStatus};
+use crate::constants::{MULTISIG_SEED, PROGRAM_CONFIG_SEED, TRANSACTION_SEED, PROPOSAL_SEED};
+use crate::state::{Multisig, PauseCategory, Permission, ProgramConfig, Proposal, ProposalStatus};
 use crate::error::ErrorCode;
+use crate::events::ProposalActivated;
 use anchor_lang::prelude::*;
 
 #[derive(Accounts)]
@@ -11,6 +12,9 @@ pub struct ProposalActivate<'info> {
     )]
     pub multisig: Account<'info, Multisig>,
 
+    #[account(seeds = [PROGRAM_CONFIG_SEED], bump)]
+    pub program_config: Account<'info, ProgramConfig>,
+
     #[account(mut)]
     pub member: Signer<'info>,
 
@@ -31,6 +35,8 @@ pub struct ProposalActivate<'info> {
 impl<'info> ProposalActivate<'info> {
     /// Validates whether the proposal can be activated.
     pub fn validate(&self) -> Result<()> {
+        self.program_config.check_not_paused(PauseCategory::Initiate)?;
+
         let Self {
             multisig,
             proposal,
@@ -40,19 +46,19 @@ impl<'info> ProposalActivate<'info> {
 
         // Check if the signer is a valid member of the multisig.
         require!(
-            multisig.members.contains(&member.key()),
+            multisig.is_member(member.key()).is_some(),
             ErrorCode::NotAMember
         );
 
-        // // Check if the member has the necessary permissions.
-        // require!(
-        //     multisig.has_permission(member.key(), Permission::Initiate),
-        //     ErrorCode::Unauthorized
-        // );
+        // Check if the member has the necessary permissions.
+        require!(
+            multisig.has_permission(member.key(), Permission::Initiate),
+            ErrorCode::Unauthorized
+        );
 
         // Check if the proposal is in the `Draft` status.
         require!(
-            matches!(proposal.status, ProposalStatus::Draft),
+            matches!(proposal.status, ProposalStatus::Draft { .. }),
             ErrorCode::InvalidProposalStatus
         );
 
@@ -67,7 +73,14 @@ impl<'info> ProposalActivate<'info> {
 
     /// Updates the status of the proposal to `Active`.
     pub fn proposal_activate(&mut self) -> Result<()> {
-        self.proposal.status = ProposalStatus::Active;
+        self.proposal.activate(self.multisig.voting_period)?;
+
+        emit!(ProposalActivated {
+            actor: self.member.key(),
+            multisig: self.multisig.key(),
+            transaction_index: self.proposal.transaction_index,
+            expires_at: self.proposal.expires_at,
+        });
 
         Ok(())
     }
@@ -1,6 +1,7 @@
-use crate::constants::{DISCRIMINATOR, MULTISIG_SEED, PROGRAM_CONFIG_SEED, TRANSACTION_SEED, PROPOSAL_SEED};
-use crate::state::{Multisig, Proposal, ProposalStatus};
+use crate::constants::{MULTISIG_SEED, PROGRAM_CONFIG_SEED, TRANSACTION_SEED, PROPOSAL_SEED};
+use crate::state::{Multisig, PauseCategory, Permission, ProgramConfig, Proposal, ProposalStatus};
 use crate::error::ErrorCode;
+use crate::events::ProposalCreated;
 use anchor_lang::prelude::*;
 
 #[derive(Accounts)]
@@ -12,6 +13,9 @@ pub struct ProposalCreate<'info> {
     )]
     pub multisig: Account<'info, Multisig>,
 
+    #[account(seeds = [PROGRAM_CONFIG_SEED], bump)]
+    pub program_config: Account<'info, ProgramConfig>,
+
     #[account(
         init,
         payer = rent_payer,
@@ -34,12 +38,32 @@ pub struct ProposalCreate<'info> {
 }
 
 impl <'info> ProposalCreate<'info> {
-    pub fn validate(&self) -> Result<()> {
+    pub fn validate(&self, transaction_index: u64) -> Result<()> {
+        self.program_config.check_not_paused(PauseCategory::Initiate)?;
+
         // Ensure the creator is a member of the multisig.
         require!(
-            self.multisig.members.contains(&self.creator.key()),
+            self.multisig.is_member(self.creator.key()).is_some(),
             ErrorCode::NotAMember
         );
+
+        // Ensure the creator is allowed to initiate transactions.
+        require!(
+            self.multisig.has_permission(self.creator.key(), Permission::Initiate),
+            ErrorCode::Unauthorized
+        );
+
+        // The transaction must have been created.
+        require!(
+            transaction_index > 0 && transaction_index <= self.multisig.transaction_index,
+            ErrorCode::InvalidTransactionIndex
+        );
+
+        // Transactions created under a previous configuration cannot be proposed.
+        require!(
+            transaction_index > self.multisig.stale_transaction_index,
+            ErrorCode::StaleProposal
+        );
         Ok(())
     }
 
@@ -50,18 +74,28 @@ impl <'info> ProposalCreate<'info> {
         bumps: &ProposalCreateBumps,
     ) -> Result<()> {
         let proposal = &mut self.proposal;
+        proposal.version = Proposal::VERSION;
         proposal.multisig = self.multisig.key();
         proposal.transaction_index = transaction_index;
-        proposal.status = if draft {
-            ProposalStatus::Draft
+        if draft {
+            proposal.status = ProposalStatus::Draft {
+                timestamp: Clock::get()?.unix_timestamp,
+            };
         } else {
-            ProposalStatus::Active
-        };
+            proposal.activate(self.multisig.voting_period)?;
+        }
         proposal.bump = bumps.proposal;
         proposal.approved = vec![];
         proposal.rejected = vec![];
         proposal.cancelled = vec![];
-    
+
+        emit!(ProposalCreated {
+            actor: self.creator.key(),
+            multisig: proposal.multisig,
+            transaction_index,
+            status: proposal.status.clone(),
+        });
+
         Ok(())
     }    
 }
\ No newline at end of file
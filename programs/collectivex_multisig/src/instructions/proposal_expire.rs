@@ -0,0 +1,63 @@
+use crate::constants::{MULTISIG_SEED, PROGRAM_CONFIG_SEED, TRANSACTION_SEED, PROPOSAL_SEED};
+use crate::state::{Multisig, Proposal, ProposalStatus};
+use crate::error::ErrorCode;
+use crate::events::ProposalExpired;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct ProposalExpire<'info> {
+    #[account(
+        seeds = [PROGRAM_CONFIG_SEED, MULTISIG_SEED, multisig.create_key.as_ref()],
+        bump,
+    )]
+    pub multisig: Account<'info, Multisig>,
+
+    #[account(
+        mut,
+        seeds = [
+            PROGRAM_CONFIG_SEED,
+            multisig.key().as_ref(),
+            TRANSACTION_SEED,
+            &proposal.transaction_index.to_le_bytes(),
+            PROPOSAL_SEED,
+        ],
+        bump = proposal.bump,
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    /// Anyone can expire a proposal past its deadline.
+    pub caller: Signer<'info>,
+}
+
+impl<'info> ProposalExpire<'info> {
+    /// Validates whether the proposal can be marked as expired.
+    pub fn validate(&self) -> Result<()> {
+        // Only proposals open for voting can expire.
+        require!(
+            matches!(self.proposal.status, ProposalStatus::Active { .. }),
+            ErrorCode::InvalidProposalStatus
+        );
+
+        require!(
+            self.proposal.is_expired(Clock::get()?.unix_timestamp),
+            ErrorCode::ProposalNotExpired
+        );
+
+        Ok(())
+    }
+
+    /// Updates the status of the proposal to `Expired`.
+    pub fn proposal_expire(&mut self) -> Result<()> {
+        self.proposal.status = ProposalStatus::Expired {
+            timestamp: Clock::get()?.unix_timestamp,
+        };
+
+        emit!(ProposalExpired {
+            actor: self.caller.key(),
+            multisig: self.multisig.key(),
+            transaction_index: self.proposal.transaction_index,
+        });
+
+        Ok(())
+    }
+}
@@ -1,6 +1,7 @@
-use crate::constants::{DISCRIMINATOR, MULTISIG_SEED, PROGRAM_CONFIG_SEED, TRANSACTION_SEED, PROPOSAL_SEED};
-use crate::state::{Multisig, Proposal, ProposalStatus};
+use crate::constants::{MAX_MEMO_LEN, MULTISIG_SEED, PROGRAM_CONFIG_SEED, TRANSACTION_SEED, PROPOSAL_SEED};
+use crate::state::{Multisig, PauseCategory, Permission, ProgramConfig, Proposal, ProposalStatus};
 use crate::error::ErrorCode;
+use crate::events::{ProposalApproved, ProposalCancelled, ProposalRejected};
 use anchor_lang::prelude::*;
 
 #[derive(Accounts)]
@@ -11,6 +12,9 @@ pub struct ProposalVote<'info> {
     )]
     pub multisig: Account<'info, Multisig>,
 
+    #[account(seeds = [PROGRAM_CONFIG_SEED], bump)]
+    pub program_config: Account<'info, ProgramConfig>,
+
     #[account(mut)]
     pub member: Signer<'info>,
 
@@ -30,7 +34,9 @@ pub struct ProposalVote<'info> {
 
 impl<'info> ProposalVote<'info> {
     /// Validates the vote action.
-    pub fn validate(&self, vote: Vote) -> Result<()> {
+    pub fn validate(&self, vote: Vote, memo: &Option<String>) -> Result<()> {
+        self.program_config.check_not_paused(PauseCategory::Vote)?;
+
         let Self {
             multisig,
             proposal,
@@ -40,15 +46,21 @@ impl<'info> ProposalVote<'info> {
 
         // Check if the signer is a valid member.
         require!(
-            multisig.members.contains(&member.key()),
+            multisig.is_member(member.key()).is_some(),
             ErrorCode::NotAMember
         );
 
         // Check if the member has the permission to vote.
-        // require!(
-        //     multisig.has_permission(member.key(), Permission::Vote),
-        //     ErrorCode::Unauthorized
-        // );
+        require!(
+            multisig.has_permission(member.key(), Permission::Vote),
+            ErrorCode::Unauthorized
+        );
+
+        // The memo is emitted with the vote, so its size is bounded.
+        require!(
+            memo.as_ref().map_or(0, String::len) <= MAX_MEMO_LEN,
+            ErrorCode::MemoTooLong
+        );
 
         // Validate based on vote type.
         match vote {
@@ -64,6 +76,12 @@ impl<'info> ProposalVote<'info> {
                     proposal.transaction_index > multisig.stale_transaction_index,
                     ErrorCode::StaleProposal
                 );
+
+                // Votes after the deadline are rejected.
+                require!(
+                    !proposal.is_expired(Clock::get()?.unix_timestamp),
+                    ErrorCode::ProposalExpired
+                );
             }
             Vote::Cancel => {
                 // Proposal must be approved for cancellation.
@@ -78,36 +96,59 @@ impl<'info> ProposalVote<'info> {
     }
 
     /// Approves the proposal.
-    pub fn proposal_approve(&mut self, _memo: Option<String>) -> Result<()> {
+    pub fn proposal_approve(&mut self, memo: Option<String>) -> Result<()> {
         let multisig = &mut self.multisig;
         let proposal = &mut self.proposal;
         let member = &self.member;
 
         // Approve the proposal.
-        proposal.approve(member.key(), multisig.threshold as usize)?;
+        proposal.approve(member.key(), multisig, Clock::get()?.unix_timestamp)?;
+
+        emit!(ProposalApproved {
+            actor: member.key(),
+            multisig: multisig.key(),
+            transaction_index: proposal.transaction_index,
+            status: proposal.status.clone(),
+            memo,
+        });
 
         Ok(())
     }
 
-    pub fn proposal_reject(&mut self, _memo: Option<String>) -> Result<()> {
+    pub fn proposal_reject(&mut self, memo: Option<String>) -> Result<()> {
         let multisig = &mut self.multisig;
         let proposal = &mut self.proposal;
         let member = &self.member;
 
-        let cutoff = multisig.members.len() - multisig.threshold as usize;
-        // Approve the proposal.
-        proposal.reject(member.key(), cutoff)?;
+        // Reject the proposal.
+        proposal.reject(member.key(), multisig, Clock::get()?.unix_timestamp)?;
+
+        emit!(ProposalRejected {
+            actor: member.key(),
+            multisig: multisig.key(),
+            transaction_index: proposal.transaction_index,
+            status: proposal.status.clone(),
+            memo,
+        });
 
         Ok(())
     }
 
-    pub fn proposal_cancel(&mut self, _memo: Option<String>) -> Result<()> {
+    pub fn proposal_cancel(&mut self, memo: Option<String>) -> Result<()> {
         let multisig = &mut self.multisig;
         let proposal = &mut self.proposal;
         let member = &self.member;
 
         // Cancel the proposal.
-        proposal.cancel(member.key(), multisig.threshold as usize)?;
+        proposal.cancel(member.key(), multisig, Clock::get()?.unix_timestamp)?;
+
+        emit!(ProposalCancelled {
+            actor: member.key(),
+            multisig: multisig.key(),
+            transaction_index: proposal.transaction_index,
+            status: proposal.status.clone(),
+            memo,
+        });
 
         Ok(())
     }
@@ -0,0 +1,210 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+use anchor_spl::token::{transfer_checked, Mint, Token, TokenAccount, TransferChecked};
+
+use crate::error::ErrorCode;
+use crate::events::SpendingLimitUsed;
+use crate::state::{Multisig, PauseCategory, ProgramConfig, SpendingLimit};
+use crate::constants::{MULTISIG_SEED, PROGRAM_CONFIG_SEED, SPENDING_LIMIT_SEED, VAULT_SEED};
+
+#[derive(Accounts)]
+pub struct SpendingLimitUse<'info> {
+    #[account(
+        seeds = [PROGRAM_CONFIG_SEED, MULTISIG_SEED, multisig.create_key.as_ref()],
+        bump,
+    )]
+    pub multisig: Account<'info, Multisig>,
+
+    #[account(seeds = [PROGRAM_CONFIG_SEED], bump)]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    /// Member listed in the spending limit.
+    pub member: Signer<'info>,
+
+    /// The SpendingLimit to spend against.
+    #[account(
+        mut,
+        seeds = [
+            PROGRAM_CONFIG_SEED,
+            multisig.key().as_ref(),
+            SPENDING_LIMIT_SEED,
+            spending_limit.create_key.as_ref(),
+        ],
+        bump = spending_limit.bump,
+    )]
+    pub spending_limit: Account<'info, SpendingLimit>,
+
+    /// The vault the funds are transferred from.
+    #[account(
+        mut,
+        seeds = [
+            PROGRAM_CONFIG_SEED,
+            multisig.key().as_ref(),
+            VAULT_SEED,
+            &[spending_limit.vault_index],
+        ],
+        bump,
+    )]
+    pub vault: SystemAccount<'info>,
+
+    /// Destination wallet of the transfer.
+    /// CHECK: validated against the spending limit destinations.
+    #[account(mut)]
+    pub destination: AccountInfo<'info>,
+
+    /// Required for SOL transfers.
+    pub system_program: Option<Program<'info, System>>,
+
+    /// Required for SPL token transfers.
+    pub mint: Option<Account<'info, Mint>>,
+
+    /// Vault token account the tokens are transferred from. Required for SPL token transfers.
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = vault,
+    )]
+    pub vault_token_account: Option<Account<'info, TokenAccount>>,
+
+    /// Destination token account. Required for SPL token transfers.
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = destination,
+    )]
+    pub destination_token_account: Option<Account<'info, TokenAccount>>,
+
+    /// Required for SPL token transfers.
+    pub token_program: Option<Program<'info, Token>>,
+}
+
+impl<'info> SpendingLimitUse<'info> {
+    /// Validates the member, destination and amount against the spending limit.
+    pub fn validate(&self, amount: u64) -> Result<()> {
+        self.program_config.check_not_paused(PauseCategory::Execute)?;
+
+        let Self {
+            multisig,
+            member,
+            spending_limit,
+            destination,
+            ..
+        } = self;
+
+        require_keys_eq!(
+            spending_limit.multisig,
+            multisig.key(),
+            ErrorCode::InvalidSpendingLimit
+        );
+
+        // Only members listed in the spending limit can use it.
+        require!(
+            spending_limit.members.contains(&member.key()),
+            ErrorCode::Unauthorized
+        );
+
+        // Transfers can only go to the allowed destinations.
+        require!(
+            spending_limit.destinations.contains(&destination.key()),
+            ErrorCode::InvalidDestination
+        );
+
+        require!(amount > 0, ErrorCode::InvalidAmount);
+
+        Ok(())
+    }
+
+    /// Transfers `amount` from the vault to the destination and decrements the remaining amount.
+    pub fn spending_limit_use(
+        &mut self,
+        amount: u64,
+        bumps: &SpendingLimitUseBumps,
+    ) -> Result<()> {
+        self.spending_limit
+            .reset_if_needed(Clock::get()?.unix_timestamp);
+
+        self.spending_limit.remaining_amount = self
+            .spending_limit
+            .remaining_amount
+            .checked_sub(amount)
+            .ok_or(ErrorCode::SpendingLimitExceeded)?;
+
+        let multisig_key = self.multisig.key();
+        let vault_seeds: &[&[u8]] = &[
+            PROGRAM_CONFIG_SEED,
+            multisig_key.as_ref(),
+            VAULT_SEED,
+            &[self.spending_limit.vault_index],
+            &[bumps.vault],
+        ];
+        let signer_seeds = &[vault_seeds];
+
+        if self.spending_limit.mint == Pubkey::default() {
+            // Native SOL transfer.
+            let system_program = self
+                .system_program
+                .as_ref()
+                .ok_or(ErrorCode::MissingAccount)?;
+
+            let cpi_accounts = Transfer {
+                from: self.vault.to_account_info(),
+                to: self.destination.to_account_info(),
+            };
+
+            let cpi_ctx = CpiContext::new_with_signer(
+                system_program.to_account_info(),
+                cpi_accounts,
+                signer_seeds,
+            );
+            transfer(cpi_ctx, amount)?;
+        } else {
+            // SPL token transfer.
+            let mint = self.mint.as_ref().ok_or(ErrorCode::MissingAccount)?;
+            let vault_token_account = self
+                .vault_token_account
+                .as_ref()
+                .ok_or(ErrorCode::MissingAccount)?;
+            let destination_token_account = self
+                .destination_token_account
+                .as_ref()
+                .ok_or(ErrorCode::MissingAccount)?;
+            let token_program = self
+                .token_program
+                .as_ref()
+                .ok_or(ErrorCode::MissingAccount)?;
+
+            require_keys_eq!(
+                mint.key(),
+                self.spending_limit.mint,
+                ErrorCode::InvalidMint
+            );
+
+            let cpi_accounts = TransferChecked {
+                from: vault_token_account.to_account_info(),
+                mint: mint.to_account_info(),
+                to: destination_token_account.to_account_info(),
+                authority: self.vault.to_account_info(),
+            };
+
+            let cpi_ctx = CpiContext::new_with_signer(
+                token_program.to_account_info(),
+                cpi_accounts,
+                signer_seeds,
+            );
+            transfer_checked(cpi_ctx, amount, mint.decimals)?;
+        }
+
+        emit!(SpendingLimitUsed {
+            actor: self.member.key(),
+            multisig: multisig_key,
+            spending_limit: self.spending_limit.key(),
+            vault_index: self.spending_limit.vault_index,
+            mint: self.spending_limit.mint,
+            destination: self.destination.key(),
+            amount,
+            remaining_amount: self.spending_limit.remaining_amount,
+        });
+
+        Ok(())
+    }
+}
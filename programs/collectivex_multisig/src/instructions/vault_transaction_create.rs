@@ -0,0 +1,119 @@
+use anchor_lang::prelude::*;
+use crate::error::ErrorCode;
+use crate::events::VaultTransactionCreated;
+use crate::state::{
+    Multisig, PauseCategory, Permission, ProgramConfig, VaultTransaction, VaultTransactionMessage,
+};
+use crate::constants::{PROGRAM_CONFIG_SEED, MULTISIG_SEED, TRANSACTION_SEED, VAULT_SEED};
+
+#[derive(Accounts)]
+#[instruction(vault_index: u8, message: VaultTransactionMessage)]
+pub struct VaultTransactionCreate<'info> {
+    #[account(
+        mut,
+        seeds = [PROGRAM_CONFIG_SEED, MULTISIG_SEED, multisig.create_key.as_ref()],
+        bump,
+    )]
+    pub multisig: Account<'info, Multisig>,
+
+    #[account(seeds = [PROGRAM_CONFIG_SEED], bump)]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    #[account(
+        init,
+        payer = rent_payer,
+        space = VaultTransaction::size(&message),
+        seeds = [
+            PROGRAM_CONFIG_SEED,
+            multisig.key().as_ref(),
+            TRANSACTION_SEED,
+            &(multisig.transaction_index + 1).to_le_bytes()
+        ],
+        bump
+    )]
+    pub transaction: Account<'info, VaultTransaction>,
+
+    pub creator: Signer<'info>,
+
+    #[account(mut)]
+    pub rent_payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> VaultTransactionCreate<'info> {
+    /// Validates the inputs and signer for creating a vault transaction.
+    pub fn validate(&self, vault_index: u8, message: &VaultTransactionMessage) -> Result<()> {
+        self.program_config.check_not_paused(PauseCategory::Initiate)?;
+
+        // Ensure the creator is a member of the multisig.
+        require!(
+            self.multisig.is_member(self.creator.key()).is_some(),
+            ErrorCode::NotAMember
+        );
+
+        // Ensure the creator is allowed to initiate transactions.
+        require!(
+            self.multisig.has_permission(self.creator.key(), Permission::Initiate),
+            ErrorCode::Unauthorized
+        );
+
+        // Ensure at least one instruction is provided and only the vault signs.
+        let (vault, _) = Pubkey::find_program_address(
+            &[
+                PROGRAM_CONFIG_SEED,
+                self.multisig.key().as_ref(),
+                VAULT_SEED,
+                &[vault_index],
+            ],
+            &crate::ID,
+        );
+        message.validate(&vault)?;
+
+        Ok(())
+    }
+
+    /// Creates the vault transaction and updates the multisig.
+    pub fn create_vault_transaction(
+        &mut self,
+        vault_index: u8,
+        message: VaultTransactionMessage,
+        bumps: &VaultTransactionCreateBumps,
+        program_id: &Pubkey,
+    ) -> Result<()> {
+        let multisig = &mut self.multisig;
+        let transaction = &mut self.transaction;
+
+        let multisig_key = multisig.key();
+        let (_, vault_bump) = Pubkey::find_program_address(
+            &[
+                PROGRAM_CONFIG_SEED,
+                multisig_key.as_ref(),
+                VAULT_SEED,
+                &[vault_index],
+            ],
+            program_id,
+        );
+
+        // Increment the transaction index.
+        multisig.transaction_index = multisig.transaction_index.checked_add(1).unwrap();
+
+        // Initialize the transaction fields.
+        transaction.multisig = multisig_key;
+        transaction.creator = self.creator.key();
+        transaction.index = multisig.transaction_index;
+        transaction.bump = bumps.transaction;
+        transaction.vault_index = vault_index;
+        transaction.vault_bump = vault_bump;
+        transaction.message = message;
+
+        emit!(VaultTransactionCreated {
+            actor: self.creator.key(),
+            multisig: multisig_key,
+            transaction_index: multisig.transaction_index,
+            vault_index,
+        });
+
+        Ok(())
+    }
+}
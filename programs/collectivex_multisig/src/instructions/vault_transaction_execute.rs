@@ -0,0 +1,166 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::program::invoke_signed;
+
+use crate::error::ErrorCode;
+use crate::events::ProposalExecuted;
+use crate::state::{
+    Multisig, PauseCategory, Permission, ProgramConfig, Proposal, ProposalStatus, VaultTransaction,
+};
+use crate::constants::{MULTISIG_SEED, PROGRAM_CONFIG_SEED, PROPOSAL_SEED, TRANSACTION_SEED, VAULT_SEED};
+
+#[derive(Accounts)]
+pub struct VaultTransactionExecute<'info> {
+    #[account(
+        seeds = [PROGRAM_CONFIG_SEED, MULTISIG_SEED, multisig.create_key.as_ref()],
+        bump,
+    )]
+    pub multisig: Account<'info, Multisig>,
+
+    #[account(seeds = [PROGRAM_CONFIG_SEED], bump)]
+    pub program_config: Account<'info, ProgramConfig>,
+
+    /// The member of the multisig executing the transaction.
+    pub member: Signer<'info>,
+
+    /// The proposal that must be approved for the transaction to be executed.
+    #[account(
+        mut,
+        seeds = [
+            PROGRAM_CONFIG_SEED,
+            multisig.key().as_ref(),
+            TRANSACTION_SEED,
+            &transaction.index.to_le_bytes(),
+            PROPOSAL_SEED,
+        ],
+        bump = proposal.bump,
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    /// The vault transaction holding the instructions to execute.
+    #[account(
+        seeds = [
+            PROGRAM_CONFIG_SEED,
+            multisig.key().as_ref(),
+            TRANSACTION_SEED,
+            &transaction.index.to_le_bytes(),
+        ],
+        bump = transaction.bump,
+    )]
+    pub transaction: Account<'info, VaultTransaction>,
+    // `remaining_accounts` must contain every account and program
+    // referenced by the instructions of the transaction message.
+}
+
+impl<'info> VaultTransactionExecute<'info> {
+    /// Validates whether the vault transaction can be executed.
+    pub fn validate(&self) -> Result<()> {
+        self.program_config.check_not_paused(PauseCategory::Execute)?;
+
+        let Self {
+            multisig,
+            proposal,
+            member,
+            ..
+        } = self;
+
+        // Check if the signer is a valid member of the multisig.
+        require!(
+            multisig.is_member(member.key()).is_some(),
+            ErrorCode::NotAMember
+        );
+
+        // Check if the member has the permission to execute.
+        require!(
+            multisig.has_permission(member.key(), Permission::Execute),
+            ErrorCode::Unauthorized
+        );
+
+        // The proposal must be approved and the time lock must have elapsed.
+        match proposal.status {
+            ProposalStatus::Approved { timestamp } => {
+                require!(
+                    Clock::get()?.unix_timestamp - timestamp >= i64::from(multisig.time_lock),
+                    ErrorCode::TimeLockNotReleased
+                );
+            }
+            _ => return err!(ErrorCode::InvalidProposalStatus),
+        }
+
+        Ok(())
+    }
+
+    /// Executes the transaction message signed by the vault PDA and marks the proposal as executed.
+    pub fn vault_transaction_execute(
+        &mut self,
+        remaining_accounts: &'info [AccountInfo<'info>],
+        program_id: &Pubkey,
+    ) -> Result<()> {
+        // Persist the `Executing` status before any CPI, so the proposal
+        // cannot be executed again by re-entering the program.
+        self.proposal.status = ProposalStatus::Executing {
+            timestamp: Clock::get()?.unix_timestamp,
+        };
+        self.proposal.exit(program_id)?;
+
+        let multisig_key = self.multisig.key();
+        let transaction = &self.transaction;
+
+        let vault_seeds: &[&[u8]] = &[
+            PROGRAM_CONFIG_SEED,
+            multisig_key.as_ref(),
+            VAULT_SEED,
+            &[transaction.vault_index],
+            &[transaction.vault_bump],
+        ];
+        let vault = Pubkey::create_program_address(vault_seeds, program_id)
+            .map_err(|_| error!(ErrorCode::InvalidTransactionMessage))?;
+        transaction.message.validate(&vault)?;
+
+        for vault_instruction in transaction.message.instructions.iter() {
+            let mut account_infos = Vec::with_capacity(vault_instruction.accounts.len() + 1);
+            let mut account_metas = Vec::with_capacity(vault_instruction.accounts.len());
+
+            for meta in vault_instruction.accounts.iter() {
+                account_infos.push(find_account(remaining_accounts, &meta.pubkey)?);
+                account_metas.push(AccountMeta {
+                    pubkey: meta.pubkey,
+                    is_signer: meta.is_signer,
+                    is_writable: meta.is_writable,
+                });
+            }
+            account_infos.push(find_account(remaining_accounts, &vault_instruction.program_id)?);
+
+            let instruction = Instruction {
+                program_id: vault_instruction.program_id,
+                accounts: account_metas,
+                data: vault_instruction.data.clone(),
+            };
+
+            invoke_signed(&instruction, &account_infos, &[vault_seeds])?;
+        }
+
+        self.proposal.status = ProposalStatus::Executed {
+            timestamp: Clock::get()?.unix_timestamp,
+        };
+
+        emit!(ProposalExecuted {
+            actor: self.member.key(),
+            multisig: multisig_key,
+            transaction_index: self.transaction.index,
+        });
+
+        Ok(())
+    }
+}
+
+fn find_account<'info>(
+    remaining_accounts: &'info [AccountInfo<'info>],
+    key: &Pubkey,
+) -> Result<AccountInfo<'info>> {
+    remaining_accounts
+        .iter()
+        .find(|account| account.key == key)
+        .cloned()
+        .ok_or(error!(ErrorCode::InvalidAccount))
+}
@@ -1,13 +1,18 @@
 pub mod constants;
 pub mod error;
+pub mod events;
 pub mod instructions;
 pub mod state;
+pub mod utils;
 
 use anchor_lang::prelude::*;
 
 pub use constants::*;
 pub use instructions::*;
-pub use state::{config_transaction, multisig, program_config, spending_limit, ConfigAction};
+pub use state::{
+    config_transaction, multisig, program_config, spending_limit, vault_transaction, ConfigAction,
+    Member, PausedCategories, Period, Permissions, VaultTransactionMessage,
+};
 
 declare_id!("8bX4XyTtZH3xGRyE1Y4tEvhvmD4GHdjiXAsEMQ39ZUBy");
 
@@ -27,12 +32,40 @@ pub mod collectivex_multisig {
         Ok(())
     }
 
-    pub fn program_config_set_authority(
+    /// Pause the instruction categories in `new_paused`, and resume the others.
+    pub fn program_config_set_paused(
+        ctx: Context<ProgramConfigEdit>,
+        new_paused: PausedCategories,
+    ) -> Result<()> {
+        ctx.accounts.check_current_authority()?;
+        ctx.accounts.set_program_config_paused(new_paused)?;
+
+        Ok(())
+    }
+
+    /// Propose `new_authority` as the program config authority. It takes over once it
+    /// signs `program_config_accept_authority`.
+    pub fn program_config_propose_authority(
         ctx: Context<ProgramConfigEdit>,
         new_authority: Pubkey,
     ) -> Result<()> {
         ctx.accounts.check_current_authority()?;
-        ctx.accounts.set_program_config_authority(new_authority)?;
+        ctx.accounts.propose_program_config_authority(new_authority)?;
+
+        Ok(())
+    }
+
+    pub fn program_config_accept_authority(
+        ctx: Context<ProgramConfigAcceptAuthority>,
+    ) -> Result<()> {
+        ctx.accounts.accept_program_config_authority()?;
+
+        Ok(())
+    }
+
+    pub fn program_config_cancel_authority(ctx: Context<ProgramConfigEdit>) -> Result<()> {
+        ctx.accounts.check_current_authority()?;
+        ctx.accounts.cancel_program_config_authority()?;
 
         Ok(())
     }
@@ -58,11 +91,51 @@ pub mod collectivex_multisig {
         Ok(())
     }
 
+    /// Charge the creation fee in `new_fee_mint` tokens, or in SOL when `None`.
+    pub fn program_config_set_fee_mint(
+        ctx: Context<ProgramConfigEdit>,
+        new_fee_mint: Option<Pubkey>,
+    ) -> Result<()> {
+        ctx.accounts.check_current_authority()?;
+        ctx.accounts.set_program_config_fee_mint(new_fee_mint)?;
+
+        Ok(())
+    }
+
+    /// Grant `creator` a discount of `discount_percent` on the creation fee, 100 for a
+    /// full waiver, optionally until `expires_at` and for `remaining_uses` creations.
+    pub fn fee_waiver_create(
+        ctx: Context<FeeWaiverCreate>,
+        creator: Pubkey,
+        discount_percent: u8,
+        expires_at: Option<i64>,
+        remaining_uses: Option<u32>,
+    ) -> Result<()> {
+        ctx.accounts
+            .validate(discount_percent, expires_at, remaining_uses)?;
+        ctx.accounts.fee_waiver_create(
+            creator,
+            discount_percent,
+            expires_at,
+            remaining_uses,
+            &ctx.bumps,
+        )?;
+
+        Ok(())
+    }
+
+    pub fn fee_waiver_remove(ctx: Context<FeeWaiverRemove>) -> Result<()> {
+        ctx.accounts.check_current_authority()?;
+        ctx.accounts.fee_waiver_remove()?;
+
+        Ok(())
+    }
+
     pub fn multisig_create(
         ctx: Context<MultisigCreate>,
         config_authority: Pubkey,
         threshold: u16,
-        members: Vec<Pubkey>,
+        members: Vec<Member>,
         time_lock: u32,
     ) -> Result<()> {
         ctx.accounts
@@ -71,7 +144,7 @@ pub mod collectivex_multisig {
         Ok(())
     }
 
-    pub fn multisig_add_member(ctx: Context<MultisigEdit>, new_member: Pubkey) -> Result<()> {
+    pub fn multisig_add_member(ctx: Context<MultisigEdit>, new_member: Member) -> Result<()> {
         ctx.accounts.check_current_authority()?;
         ctx.accounts.add_multisig_member(new_member)?;
 
@@ -92,13 +165,90 @@ pub mod collectivex_multisig {
         Ok(())
     }
 
-    pub fn multisig_set_config_authority(
+    pub fn multisig_change_threshold(ctx: Context<MultisigEdit>, new_threshold: u16) -> Result<()> {
+        ctx.accounts.check_current_authority()?;
+        ctx.accounts.change_multisig_threshold(new_threshold)?;
+
+        Ok(())
+    }
+
+    pub fn multisig_set_member_permissions(
+        ctx: Context<MultisigEdit>,
+        member: Pubkey,
+        permissions: Permissions,
+    ) -> Result<()> {
+        ctx.accounts.check_current_authority()?;
+        ctx.accounts
+            .set_multisig_member_permissions(member, permissions)?;
+
+        Ok(())
+    }
+
+    pub fn multisig_set_member_weight(
+        ctx: Context<MultisigEdit>,
+        member: Pubkey,
+        weight: u16,
+    ) -> Result<()> {
+        ctx.accounts.check_current_authority()?;
+        ctx.accounts.set_multisig_member_weight(member, weight)?;
+
+        Ok(())
+    }
+
+    /// Propose `new_config_authority` as the config authority of a controlled multisig. It
+    /// takes over once it signs `multisig_accept_config_authority`.
+    pub fn multisig_propose_config_authority(
         ctx: Context<MultisigEdit>,
         new_config_authority: Pubkey,
     ) -> Result<()> {
         ctx.accounts.check_current_authority()?;
         ctx.accounts
-            .set_multisig_config_authority(new_config_authority)?;
+            .propose_multisig_config_authority(new_config_authority)?;
+
+        Ok(())
+    }
+
+    pub fn multisig_accept_config_authority(
+        ctx: Context<MultisigAcceptConfigAuthority>,
+    ) -> Result<()> {
+        ctx.accounts.accept_multisig_config_authority()?;
+
+        Ok(())
+    }
+
+    pub fn multisig_cancel_config_authority(ctx: Context<MultisigEdit>) -> Result<()> {
+        ctx.accounts.check_current_authority()?;
+        ctx.accounts.cancel_multisig_config_authority()?;
+
+        Ok(())
+    }
+
+    /// Give up the config authority, making the multisig autonomous.
+    pub fn multisig_renounce_config_authority(ctx: Context<MultisigEdit>) -> Result<()> {
+        ctx.accounts.check_current_authority()?;
+        ctx.accounts.renounce_multisig_config_authority()?;
+
+        Ok(())
+    }
+
+    pub fn multisig_set_rent_collector(
+        ctx: Context<MultisigEdit>,
+        new_rent_collector: Option<Pubkey>,
+    ) -> Result<()> {
+        ctx.accounts.check_current_authority()?;
+        ctx.accounts
+            .set_multisig_rent_collector(new_rent_collector)?;
+
+        Ok(())
+    }
+
+    pub fn multisig_set_voting_period(
+        ctx: Context<MultisigEdit>,
+        new_voting_period: Option<u32>,
+    ) -> Result<()> {
+        ctx.accounts.check_current_authority()?;
+        ctx.accounts
+            .set_multisig_voting_period(new_voting_period)?;
 
         Ok(())
     }
@@ -108,19 +258,21 @@ pub mod collectivex_multisig {
         vault_index: u8,
         mint: Pubkey,
         amount: u64,
+        period: Period,
         members: Vec<Pubkey>,
         destinations: Vec<Pubkey>,
     ) -> Result<()> {
         ctx.accounts.check_current_authority()?;
 
-        let _ = ctx.accounts.multisig_add_spending_limit(
+        ctx.accounts.multisig_add_spending_limit(
             &ctx.bumps,
             vault_index,
             mint,
             amount,
+            period,
             members,
             destinations,
-        );
+        )?;
 
         Ok(())
     }
@@ -135,12 +287,64 @@ pub mod collectivex_multisig {
         Ok(())
     }
 
+    /// Transfer SOL or SPL tokens from a vault within the bounds of a spending limit, without a proposal.
+    pub fn spending_limit_use(ctx: Context<SpendingLimitUse>, amount: u64) -> Result<()> {
+        ctx.accounts.validate(amount)?;
+        ctx.accounts.spending_limit_use(amount, &ctx.bumps)?;
+
+        Ok(())
+    }
+
     pub fn config_transaction_create(
         ctx: Context<ConfigTransactionCreate>,
         actions: Vec<ConfigAction>,
     ) -> Result<()> {
         ctx.accounts.validate(&actions)?;
-        ctx.accounts.create_config_transaction(actions)?;
+        ctx.accounts.create_config_transaction(actions, &ctx.bumps)?;
+
+        Ok(())
+    }
+
+    /// Apply the actions of an approved config transaction to the multisig.
+    pub fn config_transaction_execute<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ConfigTransactionExecute<'info>>,
+    ) -> Result<()> {
+        ctx.accounts.validate()?;
+        ctx.accounts
+            .config_transaction_execute(ctx.remaining_accounts, ctx.program_id)?;
+
+        Ok(())
+    }
+
+    /// Close a finished or stale config transaction and its proposal, sending the rent to the rent collector.
+    pub fn config_transaction_accounts_close(
+        ctx: Context<ConfigTransactionAccountsClose>,
+    ) -> Result<()> {
+        ctx.accounts.validate()?;
+        ctx.accounts.config_transaction_accounts_close()?;
+
+        Ok(())
+    }
+
+    pub fn vault_transaction_create(
+        ctx: Context<VaultTransactionCreate>,
+        vault_index: u8,
+        message: VaultTransactionMessage,
+    ) -> Result<()> {
+        ctx.accounts.validate(vault_index, &message)?;
+        ctx.accounts
+            .create_vault_transaction(vault_index, message, &ctx.bumps, ctx.program_id)?;
+
+        Ok(())
+    }
+
+    /// Execute the instructions of an approved vault transaction, signed by the vault PDA.
+    pub fn vault_transaction_execute<'info>(
+        ctx: Context<'_, '_, 'info, 'info, VaultTransactionExecute<'info>>,
+    ) -> Result<()> {
+        ctx.accounts.validate()?;
+        ctx.accounts
+            .vault_transaction_execute(ctx.remaining_accounts, ctx.program_id)?;
 
         Ok(())
     }
@@ -150,7 +354,7 @@ pub mod collectivex_multisig {
         transaction_index: u64,
         draft: bool,
     ) -> Result<()> {
-        ctx.accounts.validate()?;
+        ctx.accounts.validate(transaction_index)?;
         ctx.accounts
             .proposal_create(transaction_index, draft, &ctx.bumps)?;
 
@@ -165,23 +369,60 @@ pub mod collectivex_multisig {
         Ok(())
     }
 
-    pub fn proposal_approve(ctx: Context<ProposalVote>, _memo: Option<String>) -> Result<()> {
-        ctx.accounts.validate(Vote::Approve)?;
-        ctx.accounts.proposal_approve(_memo)?;
+    pub fn proposal_approve(ctx: Context<ProposalVote>, memo: Option<String>) -> Result<()> {
+        ctx.accounts.validate(Vote::Approve, &memo)?;
+        ctx.accounts.proposal_approve(memo)?;
     
         Ok(())
     }
 
-    pub fn proposal_reject(ctx: Context<ProposalVote>, _memo: Option<String>) -> Result<()> {
-        ctx.accounts.validate(Vote::Reject)?;
-        ctx.accounts.proposal_reject(_memo)?;
+    pub fn proposal_reject(ctx: Context<ProposalVote>, memo: Option<String>) -> Result<()> {
+        ctx.accounts.validate(Vote::Reject, &memo)?;
+        ctx.accounts.proposal_reject(memo)?;
+
+        Ok(())
+    }
+
+    pub fn proposal_cancel(ctx: Context<ProposalVote>, memo: Option<String>) -> Result<()> {
+        ctx.accounts.validate(Vote::Cancel, &memo)?;
+        ctx.accounts.proposal_cancel(memo)?;
+
+        Ok(())
+    }
+
+    /// Mark an active proposal whose voting period has passed as `Expired`. Anyone can call it.
+    pub fn proposal_expire(ctx: Context<ProposalExpire>) -> Result<()> {
+        ctx.accounts.validate()?;
+        ctx.accounts.proposal_expire()?;
+
+        Ok(())
+    }
+
+    /// Upgrade the program config from a legacy layout to the current one.
+    pub fn migrate_program_config(ctx: Context<Migrate>) -> Result<()> {
+        ctx.accounts.migrate_program_config()?;
+
+        Ok(())
+    }
+
+    /// Upgrade a multisig from a legacy layout to the current one. The original program never
+    /// stored `create_key`, so it is passed in, checked against the address and written.
+    pub fn migrate_multisig(ctx: Context<Migrate>, create_key: Pubkey) -> Result<()> {
+        ctx.accounts.migrate_multisig(create_key)?;
+
+        Ok(())
+    }
+
+    /// Upgrade a spending limit from a legacy layout to the current one.
+    pub fn migrate_spending_limit(ctx: Context<Migrate>) -> Result<()> {
+        ctx.accounts.migrate_spending_limit()?;
 
         Ok(())
     }
 
-    pub fn proposal_cancel(ctx: Context<ProposalVote>, _memo: Option<String>) -> Result<()> {
-        ctx.accounts.validate(Vote::Cancel)?;
-        ctx.accounts.proposal_cancel(_memo)?;
+    /// Upgrade a proposal from a legacy layout to the current one.
+    pub fn migrate_proposal(ctx: Context<Migrate>) -> Result<()> {
+        ctx.accounts.migrate_proposal()?;
 
         Ok(())
     }
@@ -1,5 +1,6 @@
 use anchor_lang::prelude::*;
-use anchor_lang::solana_program::borsh0_10::get_instance_packed_len;
+
+use crate::state::{Member, Period, Permissions};
 
 #[account]
 pub struct ConfigTransaction {
@@ -19,13 +20,14 @@ impl ConfigTransaction {
     pub fn size(actions: &[ConfigAction]) -> usize {
         let actions_size: usize = actions
             .iter()
-            .map(|action| get_instance_packed_len(action).unwrap())
+            .map(|action| action.try_to_vec().unwrap().len())
             .sum();
 
         8 +   // discriminator
         32 +  // multisig
         32 +  // creator
         8 +   // index
+        1 +   // bump
         4 +  // actions length
         actions_size
     }
@@ -33,8 +35,10 @@ impl ConfigTransaction {
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
 pub enum ConfigAction {
-    AddMember { new_member: Pubkey },
+    AddMember { new_member: Member },
     RemoveMember { old_member: Pubkey },
+    SetMemberPermissions { member: Pubkey, permissions: Permissions },
+    SetMemberWeight { member: Pubkey, weight: u16 },
     ChangeThreshold { new_threshold: u16 },
     SetTimeLock { new_time_lock: u32 },
     AddSpendingLimit {
@@ -42,8 +46,11 @@ pub enum ConfigAction {
         vault_index: u8,
         mint: Pubkey,
         amount: u64,
+        period: Period,
         members: Vec<Pubkey>,
         destinations: Vec<Pubkey>,
     },
     RemoveSpendingLimit { spending_limit: Pubkey },
+    SetRentCollector { new_rent_collector: Option<Pubkey> },
+    SetVotingPeriod { new_voting_period: Option<u32> },
 }
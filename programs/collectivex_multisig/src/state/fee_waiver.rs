@@ -0,0 +1,45 @@
+use anchor_lang::prelude::*;
+
+use crate::error::ErrorCode;
+
+/// A discount on the multisig creation fee for one creator, such as a partner,
+/// managed by the program config authority.
+#[account]
+#[derive(InitSpace)]
+pub struct FeeWaiver {
+    pub version: u8, // Layout version.
+    pub creator: Pubkey, // Creator the waiver applies to, also seeds the PDA.
+    pub discount_percent: u8, // Share of the creation fee waived, 100 for a full waiver.
+    pub expires_at: Option<i64>, // Timestamp from which the waiver no longer applies, None if it never expires.
+    pub remaining_uses: Option<u32>, // Multisig creations left, None if unlimited.
+    pub bump: u8, // PDA bump for this FeeWaiver account.
+    pub reserved: [u8; 64], // Zeroed space for fields added by later versions.
+}
+
+impl FeeWaiver {
+    /// Current layout version.
+    pub const VERSION: u8 = 1;
+
+    /// The creation fee left to pay once the discount is applied, rounded down.
+    pub fn discounted_fee(&self, creation_fee: u64) -> u64 {
+        let due = u128::from(creation_fee) * u128::from(100 - self.discount_percent) / 100;
+        due as u64
+    }
+
+    /// Records one use of the waiver at `now`, failing if it has expired or is used up.
+    pub fn consume(&mut self, now: i64) -> Result<()> {
+        if let Some(expires_at) = self.expires_at {
+            require!(now < expires_at, ErrorCode::FeeWaiverExpired);
+        }
+
+        if let Some(remaining_uses) = self.remaining_uses {
+            self.remaining_uses = Some(
+                remaining_uses
+                    .checked_sub(1)
+                    .ok_or(ErrorCode::FeeWaiverUsedUp)?,
+            );
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,169 @@
+//! Account layouts of earlier program versions, read by the `migrate_*` instructions.
+
+use anchor_lang::prelude::*;
+
+use crate::state::{
+    Member, Multisig, PausedCategories, Period, Permissions, ProgramConfig, Proposal,
+    ProposalStatus, SpendingLimit,
+};
+
+/// `ProgramConfig` before versioning.
+#[derive(AnchorDeserialize)]
+pub struct ProgramConfigV0 {
+    pub authority: Pubkey,
+    pub creation_fee: u64,
+    pub treasury: Pubkey,
+}
+
+impl ProgramConfigV0 {
+    /// Account size, discriminator included.
+    pub const SIZE: usize = 8 + 32 + 8 + 32;
+}
+
+impl From<ProgramConfigV0> for ProgramConfig {
+    fn from(legacy: ProgramConfigV0) -> Self {
+        Self {
+            version: Self::VERSION,
+            authority: legacy.authority,
+            creation_fee: legacy.creation_fee,
+            treasury: legacy.treasury,
+            fee_mint: None,
+            pending_authority: None,
+            paused: PausedCategories::default(),
+            reserved: [0; 63],
+        }
+    }
+}
+
+/// `Multisig` before versioning, when members were plain keys.
+#[derive(AnchorDeserialize)]
+pub struct MultisigV0 {
+    pub create_key: Pubkey,
+    pub config_authority: Pubkey,
+    pub threshold: u16,
+    pub members: Vec<Pubkey>,
+    pub time_lock: u32,
+    pub transaction_index: u64,
+    pub stale_transaction_index: u64,
+}
+
+impl From<MultisigV0> for Multisig {
+    fn from(legacy: MultisigV0) -> Self {
+        Self {
+            version: Self::VERSION,
+            create_key: legacy.create_key,
+            config_authority: legacy.config_authority,
+            threshold: legacy.threshold,
+            // Every legacy member could do everything, and each vote counted once.
+            members: legacy
+                .members
+                .into_iter()
+                .map(|key| Member {
+                    key,
+                    permissions: Permissions::all(),
+                    weight: 1,
+                })
+                .collect(),
+            time_lock: legacy.time_lock,
+            transaction_index: legacy.transaction_index,
+            stale_transaction_index: legacy.stale_transaction_index,
+            rent_collector: None,
+            voting_period: None,
+            pending_config_authority: None,
+            reserved: [0; 31],
+        }
+    }
+}
+
+/// `SpendingLimit` before versioning, when limits were never refilled.
+#[derive(AnchorDeserialize)]
+pub struct SpendingLimitV0 {
+    pub multisig: Pubkey,
+    pub create_key: Pubkey,
+    pub vault_index: u8,
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub remaining_amount: u64,
+    pub last_reset: i64,
+    pub bump: u8,
+    pub members: Vec<Pubkey>,
+    pub destinations: Vec<Pubkey>,
+}
+
+impl From<SpendingLimitV0> for SpendingLimit {
+    fn from(legacy: SpendingLimitV0) -> Self {
+        Self {
+            version: Self::VERSION,
+            multisig: legacy.multisig,
+            create_key: legacy.create_key,
+            vault_index: legacy.vault_index,
+            mint: legacy.mint,
+            amount: legacy.amount,
+            period: Period::OneTime,
+            remaining_amount: legacy.remaining_amount,
+            last_reset: legacy.last_reset,
+            bump: legacy.bump,
+            members: legacy.members,
+            destinations: legacy.destinations,
+            reserved: [0; 64],
+        }
+    }
+}
+
+/// `ProposalStatus` before versioning, when statuses had no timestamp.
+#[derive(AnchorDeserialize)]
+pub enum ProposalStatusV0 {
+    Draft,
+    Active,
+    Approved,
+    Rejected,
+    Cancelled,
+}
+
+impl ProposalStatusV0 {
+    /// The current status, as if the proposal entered it at `timestamp`.
+    pub fn at(self, timestamp: i64) -> ProposalStatus {
+        match self {
+            ProposalStatusV0::Draft => ProposalStatus::Draft { timestamp },
+            ProposalStatusV0::Active => ProposalStatus::Active { timestamp },
+            ProposalStatusV0::Approved => ProposalStatus::Approved { timestamp },
+            ProposalStatusV0::Rejected => ProposalStatus::Rejected { timestamp },
+            ProposalStatusV0::Cancelled => ProposalStatus::Cancelled { timestamp },
+        }
+    }
+}
+
+/// `Proposal` before versioning.
+#[derive(AnchorDeserialize)]
+pub struct ProposalV0 {
+    pub multisig: Pubkey,
+    pub transaction_index: u64,
+    pub status: ProposalStatusV0,
+    pub bump: u8,
+    pub approved: Vec<Pubkey>,
+    pub rejected: Vec<Pubkey>,
+    pub cancelled: Vec<Pubkey>,
+}
+
+impl ProposalV0 {
+    /// Account size without votes, discriminator included. Room was left for a status
+    /// timestamp that was never written.
+    pub const SIZE: usize = 8 + 32 + 8 + 1 + 8 + 1 + 3 * 4;
+
+    /// The current layout, with the status entered at `now`, so approved proposals wait
+    /// for the time lock from their migration.
+    pub fn upgrade(self, now: i64) -> Proposal {
+        Proposal {
+            version: Proposal::VERSION,
+            multisig: self.multisig,
+            transaction_index: self.transaction_index,
+            status: self.status.at(now),
+            bump: self.bump,
+            approved: self.approved,
+            rejected: self.rejected,
+            cancelled: self.cancelled,
+            expires_at: None,
+            reserved: [0; 64],
+        }
+    }
+}
@@ -3,10 +3,15 @@ pub mod program_config;
 pub mod spending_limit;
 pub mod config_transaction;
 pub mod proposal;
+pub mod vault_transaction;
+pub mod fee_waiver;
+pub mod legacy;
 
 
 pub use multisig::*;
 pub use program_config::*;
 pub use spending_limit::*;
 pub use config_transaction::*;
-pub use proposal::*;
\ No newline at end of file
+pub use proposal::*;
+pub use vault_transaction::*;
+pub use fee_waiver::*;
\ No newline at end of file
@@ -1,24 +1,223 @@
 use anchor_lang::prelude::*;
 
+use crate::constants::{DISCRIMINATOR, MAX_MEMBERS, MAX_TIME_LOCK};
+use crate::error::ErrorCode;
+use crate::utils::realloc;
+
 #[account]
-#[derive(InitSpace)]
+#[derive(Debug)]
 /// Represents a multisig (multiple signature) configuration.
 ///
 /// Fields:
+/// - `version`: The layout version of the account, upgraded by `migrate_multisig`.
 /// - `create_key`: The key used as a seed to generate the multisig PDA (Program Derived Address).
 /// - `config_authority`: The authority that has the permission to update the multisig configuration.
-/// - `threshold`: The number of signatures required to approve a transaction.
-/// - `members`: A vector containing the public keys of the members of the multisig. The maximum length is 10.
+///   A multisig with a `config_authority` is *controlled*: only the authority can change its configuration,
+///   directly. A multisig with `Pubkey::default()` as `config_authority` is *autonomous*: its configuration
+///   can only be changed through approved config transactions.
+/// - `threshold`: The combined member weight required to approve a transaction.
+/// - `members`: A vector containing the members of the multisig and their permissions. The account is resized
+///   as members are added and removed, up to `MAX_MEMBERS`.
 /// - `time_lock`: The time lock in seconds, which specifies the delay before a transaction can be executed.
 /// - `transaction_index`: The index of the current transaction.
 /// - `stale_transaction_index`: The index of the stale transaction.
+/// - `voting_period`: The time in seconds members have to vote on an active proposal. Proposals never expire when `None`.
+/// - `rent_collector`: The account receiving the rent of closed transaction accounts. Rent reclamation is disabled when `None`.
+/// - `pending_config_authority`: The config authority proposed to take over, until it accepts.
+/// - `reserved`: Zeroed space for fields added by later versions.
 pub struct Multisig {
+    pub version: u8,                 // Layout version.
     pub create_key: Pubkey,          // Key used as a seed to multisig PDA.
     pub config_authority: Pubkey,    // Authority to update the multisig
-    pub threshold: u16,              // Number of signatures required
-    #[max_len(10)]
-    pub members: Vec<Pubkey>,       // Members of the multisig
+    pub threshold: u16,              // Combined weight of approvals required
+    pub members: Vec<Member>,       // Members of the multisig
     pub time_lock: u32,             // Time lock in seconds
     pub transaction_index: u64,     //The index of the current transaction.
     pub stale_transaction_index: u64,    // The index of the stale transaction.
+    pub rent_collector: Option<Pubkey>,  // Receives the rent of closed transaction accounts.
+    pub voting_period: Option<u32>,      // Voting period of proposals in seconds.
+    pub pending_config_authority: Option<Pubkey>, // Proposed config authority.
+    pub reserved: [u8; 31],              // Zeroed space for later versions.
+}
+
+impl Multisig {
+    /// Current layout version.
+    pub const VERSION: u8 = 1;
+
+    /// Calculate account size.
+    pub fn size(members_len: usize) -> usize {
+        DISCRIMINATOR +
+        1 +   // version
+        32 +  // create_key
+        32 +  // config_authority
+        2 +   // threshold
+        (4 + members_len * Member::INIT_SPACE) + // members vec
+        4 +   // time_lock
+        8 +   // transaction_index
+        8 +   // stale_transaction_index
+        (1 + 32) + // rent_collector
+        (1 + 4) + // voting_period
+        (1 + 32) + // pending_config_authority
+        31    // reserved
+    }
+
+    /// Resizes the multisig account to fit `members_len` members.
+    ///
+    /// Growing is paid by `rent_payer`. The rent freed by shrinking goes to `rent_collector`,
+    /// or stays in the account when there is none.
+    pub fn realloc<'info>(
+        multisig: AccountInfo<'info>,
+        members_len: usize,
+        rent_payer: Option<AccountInfo<'info>>,
+        rent_collector: Option<AccountInfo<'info>>,
+        system_program: Option<AccountInfo<'info>>,
+    ) -> Result<()> {
+        realloc(
+            multisig,
+            Self::size(members_len),
+            rent_payer,
+            rent_collector,
+            system_program,
+        )
+    }
+
+    /// Returns `true` if the multisig can only be changed through config transactions.
+    pub fn is_autonomous(&self) -> bool {
+        self.config_authority == Pubkey::default()
+    }
+
+    /// Returns `true` if the multisig is changed directly by its `config_authority`.
+    pub fn is_controlled(&self) -> bool {
+        !self.is_autonomous()
+    }
+
+    /// Checks that `authority` may change the multisig directly. Autonomous multisigs can only
+    /// be changed through config transactions.
+    pub fn check_config_authority(&self, authority: Pubkey) -> Result<()> {
+        require!(self.is_controlled(), ErrorCode::NotSupportedForAutonomous);
+        require_keys_eq!(self.config_authority, authority, ErrorCode::InvalidAuthority);
+
+        Ok(())
+    }
+
+    /// Returns the index of the member in the `members` vector, if present.
+    pub fn is_member(&self, key: Pubkey) -> Option<usize> {
+        self.members.iter().position(|m| m.key == key)
+    }
+
+    /// Returns `true` if `key` is a member holding `permission`.
+    pub fn has_permission(&self, key: Pubkey, permission: Permission) -> bool {
+        self.members
+            .iter()
+            .any(|m| m.key == key && m.permissions.has(permission))
+    }
+
+    /// Makes all proposals created so far stale, so they can no longer be voted on.
+    /// Must be called whenever the members, threshold or time lock change.
+    pub fn invalidate_prior_transactions(&mut self) {
+        self.stale_transaction_index = self.transaction_index;
+    }
+
+    /// Total weight of the members that are allowed to vote.
+    pub fn voting_weight(&self) -> u32 {
+        self.members
+            .iter()
+            .filter(|m| m.permissions.has(Permission::Vote))
+            .map(|m| u32::from(m.weight))
+            .sum()
+    }
+
+    /// Combined weight of the given keys, counting only members that are allowed to vote.
+    pub fn vote_weight(&self, keys: &[Pubkey]) -> u32 {
+        self.members
+            .iter()
+            .filter(|m| m.permissions.has(Permission::Vote) && keys.contains(&m.key))
+            .map(|m| u32::from(m.weight))
+            .sum()
+    }
+
+    /// Rejection weight from which the threshold can no longer be reached.
+    pub fn cutoff(&self) -> u32 {
+        self.voting_weight()
+            .saturating_sub(u32::from(self.threshold))
+            .saturating_add(1)
+    }
+
+    /// Checks that the configuration is usable. Must hold after every change to the multisig.
+    pub fn invariant(&self) -> Result<()> {
+        require!(
+            self.members.len() <= MAX_MEMBERS,
+            ErrorCode::ExceedsMaxMembers
+        );
+
+        for (index, member) in self.members.iter().enumerate() {
+            require!(
+                self.members[..index].iter().all(|m| m.key != member.key),
+                ErrorCode::DuplicateMember
+            );
+            require!(member.permissions.is_valid(), ErrorCode::InvalidPermissions);
+            require!(member.weight > 0, ErrorCode::InvalidWeight);
+        }
+
+        require!(self.threshold > 0, ErrorCode::ZeroThreshold);
+
+        // The members allowed to vote must be able to reach the threshold.
+        require!(
+            u32::from(self.threshold) <= self.voting_weight(),
+            ErrorCode::InvalidThreshold
+        );
+
+        require!(self.time_lock <= MAX_TIME_LOCK, ErrorCode::TimeLockTooLong);
+
+        require!(self.voting_period != Some(0), ErrorCode::InvalidVotingPeriod);
+
+        Ok(())
+    }
+}
+
+/// A member of the multisig and the actions it is allowed to perform.
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Member {
+    pub key: Pubkey,
+    pub permissions: Permissions,
+    /// Number of votes the member's approval, rejection or cancellation counts for.
+    pub weight: u16,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Permission {
+    /// Create transactions and proposals, and activate draft proposals.
+    Initiate = 1 << 0,
+    /// Approve, reject and cancel proposals.
+    Vote = 1 << 1,
+    /// Execute approved transactions.
+    Execute = 1 << 2,
+}
+
+/// Bitmask of `Permission` values.
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Permissions {
+    pub mask: u8,
+}
+
+impl Permissions {
+    /// All permissions.
+    pub fn all() -> Self {
+        Self::from_vec(&[Permission::Initiate, Permission::Vote, Permission::Execute])
+    }
+
+    pub fn from_vec(permissions: &[Permission]) -> Self {
+        let mask = permissions.iter().fold(0, |mask, p| mask | *p as u8);
+
+        Self { mask }
+    }
+
+    pub fn has(&self, permission: Permission) -> bool {
+        self.mask & (permission as u8) != 0
+    }
+
+    /// Returns `true` if no bits outside of the known permissions are set.
+    pub fn is_valid(&self) -> bool {
+        self.mask & !Self::all().mask == 0
+    }
 }
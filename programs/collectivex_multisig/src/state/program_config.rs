@@ -1,9 +1,74 @@
 use anchor_lang::prelude::*;
 
+use crate::error::ErrorCode;
+
 #[account]
 #[derive(InitSpace)]
 pub struct ProgramConfig {
+    pub version: u8,        // Layout version, see `migrate_program_config`.
     pub authority: Pubkey, // Authority to update the config
     pub creation_fee: u64, // Multisig creation fee
     pub treasury: Pubkey,  // Treasury account for fees
+    pub fee_mint: Option<Pubkey>, // Mint of the creation fee, None for SOL
+    pub pending_authority: Option<Pubkey>, // Authority proposed to take over, until it accepts
+    pub paused: PausedCategories, // Instruction categories stopped by the authority
+    pub reserved: [u8; 63], // Zeroed space for fields added by later versions.
+}
+
+impl ProgramConfig {
+    /// Current layout version.
+    pub const VERSION: u8 = 1;
+
+    pub fn check_not_paused(&self, category: PauseCategory) -> Result<()> {
+        require!(!self.paused.has(category), ErrorCode::Paused);
+
+        Ok(())
+    }
+}
+
+/// Instruction categories the program config authority can pause in an emergency.
+/// Read-only, rent reclamation and configuration instructions are never paused.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PauseCategory {
+    /// Create multisigs.
+    Create = 1 << 0,
+    /// Create transactions and proposals, and activate draft proposals.
+    Initiate = 1 << 1,
+    /// Approve, reject and cancel proposals.
+    Vote = 1 << 2,
+    /// Execute approved transactions and use spending limits.
+    Execute = 1 << 3,
+}
+
+/// Bitmask of `PauseCategory` values.
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PausedCategories {
+    pub mask: u8,
+}
+
+impl PausedCategories {
+    /// All categories.
+    pub fn all() -> Self {
+        Self::from_vec(&[
+            PauseCategory::Create,
+            PauseCategory::Initiate,
+            PauseCategory::Vote,
+            PauseCategory::Execute,
+        ])
+    }
+
+    pub fn from_vec(categories: &[PauseCategory]) -> Self {
+        let mask = categories.iter().fold(0, |mask, c| mask | *c as u8);
+
+        Self { mask }
+    }
+
+    pub fn has(&self, category: PauseCategory) -> bool {
+        self.mask & (category as u8) != 0
+    }
+
+    /// Returns `true` if no bits outside of the known categories are set.
+    pub fn is_valid(&self) -> bool {
+        self.mask & !Self::all().mask == 0
+    }
 }
\ No newline at end of file
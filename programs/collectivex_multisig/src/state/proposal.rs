@@ -1,9 +1,12 @@
 use anchor_lang::prelude::*;
-use crate::error::ErrorCode;
+
+use crate::state::Multisig;
 
 /// Tracks the status of a multisig proposal.
 #[account]
+#[derive(Debug)]
 pub struct Proposal {
+    pub version: u8,            // Layout version, see `migrate_proposal`.
     pub multisig: Pubkey,       // Associated multisig.
     pub transaction_index: u64, // Index of the transaction.
     pub status: ProposalStatus, // Status of the proposal.
@@ -11,12 +14,18 @@ pub struct Proposal {
     pub approved: Vec<Pubkey>,  // Approved members.
     pub rejected: Vec<Pubkey>,  // Rejected members.
     pub cancelled: Vec<Pubkey>, // Cancelled members.
+    pub expires_at: Option<i64>, // Voting deadline, if the multisig has a voting period.
+    pub reserved: [u8; 64],     // Zeroed space for fields added by later versions.
 }
 
 impl Proposal {
+    /// Current layout version.
+    pub const VERSION: u8 = 1;
+
     /// Calculate account size.
     pub fn size(members_len: usize) -> usize {
         8 +   // anchor account discriminator
+        1 +   // version
         32 +  // multisig
         8 +   // index
         1 +   // status enum variant
@@ -24,39 +33,56 @@ impl Proposal {
         1 +   // bump
         (4 + (members_len * 32)) + // approved vec
         (4 + (members_len * 32)) + // rejected vec
-        (4 + (members_len * 32)) // cancelled vec
+        (4 + (members_len * 32)) + // cancelled vec
+        1 + 8 + // expires_at
+        64    // reserved
+    }
+
+    /// Opens the proposal for voting, starting the voting period if any.
+    pub fn activate(&mut self, voting_period: Option<u32>) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+
+        self.status = ProposalStatus::Active { timestamp: now };
+        self.expires_at = voting_period.map(|period| now.saturating_add(i64::from(period)));
+
+        Ok(())
+    }
+
+    /// Returns `true` if the voting deadline has passed.
+    pub fn is_expired(&self, now: i64) -> bool {
+        self.expires_at.is_some_and(|expires_at| now >= expires_at)
     }
 
     /// Approve the proposal.
-    pub fn approve(&mut self, member: Pubkey, threshold: usize) -> Result<()> {
+    pub fn approve(&mut self, member: Pubkey, multisig: &Multisig, now: i64) -> Result<()> {
         if !self.rejected.is_empty() {
             self.rejected.retain(|&x| x != member);
         }
         if !self.approved.contains(&member) {
             self.approved.push(member);
         }
-        if self.approved.len() >= threshold {
-            self.status = ProposalStatus::Approved;
+        if multisig.vote_weight(&self.approved) >= u32::from(multisig.threshold) {
+            self.status = ProposalStatus::Approved { timestamp: now };
         }
         Ok(())
     }
 
     /// Reject the proposal.
-    pub fn reject(&mut self, member: Pubkey, cutoff: usize) -> Result<()> {
+    pub fn reject(&mut self, member: Pubkey, multisig: &Multisig, now: i64) -> Result<()> {
         if !self.approved.is_empty() {
             self.approved.retain(|&x| x != member);
         }
         if !self.rejected.contains(&member) {
             self.rejected.push(member);
         }
-        if self.rejected.len() >= cutoff {
-            self.status = ProposalStatus::Rejected;
+        if multisig.vote_weight(&self.rejected) >= multisig.cutoff() {
+            self.status = ProposalStatus::Rejected { timestamp: now };
         }
         Ok(())
     }
 
     /// Registers a cancellation vote.
-    pub fn cancel(&mut self, member: Pubkey, threshold: usize) -> Result<()> {
+    pub fn cancel(&mut self, member: Pubkey, multisig: &Multisig, now: i64) -> Result<()> {
         if !self.approved.is_empty() {
             self.approved.retain(|&x| x != member);
         }
@@ -68,20 +94,31 @@ impl Proposal {
             self.cancelled.push(member);
         }
 
-        if self.cancelled.len() >= threshold {
-            self.status = ProposalStatus::Cancelled;
+        if multisig.vote_weight(&self.cancelled) >= u32::from(multisig.threshold) {
+            self.status = ProposalStatus::Cancelled { timestamp: now };
         }
 
         Ok(())
     }
 }
 
-/// The status of a proposal.
+/// The status of a proposal. Each variant records when the proposal entered it.
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
 pub enum ProposalStatus {
-    Draft,
-    Active,
-    Approved,
-    Rejected,
-    Cancelled
+    /// Created as a draft, not open for voting yet.
+    Draft { timestamp: i64 },
+    /// Open for voting.
+    Active { timestamp: i64 },
+    /// Reached the threshold. The time lock is measured from this timestamp.
+    Approved { timestamp: i64 },
+    /// Can no longer reach the threshold.
+    Rejected { timestamp: i64 },
+    /// Cancelled after approval.
+    Cancelled { timestamp: i64 },
+    /// The transaction is being executed.
+    Executing { timestamp: i64 },
+    /// The transaction has been executed.
+    Executed { timestamp: i64 },
+    /// The voting period passed before the proposal was approved or rejected.
+    Expired { timestamp: i64 },
 }
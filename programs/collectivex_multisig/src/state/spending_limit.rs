@@ -1,18 +1,87 @@
 use anchor_lang::prelude::*;
 
+use crate::constants::DISCRIMINATOR;
+
 #[account]
-#[derive(InitSpace)]
 pub struct SpendingLimit {
+    pub version: u8, // Layout version, see `migrate_spending_limit`.
     pub multisig: Pubkey,  // Multisig this spending limit belongs to.
     pub create_key: Pubkey,  // Key used to seed the SpendingLimit PDA.
     pub vault_index: u8,  // Index of the vault associated with this spending limit.
     pub mint: Pubkey,  // Token mint the spending limit is for.
     pub amount: u64, // Maximum amount of tokens allowed in a reset period.
+    pub period: Period, // Reset period of the spending limit.
     pub remaining_amount: u64, // Remaining tokens available for the current period.
     pub last_reset: i64, // Timestamp of the last reset (or creation).
     pub bump: u8, /// PDA bump for this SpendingLimit account.
-    #[max_len(10)]
     pub members: Vec<Pubkey>, // Members of the multisig allowed to spend under this limit.
-    #[max_len(10)]
     pub destinations: Vec<Pubkey>, // Allowed destinations for transfers under this spending limit.
-}
\ No newline at end of file
+    pub reserved: [u8; 64], // Zeroed space for fields added by later versions.
+}
+
+impl SpendingLimit {
+    /// Current layout version.
+    pub const VERSION: u8 = 1;
+
+    /// Calculate account size.
+    pub fn size(members_len: usize, destinations_len: usize) -> usize {
+        DISCRIMINATOR +
+        1 +   // version
+        32 +  // multisig
+        32 +  // create_key
+        1 +   // vault_index
+        32 +  // mint
+        8 +   // amount
+        Period::INIT_SPACE + // period
+        8 +   // remaining_amount
+        8 +   // last_reset
+        1 +   // bump
+        (4 + members_len * 32) + // members vec
+        (4 + destinations_len * 32) + // destinations vec
+        64    // reserved
+    }
+
+    /// Refills `remaining_amount` if a new period has started since `last_reset`.
+    pub fn reset_if_needed(&mut self, now: i64) {
+        if let Some(period) = self.period.to_seconds() {
+            let periods_passed = now.saturating_sub(self.last_reset) / period;
+
+            if periods_passed > 0 {
+                self.remaining_amount = self.amount;
+                self.last_reset = self.last_reset.saturating_add(periods_passed.saturating_mul(period));
+            }
+        }
+    }
+}
+
+/// The reset period of a spending limit.
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Period {
+    /// The limit can only be used once and is never refilled.
+    OneTime,
+    Day,
+    Week,
+    /// A month is considered to be 30 days.
+    Month,
+    Custom { seconds: i64 },
+}
+
+impl Period {
+    /// Length of the period in seconds, `None` for `OneTime`.
+    pub fn to_seconds(&self) -> Option<i64> {
+        match self {
+            Period::OneTime => None,
+            Period::Day => Some(24 * 60 * 60),
+            Period::Week => Some(7 * 24 * 60 * 60),
+            Period::Month => Some(30 * 24 * 60 * 60),
+            Period::Custom { seconds } => Some(*seconds),
+        }
+    }
+
+    pub fn is_valid(&self) -> bool {
+        match self {
+            Period::Custom { seconds } => *seconds > 0,
+            _ => true,
+        }
+    }
+}
@@ -0,0 +1,74 @@
+use anchor_lang::prelude::*;
+
+use crate::error::ErrorCode;
+
+#[account]
+pub struct VaultTransaction {
+    /// The multisig this transaction belongs to.
+    pub multisig: Pubkey,
+    /// The member of the multisig who created this transaction.
+    pub creator: Pubkey,
+    /// Unique index for this transaction in the context of the multisig.
+    pub index: u64,
+    /// Bump seed for the PDA of this transaction.
+    pub bump: u8,
+    /// Index of the vault this transaction signs for.
+    pub vault_index: u8,
+    /// Bump seed of the vault PDA.
+    pub vault_bump: u8,
+    /// Instructions to be executed by the vault.
+    pub message: VaultTransactionMessage,
+}
+
+impl VaultTransaction {
+    pub fn size(message: &VaultTransactionMessage) -> usize {
+        let message_size = message.try_to_vec().unwrap().len();
+
+        8 +   // discriminator
+        32 +  // multisig
+        32 +  // creator
+        8 +   // index
+        1 +   // bump
+        1 +   // vault_index
+        1 +   // vault_bump
+        message_size
+    }
+}
+
+/// The instructions executed on behalf of a vault.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub struct VaultTransactionMessage {
+    pub instructions: Vec<VaultInstruction>,
+}
+
+impl VaultTransactionMessage {
+    /// Checks that the instructions are valid and that `vault` is the only account they expect
+    /// to sign. Other signer flags would lend the signatures of the executing transaction, such
+    /// as the member's or the fee payer's, to the instructions.
+    pub fn validate(&self, vault: &Pubkey) -> Result<()> {
+        require!(!self.instructions.is_empty(), ErrorCode::InvalidTransactionMessage);
+
+        let only_vault_signs = self
+            .instructions
+            .iter()
+            .flat_map(|instruction| instruction.accounts.iter())
+            .all(|meta| !meta.is_signer || meta.pubkey == *vault);
+        require!(only_vault_signs, ErrorCode::InvalidTransactionMessage);
+
+        Ok(())
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub struct VaultInstruction {
+    pub program_id: Pubkey,
+    pub accounts: Vec<VaultAccountMeta>,
+    pub data: Vec<u8>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub struct VaultAccountMeta {
+    pub pubkey: Pubkey,
+    pub is_signer: bool,
+    pub is_writable: bool,
+}
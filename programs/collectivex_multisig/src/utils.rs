@@ -0,0 +1,139 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{
+    allocate, assign, create_account, transfer, Allocate, Assign, CreateAccount, Transfer,
+};
+
+use crate::constants::ASSOCIATED_TOKEN_PROGRAM_ID;
+use crate::error::ErrorCode;
+
+/// Address of the associated token account of `owner` for `mint`.
+pub fn associated_token_address(owner: &Pubkey, mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[owner.as_ref(), anchor_spl::token::ID.as_ref(), mint.as_ref()],
+        &ASSOCIATED_TOKEN_PROGRAM_ID,
+    )
+    .0
+}
+
+/// Resizes a program account to `space` bytes, keeping it rent-exempt.
+///
+/// Growing is paid by `rent_payer`. The rent freed by shrinking goes to `rent_collector`,
+/// or stays in the account when there is none.
+pub fn realloc<'info>(
+    account: AccountInfo<'info>,
+    space: usize,
+    rent_payer: Option<AccountInfo<'info>>,
+    rent_collector: Option<AccountInfo<'info>>,
+    system_program: Option<AccountInfo<'info>>,
+) -> Result<()> {
+    if account.data_len() == space {
+        return Ok(());
+    }
+
+    let rent = Rent::get()?.minimum_balance(space);
+    let lamports = account.lamports();
+
+    if lamports < rent {
+        let rent_payer = rent_payer.ok_or(ErrorCode::MissingAccount)?;
+        let system_program = system_program.ok_or(ErrorCode::MissingAccount)?;
+
+        transfer(
+            CpiContext::new(
+                system_program,
+                Transfer {
+                    from: rent_payer,
+                    to: account.clone(),
+                },
+            ),
+            rent - lamports,
+        )?;
+    } else if let Some(rent_collector) = rent_collector {
+        **account.try_borrow_mut_lamports()? -= lamports - rent;
+        **rent_collector.try_borrow_mut_lamports()? += lamports - rent;
+    }
+
+    account.realloc(space, false)?;
+
+    Ok(())
+}
+
+/// Creates a rent-exempt account of `space` bytes owned by this program at the PDA signed
+/// for by `signer_seeds`.
+///
+/// Like Anchor's `init`, an address that already holds lamports is topped up, allocated and
+/// assigned instead, so sending lamports to the address beforehand cannot block its creation.
+pub fn create_program_account<'info>(
+    account: AccountInfo<'info>,
+    space: usize,
+    rent_payer: AccountInfo<'info>,
+    system_program: AccountInfo<'info>,
+    signer_seeds: &[&[u8]],
+) -> Result<()> {
+    let rent = Rent::get()?.minimum_balance(space);
+    let lamports = account.lamports();
+
+    if lamports == 0 {
+        return create_account(
+            CpiContext::new_with_signer(
+                system_program,
+                CreateAccount {
+                    from: rent_payer,
+                    to: account,
+                },
+                &[signer_seeds],
+            ),
+            rent,
+            space as u64,
+            &crate::ID,
+        );
+    }
+
+    if lamports < rent {
+        transfer(
+            CpiContext::new(
+                system_program.clone(),
+                Transfer {
+                    from: rent_payer,
+                    to: account.clone(),
+                },
+            ),
+            rent - lamports,
+        )?;
+    }
+
+    allocate(
+        CpiContext::new_with_signer(
+            system_program.clone(),
+            Allocate {
+                account_to_allocate: account.clone(),
+            },
+            &[signer_seeds],
+        ),
+        space as u64,
+    )?;
+
+    assign(
+        CpiContext::new_with_signer(
+            system_program,
+            Assign {
+                account_to_assign: account,
+            },
+            &[signer_seeds],
+        ),
+        &crate::ID,
+    )
+}
+
+/// Closes a program account, sending its lamports to `destination`.
+pub fn close_account<'info>(
+    account: &AccountInfo<'info>,
+    destination: &AccountInfo<'info>,
+) -> Result<()> {
+    **destination.try_borrow_mut_lamports()? += account.lamports();
+    **account.try_borrow_mut_lamports()? = 0;
+
+    account.assign(&System::id());
+    account.realloc(0, false)?;
+
+    Ok(())
+}
@@ -0,0 +1,569 @@
+//! Helpers shared by the integration test binaries, each of which uses only some of them.
+#![allow(dead_code)]
+
+use std::collections::HashSet;
+use std::sync::{Once, OnceLock};
+
+use anchor_lang::{AccountDeserialize, AnchorDeserialize, Discriminator};
+use anchor_spl::token::spl_token;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use collectivex_multisig::constants::ASSOCIATED_TOKEN_PROGRAM_ID;
+use collectivex_multisig::error::ErrorCode;
+use collectivex_multisig::state::{
+    Member, Permission, Permissions, VaultAccountMeta, VaultInstruction, VaultTransactionMessage,
+};
+use collectivex_multisig_sdk::{instructions, pda};
+use solana_program_test::{
+    processor, BanksClientError, ProgramTest, ProgramTestBanksClientExt, ProgramTestContext,
+};
+use solana_sdk::account::{Account, AccountSharedData};
+use solana_sdk::account_info::AccountInfo;
+use solana_sdk::bpf_loader_upgradeable::{self, UpgradeableLoaderState};
+use solana_sdk::clock::Clock;
+use solana_sdk::entrypoint::ProgramResult;
+use solana_sdk::instruction::{AccountMeta, Instruction, InstructionError};
+use solana_sdk::packet::PACKET_DATA_SIZE;
+use solana_sdk::program_pack::Pack;
+use solana_sdk::program_stubs::{set_syscall_stubs, SyscallStubs};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::rent::Rent;
+use solana_sdk::signature::{Keypair, Signature, Signer};
+use solana_sdk::system_instruction;
+use solana_sdk::transaction::{Transaction, TransactionError};
+
+pub type TestResult = Result<(), BanksClientError>;
+
+fn process_instruction(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    data: &[u8],
+) -> ProgramResult {
+    // Anchor requires the accounts slice to live as long as the accounts themselves.
+    let accounts = Box::leak(Box::new(accounts.to_vec()));
+
+    collectivex_multisig::entry(program_id, accounts, data)
+}
+
+/// The syscall stubs of program-test, which print the data logged by `emit!` to stdout.
+static PROGRAM_TEST_STUBS: OnceLock<Box<dyn SyscallStubs>> = OnceLock::new();
+
+/// Syscall stubs that write event data to the transaction logs, as `Program log: data: <base64>`,
+/// and leave every other syscall to program-test.
+struct EventLogStubs;
+
+impl EventLogStubs {
+    fn inner(&self) -> &dyn SyscallStubs {
+        // Set right after these stubs replace the ones of program-test.
+        loop {
+            if let Some(stubs) = PROGRAM_TEST_STUBS.get() {
+                return stubs.as_ref();
+            }
+            std::hint::spin_loop();
+        }
+    }
+
+    /// Installs the stubs, once program-test has installed its own.
+    fn install() {
+        static INSTALL: Once = Once::new();
+
+        INSTALL.call_once(|| {
+            let program_test_stubs = set_syscall_stubs(Box::new(EventLogStubs));
+            let _ = PROGRAM_TEST_STUBS.set(program_test_stubs);
+        });
+    }
+}
+
+impl SyscallStubs for EventLogStubs {
+    fn sol_log(&self, message: &str) {
+        self.inner().sol_log(message);
+    }
+
+    fn sol_log_compute_units(&self) {
+        self.inner().sol_log_compute_units();
+    }
+
+    fn sol_remaining_compute_units(&self) -> u64 {
+        self.inner().sol_remaining_compute_units()
+    }
+
+    fn sol_invoke_signed(
+        &self,
+        instruction: &Instruction,
+        account_infos: &[AccountInfo],
+        signers_seeds: &[&[&[u8]]],
+    ) -> ProgramResult {
+        self.inner()
+            .sol_invoke_signed(instruction, account_infos, signers_seeds)
+    }
+
+    fn sol_get_clock_sysvar(&self, var_addr: *mut u8) -> u64 {
+        self.inner().sol_get_clock_sysvar(var_addr)
+    }
+
+    fn sol_get_epoch_schedule_sysvar(&self, var_addr: *mut u8) -> u64 {
+        self.inner().sol_get_epoch_schedule_sysvar(var_addr)
+    }
+
+    fn sol_get_fees_sysvar(&self, var_addr: *mut u8) -> u64 {
+        self.inner().sol_get_fees_sysvar(var_addr)
+    }
+
+    fn sol_get_rent_sysvar(&self, var_addr: *mut u8) -> u64 {
+        self.inner().sol_get_rent_sysvar(var_addr)
+    }
+
+    fn sol_get_epoch_rewards_sysvar(&self, var_addr: *mut u8) -> u64 {
+        self.inner().sol_get_epoch_rewards_sysvar(var_addr)
+    }
+
+    fn sol_get_last_restart_slot(&self, var_addr: *mut u8) -> u64 {
+        self.inner().sol_get_last_restart_slot(var_addr)
+    }
+
+    fn sol_get_return_data(&self) -> Option<(Pubkey, Vec<u8>)> {
+        self.inner().sol_get_return_data()
+    }
+
+    fn sol_set_return_data(&self, data: &[u8]) {
+        self.inner().sol_set_return_data(data);
+    }
+
+    fn sol_log_data(&self, fields: &[&[u8]]) {
+        let fields: Vec<String> = fields.iter().map(|field| BASE64.encode(field)).collect();
+        self.inner().sol_log(&format!("data: {}", fields.join(" ")));
+    }
+
+    fn sol_get_processed_sibling_instruction(&self, index: usize) -> Option<Instruction> {
+        self.inner().sol_get_processed_sibling_instruction(index)
+    }
+
+    fn sol_get_stack_height(&self) -> u64 {
+        self.inner().sol_get_stack_height()
+    }
+}
+
+/// Decodes the events of type `T` from the logs of a transaction.
+pub fn events<T: AnchorDeserialize + Discriminator>(logs: &[String]) -> Vec<T> {
+    logs.iter()
+        .filter_map(|log| log.strip_prefix("Program log: data: "))
+        .filter_map(|data| BASE64.decode(data).ok())
+        .filter_map(|data| {
+            data.strip_prefix(&T::DISCRIMINATOR[..])
+                .map(|mut event| T::deserialize(&mut event).unwrap())
+        })
+        .collect()
+}
+
+/// An in-process runtime with the program loaded and a funded payer.
+pub struct TestContext {
+    pub context: ProgramTestContext,
+    signatures: HashSet<Signature>,
+}
+
+impl TestContext {
+    pub async fn new() -> Self {
+        Self::with_accounts(&[]).await
+    }
+
+    /// Starts a runtime holding the given program accounts, such as fixtures of legacy layouts.
+    pub async fn with_accounts(accounts: &[(Pubkey, Vec<u8>)]) -> Self {
+        let mut program_test = ProgramTest::new(
+            "collectivex_multisig",
+            collectivex_multisig::ID,
+            processor!(process_instruction),
+        );
+        program_test.prefer_bpf(false);
+
+        for (address, data) in accounts {
+            program_test.add_account(
+                *address,
+                Account {
+                    lamports: Rent::default().minimum_balance(data.len()),
+                    data: data.clone(),
+                    owner: collectivex_multisig::ID,
+                    executable: false,
+                    rent_epoch: 0,
+                },
+            );
+        }
+
+        let mut ctx = Self {
+            context: program_test.start_with_context().await,
+            signatures: HashSet::new(),
+        };
+        EventLogStubs::install();
+        let payer = ctx.payer();
+        ctx.set_upgrade_authority(Some(payer));
+
+        ctx
+    }
+
+    /// Writes the program data account of the program, as deployed by `upgrade_authority`.
+    /// `None` makes the program immutable.
+    pub fn set_upgrade_authority(&mut self, upgrade_authority: Option<Pubkey>) {
+        self.context
+            .set_account(&pda::program_data().0, &program_data_account(upgrade_authority));
+    }
+
+    /// Starts a runtime with a program config charging no creation fee.
+    pub async fn with_program_config() -> Self {
+        let mut ctx = Self::new().await;
+        let payer = ctx.payer();
+        ctx.send(&[instructions::program_config_init(payer, payer, 0, payer)], &[])
+            .await
+            .unwrap();
+
+        ctx
+    }
+
+    pub fn payer(&self) -> Pubkey {
+        self.context.payer.pubkey()
+    }
+
+    /// Sends the instructions, signed by the payer and `signers`.
+    pub async fn send(&mut self, instructions: &[Instruction], signers: &[&Keypair]) -> TestResult {
+        let transaction = self.sign(instructions, signers).await?;
+
+        self.context.banks_client.process_transaction(transaction).await
+    }
+
+    /// Sends the instructions like `send`, returning the logs of the transaction.
+    pub async fn send_with_logs(
+        &mut self,
+        instructions: &[Instruction],
+        signers: &[&Keypair],
+    ) -> Result<Vec<String>, BanksClientError> {
+        let transaction = self.sign(instructions, signers).await?;
+
+        // The logs are taken from a simulation: processing with metadata skips the queue
+        // `send` goes through, and can find accounts still locked by the previous transaction.
+        let simulation = self
+            .context
+            .banks_client
+            .simulate_transaction(transaction.clone())
+            .await?;
+        self.context.banks_client.process_transaction(transaction).await?;
+
+        Ok(simulation.simulation_details.map(|details| details.logs).unwrap_or_default())
+    }
+
+    async fn sign(
+        &mut self,
+        instructions: &[Instruction],
+        signers: &[&Keypair],
+    ) -> Result<Transaction, BanksClientError> {
+        let mut all_signers = vec![&self.context.payer];
+        all_signers.extend_from_slice(signers);
+
+        let mut blockhash = self.context.last_blockhash;
+        loop {
+            let transaction = Transaction::new_signed_with_payer(
+                instructions,
+                Some(&self.context.payer.pubkey()),
+                &all_signers,
+                blockhash,
+            );
+
+            // Program-test accepts transactions of any size, unlike a cluster.
+            let size = bincode::serialized_size(&transaction).unwrap() as usize;
+            assert!(size <= PACKET_DATA_SIZE, "transaction of {size} bytes");
+
+            // Identical transactions would be deduplicated by the runtime.
+            if self.signatures.insert(transaction.signatures[0]) {
+                return Ok(transaction);
+            }
+
+            blockhash = self
+                .context
+                .banks_client
+                .get_new_latest_blockhash(&blockhash)
+                .await?;
+        }
+    }
+
+    pub async fn account<T: AccountDeserialize>(&mut self, address: Pubkey) -> T {
+        let account = self
+            .context
+            .banks_client
+            .get_account(address)
+            .await
+            .unwrap()
+            .unwrap_or_else(|| panic!("account {address} does not exist"));
+
+        T::try_deserialize(&mut &account.data[..]).unwrap()
+    }
+
+    pub async fn exists(&mut self, address: Pubkey) -> bool {
+        self.context
+            .banks_client
+            .get_account(address)
+            .await
+            .unwrap()
+            .is_some()
+    }
+
+    pub async fn data_len(&mut self, address: Pubkey) -> usize {
+        self.context
+            .banks_client
+            .get_account(address)
+            .await
+            .unwrap()
+            .unwrap_or_else(|| panic!("account {address} does not exist"))
+            .data
+            .len()
+    }
+
+    /// The rent-exempt balance of an account holding `data_len` bytes.
+    pub async fn minimum_balance(&mut self, data_len: usize) -> u64 {
+        self.context
+            .banks_client
+            .get_rent()
+            .await
+            .unwrap()
+            .minimum_balance(data_len)
+    }
+
+    pub async fn lamports(&mut self, address: Pubkey) -> u64 {
+        self.context.banks_client.get_balance(address).await.unwrap()
+    }
+
+    pub async fn fund(&mut self, address: Pubkey, lamports: u64) {
+        let payer = self.payer();
+        self.send(&[system_instruction::transfer(&payer, &address, lamports)], &[])
+            .await
+            .unwrap();
+    }
+
+    /// The current unix timestamp of the runtime.
+    pub async fn now(&mut self) -> i64 {
+        let clock: Clock = self.context.banks_client.get_sysvar().await.unwrap();
+        clock.unix_timestamp
+    }
+
+    /// Moves the clock forward by `seconds`.
+    pub async fn warp(&mut self, seconds: i64) {
+        let mut clock: Clock = self.context.banks_client.get_sysvar().await.unwrap();
+        clock.unix_timestamp += seconds;
+        self.context.set_sysvar(&clock);
+    }
+
+    pub async fn create_multisig(
+        &mut self,
+        config_authority: Pubkey,
+        threshold: u16,
+        members: Vec<Member>,
+    ) -> Pubkey {
+        let create_key = Keypair::new();
+        let payer = self.payer();
+        let treasury = self
+            .account::<collectivex_multisig::state::ProgramConfig>(pda::program_config().0)
+            .await
+            .treasury;
+
+        self.send(
+            &[instructions::multisig_create(
+                payer,
+                create_key.pubkey(),
+                treasury,
+                false,
+                config_authority,
+                threshold,
+                members,
+                0,
+            )],
+            &[&create_key],
+        )
+        .await
+        .unwrap();
+
+        pda::multisig(&create_key.pubkey()).0
+    }
+
+    /// Creates the next transaction of `multisig` with the instruction `create_transaction`
+    /// builds for its index, then proposes and approves it as the payer. Returns the index.
+    pub async fn create_approved(
+        &mut self,
+        multisig: Pubkey,
+        create_transaction: impl FnOnce(u64) -> Instruction,
+    ) -> u64 {
+        let payer = self.payer();
+        let index = self
+            .account::<collectivex_multisig::state::Multisig>(multisig)
+            .await
+            .transaction_index
+            + 1;
+
+        self.send(
+            &[
+                create_transaction(index),
+                instructions::proposal_create(multisig, payer, payer, index, false),
+                instructions::proposal_approve(multisig, payer, index, None),
+            ],
+            &[],
+        )
+        .await
+        .unwrap();
+
+        index
+    }
+
+    /// Creates a mint with 6 decimals, with the payer as mint authority.
+    pub async fn create_mint(&mut self) -> Pubkey {
+        let payer = self.payer();
+        let mint = Keypair::new();
+        let rent = self.minimum_balance(spl_token::state::Mint::LEN).await;
+
+        self.send(
+            &[
+                system_instruction::create_account(
+                    &payer,
+                    &mint.pubkey(),
+                    rent,
+                    spl_token::state::Mint::LEN as u64,
+                    &spl_token::ID,
+                ),
+                spl_token::instruction::initialize_mint2(&spl_token::ID, &mint.pubkey(), &payer, None, 6)
+                    .unwrap(),
+            ],
+            &[&mint],
+        )
+        .await
+        .unwrap();
+
+        mint.pubkey()
+    }
+
+    /// Creates a token account of `owner` holding `amount` tokens.
+    pub async fn create_token_account(&mut self, mint: Pubkey, owner: Pubkey, amount: u64) -> Pubkey {
+        let payer = self.payer();
+        let account = Keypair::new();
+        let rent = self.minimum_balance(spl_token::state::Account::LEN).await;
+
+        self.send(
+            &[
+                system_instruction::create_account(
+                    &payer,
+                    &account.pubkey(),
+                    rent,
+                    spl_token::state::Account::LEN as u64,
+                    &spl_token::ID,
+                ),
+                spl_token::instruction::initialize_account3(&spl_token::ID, &account.pubkey(), &mint, &owner)
+                    .unwrap(),
+                spl_token::instruction::mint_to(&spl_token::ID, &mint, &account.pubkey(), &payer, &[], amount)
+                    .unwrap(),
+            ],
+            &[&account],
+        )
+        .await
+        .unwrap();
+
+        account.pubkey()
+    }
+
+    /// Creates the associated token account of `owner` for `mint`, holding no tokens.
+    pub async fn create_associated_token_account(&mut self, mint: Pubkey, owner: Pubkey) -> Pubkey {
+        let payer = self.payer();
+        let account = pda::associated_token_account(&owner, &mint);
+
+        // The `Create` instruction of the associated token account program.
+        let create = Instruction {
+            program_id: ASSOCIATED_TOKEN_PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(payer, true),
+                AccountMeta::new(account, false),
+                AccountMeta::new_readonly(owner, false),
+                AccountMeta::new_readonly(mint, false),
+                AccountMeta::new_readonly(solana_sdk::system_program::ID, false),
+                AccountMeta::new_readonly(spl_token::ID, false),
+            ],
+            data: vec![0],
+        };
+
+        self.send(&[create], &[]).await.unwrap();
+
+        account
+    }
+}
+
+/// A program data account of the upgradeable loader, with `upgrade_authority`.
+pub fn program_data_account(upgrade_authority: Option<Pubkey>) -> AccountSharedData {
+    let state = UpgradeableLoaderState::ProgramData {
+        slot: 0,
+        upgrade_authority_address: upgrade_authority,
+    };
+    let account = Account::new_data(
+        Rent::default().minimum_balance(UpgradeableLoaderState::size_of_programdata_metadata()),
+        &state,
+        &bpf_loader_upgradeable::ID,
+    )
+    .unwrap();
+
+    AccountSharedData::from(account)
+}
+
+/// A message transferring `lamports` from `vault` to `destination`.
+pub fn transfer_message(vault: Pubkey, destination: Pubkey, lamports: u64) -> VaultTransactionMessage {
+    let ix = system_instruction::transfer(&vault, &destination, lamports);
+
+    VaultTransactionMessage {
+        instructions: vec![VaultInstruction {
+            program_id: ix.program_id,
+            accounts: ix
+                .accounts
+                .iter()
+                .map(|meta| VaultAccountMeta {
+                    pubkey: meta.pubkey,
+                    is_signer: meta.is_signer,
+                    is_writable: meta.is_writable,
+                })
+                .collect(),
+            data: ix.data,
+        }],
+    }
+}
+
+pub fn member(key: Pubkey) -> Member {
+    Member {
+        key,
+        permissions: Permissions::all(),
+        weight: 1,
+    }
+}
+
+pub fn member_with(key: Pubkey, permissions: &[Permission], weight: u16) -> Member {
+    Member {
+        key,
+        permissions: Permissions::from_vec(permissions),
+        weight,
+    }
+}
+
+fn instruction_error(result: TestResult) -> InstructionError {
+    match result {
+        Err(BanksClientError::TransactionError(TransactionError::InstructionError(_, err))) => err,
+        other => panic!("expected an instruction error, got {other:?}"),
+    }
+}
+
+/// Asserts that the transaction failed with the program error `error`.
+pub fn assert_error(result: TestResult, error: ErrorCode) {
+    let expected = u32::from(error);
+
+    match instruction_error(result) {
+        InstructionError::Custom(code) => assert_eq!(
+            code, expected,
+            "expected {error:?} ({expected}), got error code {code}"
+        ),
+        err => panic!("expected {error:?}, got {err:?}"),
+    }
+}
+
+/// Asserts that the transaction failed with the Anchor framework error `error`.
+pub fn assert_anchor_error(result: TestResult, error: anchor_lang::error::ErrorCode) {
+    assert_eq!(
+        instruction_error(result),
+        InstructionError::Custom(u32::from(error))
+    );
+}
@@ -0,0 +1,1210 @@
+mod common;
+
+use anchor_lang::error::ErrorCode as AnchorErrorCode;
+use collectivex_multisig::constants::{
+    MAX_MEMBERS, MAX_SPENDING_LIMIT_DESTINATIONS, MAX_SPENDING_LIMIT_MEMBERS, MAX_TIME_LOCK,
+};
+use collectivex_multisig::error::ErrorCode;
+use collectivex_multisig::state::{
+    ConfigAction, ConfigTransaction, Member, Multisig, Period, Permission, Permissions, Proposal,
+    ProposalStatus, SpendingLimit,
+};
+use collectivex_multisig_sdk::{instructions, pda};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+
+use crate::common::{
+    assert_anchor_error, assert_error, member, member_with, transfer_message, TestContext,
+    TestResult,
+};
+
+/// An autonomous multisig whose only member is the payer.
+async fn autonomous_multisig(ctx: &mut TestContext) -> Pubkey {
+    let payer = ctx.payer();
+    ctx.create_multisig(Pubkey::default(), 1, vec![member(payer)])
+        .await
+}
+
+/// Creates, approves and executes a config transaction.
+async fn execute(ctx: &mut TestContext, multisig: Pubkey, actions: Vec<ConfigAction>) -> TestResult {
+    let payer = ctx.payer();
+    let index = ctx
+        .create_approved(multisig, |index| {
+            instructions::config_transaction_create(multisig, payer, payer, index, actions.clone())
+        })
+        .await;
+
+    ctx.send(
+        &[instructions::config_transaction_execute(
+            multisig,
+            payer,
+            index,
+            &actions,
+            Some(payer),
+            None,
+        )],
+        &[],
+    )
+    .await
+}
+
+fn add_spending_limit(create_key: Pubkey, members: Vec<Pubkey>) -> ConfigAction {
+    ConfigAction::AddSpendingLimit {
+        create_key,
+        vault_index: 0,
+        mint: Pubkey::default(),
+        amount: 1_000,
+        period: Period::Day,
+        members,
+        destinations: vec![Pubkey::new_unique()],
+    }
+}
+
+#[tokio::test]
+async fn config_transaction_create() {
+    let mut ctx = TestContext::with_program_config().await;
+    let payer = ctx.payer();
+    let multisig = autonomous_multisig(&mut ctx).await;
+    let actions = vec![ConfigAction::SetTimeLock { new_time_lock: 60 }];
+
+    ctx.send(
+        &[instructions::config_transaction_create(multisig, payer, payer, 1, actions.clone())],
+        &[],
+    )
+    .await
+    .unwrap();
+
+    let transaction: ConfigTransaction = ctx.account(pda::transaction(&multisig, 1).0).await;
+    assert_eq!(transaction.creator, payer);
+    assert_eq!(transaction.index, 1);
+    assert!(transaction.actions == actions);
+}
+
+#[tokio::test]
+async fn config_transaction_create_requires_initiate_permission() {
+    let mut ctx = TestContext::with_program_config().await;
+    let payer = ctx.payer();
+    let voter = Keypair::new();
+    let multisig = ctx
+        .create_multisig(
+            Pubkey::default(),
+            1,
+            vec![member(payer), member_with(voter.pubkey(), &[Permission::Vote], 1)],
+        )
+        .await;
+
+    let result = ctx
+        .send(
+            &[instructions::config_transaction_create(
+                multisig,
+                voter.pubkey(),
+                payer,
+                1,
+                vec![ConfigAction::SetTimeLock { new_time_lock: 60 }],
+            )],
+            &[&voter],
+        )
+        .await;
+
+    assert_error(result, ErrorCode::Unauthorized);
+}
+
+#[tokio::test]
+async fn config_transaction_create_rejects_controlled_multisigs() {
+    let mut ctx = TestContext::with_program_config().await;
+    let payer = ctx.payer();
+    let multisig = ctx.create_multisig(payer, 1, vec![member(payer)]).await;
+
+    let result = ctx
+        .send(
+            &[instructions::config_transaction_create(
+                multisig,
+                payer,
+                payer,
+                1,
+                vec![ConfigAction::SetTimeLock { new_time_lock: 60 }],
+            )],
+            &[],
+        )
+        .await;
+
+    assert_error(result, ErrorCode::NotSupportedForControlled);
+}
+
+#[tokio::test]
+async fn config_transaction_create_requires_actions() {
+    let mut ctx = TestContext::with_program_config().await;
+    let payer = ctx.payer();
+    let multisig = autonomous_multisig(&mut ctx).await;
+
+    let result = ctx
+        .send(
+            &[instructions::config_transaction_create(multisig, payer, payer, 1, vec![])],
+            &[],
+        )
+        .await;
+
+    assert_error(result, ErrorCode::NoActions);
+}
+
+#[tokio::test]
+async fn config_transaction_execute() {
+    let mut ctx = TestContext::with_program_config().await;
+    let payer = ctx.payer();
+    let multisig = autonomous_multisig(&mut ctx).await;
+    let new_member = Pubkey::new_unique();
+    let rent_collector = Pubkey::new_unique();
+
+    execute(
+        &mut ctx,
+        multisig,
+        vec![
+            ConfigAction::AddMember {
+                new_member: member(new_member),
+            },
+            ConfigAction::SetMemberPermissions {
+                member: new_member,
+                permissions: Permissions::from_vec(&[Permission::Vote]),
+            },
+            ConfigAction::SetMemberWeight {
+                member: new_member,
+                weight: 2,
+            },
+            ConfigAction::ChangeThreshold { new_threshold: 3 },
+            ConfigAction::SetTimeLock { new_time_lock: 60 },
+            ConfigAction::SetRentCollector {
+                new_rent_collector: Some(rent_collector),
+            },
+            ConfigAction::SetVotingPeriod {
+                new_voting_period: Some(3600),
+            },
+        ],
+    )
+    .await
+    .unwrap();
+
+    let account: Multisig = ctx.account(multisig).await;
+    assert_eq!(
+        account.members,
+        vec![
+            member(payer),
+            member_with(new_member, &[Permission::Vote], 2)
+        ]
+    );
+    assert_eq!(account.threshold, 3);
+    assert_eq!(account.time_lock, 60);
+    assert_eq!(account.rent_collector, Some(rent_collector));
+    assert_eq!(account.voting_period, Some(3600));
+    assert_eq!(account.stale_transaction_index, 1);
+
+    let proposal: Proposal = ctx.account(pda::proposal(&multisig, 1).0).await;
+    assert!(matches!(proposal.status, ProposalStatus::Executed { .. }));
+}
+
+#[tokio::test]
+async fn config_transaction_execute_remove_member() {
+    let mut ctx = TestContext::with_program_config().await;
+    let payer = ctx.payer();
+    let other = Pubkey::new_unique();
+    let multisig = ctx
+        .create_multisig(Pubkey::default(), 1, vec![member(payer), member(other)])
+        .await;
+
+    execute(
+        &mut ctx,
+        multisig,
+        vec![ConfigAction::RemoveMember { old_member: other }],
+    )
+    .await
+    .unwrap();
+    assert_eq!(ctx.account::<Multisig>(multisig).await.members, vec![member(payer)]);
+
+    let result = execute(
+        &mut ctx,
+        multisig,
+        vec![ConfigAction::RemoveMember { old_member: payer }],
+    )
+    .await;
+    assert_error(result, ErrorCode::RemoveLastMember);
+}
+
+#[tokio::test]
+async fn config_transaction_execute_validates_members() {
+    let mut ctx = TestContext::with_program_config().await;
+    let payer = ctx.payer();
+    let multisig = autonomous_multisig(&mut ctx).await;
+
+    let cases = [
+        (
+            ConfigAction::AddMember {
+                new_member: member(payer),
+            },
+            ErrorCode::DuplicateMember,
+        ),
+        (
+            ConfigAction::AddMember {
+                new_member: Member {
+                    permissions: Permissions { mask: 8 },
+                    ..member(Pubkey::new_unique())
+                },
+            },
+            ErrorCode::InvalidPermissions,
+        ),
+        (
+            ConfigAction::SetMemberWeight {
+                member: payer,
+                weight: 0,
+            },
+            ErrorCode::InvalidWeight,
+        ),
+        (
+            ConfigAction::SetMemberPermissions {
+                member: Pubkey::new_unique(),
+                permissions: Permissions::all(),
+            },
+            ErrorCode::NotAMember,
+        ),
+        (
+            ConfigAction::ChangeThreshold { new_threshold: 2 },
+            ErrorCode::InvalidThreshold,
+        ),
+        (
+            ConfigAction::ChangeThreshold { new_threshold: 0 },
+            ErrorCode::ZeroThreshold,
+        ),
+        (
+            ConfigAction::SetTimeLock {
+                new_time_lock: MAX_TIME_LOCK + 1,
+            },
+            ErrorCode::TimeLockTooLong,
+        ),
+        (
+            ConfigAction::SetVotingPeriod {
+                new_voting_period: Some(0),
+            },
+            ErrorCode::InvalidVotingPeriod,
+        ),
+    ];
+
+    for (action, error) in cases {
+        let result = execute(&mut ctx, multisig, vec![action]).await;
+        assert_error(result, error);
+    }
+}
+
+#[tokio::test]
+async fn config_transaction_execute_caps_members() {
+    let mut ctx = TestContext::with_program_config().await;
+    let payer = ctx.payer();
+    let multisig = autonomous_multisig(&mut ctx).await;
+    let add_members = |count| {
+        (0..count)
+            .map(|_| ConfigAction::AddMember {
+                new_member: member(Pubkey::new_unique()),
+            })
+            .collect::<Vec<_>>()
+    };
+
+    // Growing the multisig account requires a rent payer.
+    let actions = add_members(1);
+    let index = ctx
+        .create_approved(multisig, |index| {
+            instructions::config_transaction_create(multisig, payer, payer, index, actions.clone())
+        })
+        .await;
+    let result = ctx
+        .send(
+            &[instructions::config_transaction_execute(multisig, payer, index, &actions, None, None)],
+            &[],
+        )
+        .await;
+    assert_error(result, ErrorCode::MissingAccount);
+
+    // Transactions are too small to add every member at once.
+    for count in [15, 15, 15, 4] {
+        execute(&mut ctx, multisig, add_members(count)).await.unwrap();
+    }
+    assert_eq!(ctx.account::<Multisig>(multisig).await.members.len(), MAX_MEMBERS);
+    assert_eq!(ctx.account::<Multisig>(multisig).await.members[0], member(payer));
+    assert_eq!(ctx.data_len(multisig).await, Multisig::size(MAX_MEMBERS));
+
+    let result = execute(&mut ctx, multisig, add_members(1)).await;
+    assert_error(result, ErrorCode::ExceedsMaxMembers);
+}
+
+#[tokio::test]
+async fn config_transaction_execute_remove_member_refunds_the_rent_collector() {
+    let mut ctx = TestContext::with_program_config().await;
+    let payer = ctx.payer();
+    let other = Pubkey::new_unique();
+    let rent_collector = Pubkey::new_unique();
+    let multisig = ctx
+        .create_multisig(Pubkey::default(), 1, vec![member(payer), member(other)])
+        .await;
+    ctx.fund(rent_collector, 1_000_000).await;
+    execute(
+        &mut ctx,
+        multisig,
+        vec![ConfigAction::SetRentCollector {
+            new_rent_collector: Some(rent_collector),
+        }],
+    )
+    .await
+    .unwrap();
+    let lamports = ctx.lamports(multisig).await;
+
+    let actions = vec![ConfigAction::RemoveMember { old_member: other }];
+    let index = ctx
+        .create_approved(multisig, |index| {
+            instructions::config_transaction_create(multisig, payer, payer, index, actions.clone())
+        })
+        .await;
+    let result = ctx
+        .send(
+            &[instructions::config_transaction_execute(
+                multisig,
+                payer,
+                index,
+                &actions,
+                None,
+                Some(Pubkey::new_unique()),
+            )],
+            &[],
+        )
+        .await;
+    assert_error(result, ErrorCode::InvalidRentCollector);
+
+    ctx.send(
+        &[instructions::config_transaction_execute(
+            multisig,
+            payer,
+            index,
+            &actions,
+            None,
+            Some(rent_collector),
+        )],
+        &[],
+    )
+    .await
+    .unwrap();
+
+    let rent = ctx.minimum_balance(Multisig::size(1)).await;
+    assert_eq!(ctx.data_len(multisig).await, Multisig::size(1));
+    assert_eq!(ctx.lamports(multisig).await, rent);
+    assert_eq!(ctx.lamports(rent_collector).await, 1_000_000 + lamports - rent);
+}
+
+#[tokio::test]
+async fn config_transaction_execute_spending_limits() {
+    let mut ctx = TestContext::with_program_config().await;
+    let payer = ctx.payer();
+    let multisig = autonomous_multisig(&mut ctx).await;
+    let create_key = Pubkey::new_unique();
+    let spending_limit = pda::spending_limit(&multisig, &create_key).0;
+
+    execute(&mut ctx, multisig, vec![add_spending_limit(create_key, vec![payer])])
+        .await
+        .unwrap();
+
+    let account: SpendingLimit = ctx.account(spending_limit).await;
+    assert_eq!(account.multisig, multisig);
+    assert_eq!(account.create_key, create_key);
+    assert_eq!(account.amount, 1_000);
+    assert_eq!(account.remaining_amount, 1_000);
+    assert_eq!(account.period, Period::Day);
+    assert_eq!(account.members, vec![payer]);
+    // Spending limits do not change the voting configuration.
+    assert_eq!(ctx.account::<Multisig>(multisig).await.stale_transaction_index, 0);
+
+    // Without a rent collector, the rent goes back to the rent payer.
+    let rent = ctx.lamports(spending_limit).await;
+    let rent_payer = Keypair::new();
+    let actions = vec![ConfigAction::RemoveSpendingLimit { spending_limit }];
+    let index = ctx
+        .create_approved(multisig, |index| {
+            instructions::config_transaction_create(multisig, payer, payer, index, actions.clone())
+        })
+        .await;
+    ctx.send(
+        &[instructions::config_transaction_execute(
+            multisig,
+            payer,
+            index,
+            &actions,
+            Some(rent_payer.pubkey()),
+            None,
+        )],
+        &[&rent_payer],
+    )
+    .await
+    .unwrap();
+    assert!(!ctx.exists(spending_limit).await);
+    assert_eq!(ctx.lamports(rent_payer.pubkey()).await, rent);
+
+    let rent_collector = Pubkey::new_unique();
+    execute(
+        &mut ctx,
+        multisig,
+        vec![
+            add_spending_limit(create_key, vec![payer]),
+            ConfigAction::SetRentCollector {
+                new_rent_collector: Some(rent_collector),
+            },
+        ],
+    )
+    .await
+    .unwrap();
+
+    // The rent goes to the rent collector of the multisig, not to the executor.
+    let index = ctx
+        .create_approved(multisig, |index| {
+            instructions::config_transaction_create(multisig, payer, payer, index, actions.clone())
+        })
+        .await;
+    let result = ctx
+        .send(
+            &[instructions::config_transaction_execute(
+                multisig,
+                payer,
+                index,
+                &actions,
+                Some(payer),
+                None,
+            )],
+            &[],
+        )
+        .await;
+    assert_error(result, ErrorCode::MissingAccount);
+
+    ctx.send(
+        &[instructions::config_transaction_execute(
+            multisig,
+            payer,
+            index,
+            &actions,
+            None,
+            Some(rent_collector),
+        )],
+        &[],
+    )
+    .await
+    .unwrap();
+    assert!(!ctx.exists(spending_limit).await);
+    assert_eq!(ctx.lamports(rent_collector).await, rent);
+}
+
+#[tokio::test]
+async fn config_transaction_execute_removes_spending_limits_to_a_new_rent_collector() {
+    let mut ctx = TestContext::with_program_config().await;
+    let payer = ctx.payer();
+    let multisig = autonomous_multisig(&mut ctx).await;
+    let create_key = Pubkey::new_unique();
+    let spending_limit = pda::spending_limit(&multisig, &create_key).0;
+    execute(&mut ctx, multisig, vec![add_spending_limit(create_key, vec![payer])])
+        .await
+        .unwrap();
+    let rent = ctx.lamports(spending_limit).await;
+
+    // The rent collector is checked against the configuration the actions result in.
+    let rent_collector = Pubkey::new_unique();
+    let actions = vec![
+        ConfigAction::SetRentCollector {
+            new_rent_collector: Some(rent_collector),
+        },
+        ConfigAction::RemoveSpendingLimit { spending_limit },
+    ];
+    let index = ctx
+        .create_approved(multisig, |index| {
+            instructions::config_transaction_create(multisig, payer, payer, index, actions.clone())
+        })
+        .await;
+    ctx.send(
+        &[instructions::config_transaction_execute(
+            multisig,
+            payer,
+            index,
+            &actions,
+            None,
+            Some(rent_collector),
+        )],
+        &[],
+    )
+    .await
+    .unwrap();
+
+    assert!(!ctx.exists(spending_limit).await);
+    assert_eq!(ctx.lamports(rent_collector).await, rent);
+}
+
+#[tokio::test]
+async fn config_transaction_execute_adds_spending_limits_at_funded_addresses() {
+    let mut ctx = TestContext::with_program_config().await;
+    let payer = ctx.payer();
+    let multisig = autonomous_multisig(&mut ctx).await;
+    let create_key = Pubkey::new_unique();
+    let spending_limit = pda::spending_limit(&multisig, &create_key).0;
+
+    // Anyone can send lamports to the address once the action is public.
+    let lamports = ctx.minimum_balance(0).await;
+    ctx.fund(spending_limit, lamports).await;
+    execute(&mut ctx, multisig, vec![add_spending_limit(create_key, vec![payer])])
+        .await
+        .unwrap();
+
+    let account: SpendingLimit = ctx.account(spending_limit).await;
+    assert_eq!(account.create_key, create_key);
+    let data_len = ctx.data_len(spending_limit).await;
+    let rent = ctx.minimum_balance(data_len).await;
+    assert_eq!(ctx.lamports(spending_limit).await, rent);
+}
+
+#[tokio::test]
+async fn autonomous_multisigs_change_only_through_config_transactions() {
+    let mut ctx = TestContext::with_program_config().await;
+    let payer = ctx.payer();
+    let multisig = autonomous_multisig(&mut ctx).await;
+    let create_key = Pubkey::new_unique();
+    let spending_limit = pda::spending_limit(&multisig, &create_key).0;
+    execute(&mut ctx, multisig, vec![add_spending_limit(create_key, vec![payer])])
+        .await
+        .unwrap();
+
+    let new_member = member(Pubkey::new_unique());
+    let new_create_key = Keypair::new();
+    for (ix, signers) in [
+        (instructions::multisig_add_member(multisig, payer, payer, new_member), vec![]),
+        (instructions::multisig_add_spending_limit(
+            multisig,
+            payer,
+            new_create_key.pubkey(),
+            0,
+            Pubkey::default(),
+            1_000,
+            Period::Day,
+            vec![payer],
+            vec![],
+        ), vec![&new_create_key]),
+        (instructions::multisig_remove_spending_limit(multisig, payer, spending_limit, payer, None), vec![]),
+    ] {
+        let result = ctx.send(&[ix], &signers).await;
+        assert_error(result, ErrorCode::NotSupportedForAutonomous);
+    }
+
+    execute(
+        &mut ctx,
+        multisig,
+        vec![
+            ConfigAction::AddMember { new_member },
+            ConfigAction::RemoveSpendingLimit { spending_limit },
+        ],
+    )
+    .await
+    .unwrap();
+    assert!(ctx.account::<Multisig>(multisig).await.members.contains(&new_member));
+    assert!(!ctx.exists(spending_limit).await);
+}
+
+#[tokio::test]
+async fn config_transaction_execute_validates_spending_limits() {
+    let mut ctx = TestContext::with_program_config().await;
+    let payer = ctx.payer();
+    let multisig = autonomous_multisig(&mut ctx).await;
+
+    let result = execute(
+        &mut ctx,
+        multisig,
+        vec![ConfigAction::AddSpendingLimit {
+            create_key: Pubkey::new_unique(),
+            vault_index: 0,
+            mint: Pubkey::default(),
+            amount: 1_000,
+            period: Period::Custom { seconds: 0 },
+            members: vec![payer],
+            destinations: vec![],
+        }],
+    )
+    .await;
+    assert_error(result, ErrorCode::InvalidPeriod);
+
+    // The spending limit to remove must be passed in the remaining accounts.
+    execute(
+        &mut ctx,
+        multisig,
+        vec![ConfigAction::SetRentCollector {
+            new_rent_collector: Some(payer),
+        }],
+    )
+    .await
+    .unwrap();
+    let spending_limit = Pubkey::new_unique();
+    let actions = vec![ConfigAction::RemoveSpendingLimit { spending_limit }];
+    let index = ctx
+        .create_approved(multisig, |index| {
+            instructions::config_transaction_create(multisig, payer, payer, index, actions.clone())
+        })
+        .await;
+    let mut ix = instructions::config_transaction_execute(
+        multisig,
+        payer,
+        index,
+        &actions,
+        None,
+        Some(payer),
+    );
+    ix.accounts.retain(|meta| meta.pubkey != spending_limit);
+    let result = ctx.send(&[ix], &[]).await;
+    assert_error(result, ErrorCode::InvalidSpendingLimit);
+
+    // Creating a spending limit requires a rent payer.
+    let actions = vec![add_spending_limit(Pubkey::new_unique(), vec![payer])];
+    let index = ctx
+        .create_approved(multisig, |index| {
+            instructions::config_transaction_create(multisig, payer, payer, index, actions.clone())
+        })
+        .await;
+    let result = ctx
+        .send(
+            &[instructions::config_transaction_execute(multisig, payer, index, &actions, None, None)],
+            &[],
+        )
+        .await;
+    assert_error(result, ErrorCode::MissingAccount);
+}
+
+#[tokio::test]
+async fn config_transaction_execute_caps_spending_limit_members_and_destinations() {
+    let mut ctx = TestContext::with_program_config().await;
+    let multisig = autonomous_multisig(&mut ctx).await;
+    let keys = |count: usize| (0..count).map(|_| Pubkey::new_unique()).collect::<Vec<_>>();
+
+    for (members, destinations, error) in [
+        (MAX_SPENDING_LIMIT_MEMBERS, MAX_SPENDING_LIMIT_DESTINATIONS, None),
+        (
+            MAX_SPENDING_LIMIT_MEMBERS + 1,
+            MAX_SPENDING_LIMIT_DESTINATIONS,
+            Some(ErrorCode::ExceedsMaxMembers),
+        ),
+        (
+            MAX_SPENDING_LIMIT_MEMBERS,
+            MAX_SPENDING_LIMIT_DESTINATIONS + 1,
+            Some(ErrorCode::ExceedsMaxDestinations),
+        ),
+    ] {
+        let action = ConfigAction::AddSpendingLimit {
+            create_key: Pubkey::new_unique(),
+            vault_index: 0,
+            mint: Pubkey::default(),
+            amount: 1_000,
+            period: Period::Day,
+            members: keys(members),
+            destinations: keys(destinations),
+        };
+        let result = execute(&mut ctx, multisig, vec![action]).await;
+
+        match error {
+            None => result.unwrap(),
+            Some(error) => assert_error(result, error),
+        }
+    }
+}
+
+#[tokio::test]
+async fn config_transaction_execute_applies_the_actions_once() {
+    let mut ctx = TestContext::with_program_config().await;
+    let payer = ctx.payer();
+    let multisig = autonomous_multisig(&mut ctx).await;
+    let actions = vec![ConfigAction::AddMember {
+        new_member: member(Pubkey::new_unique()),
+    }];
+    let index = ctx
+        .create_approved(multisig, |index| {
+            instructions::config_transaction_create(multisig, payer, payer, index, actions.clone())
+        })
+        .await;
+    let ix = instructions::config_transaction_execute(multisig, payer, index, &actions, Some(payer), None);
+
+    ctx.send(std::slice::from_ref(&ix), &[]).await.unwrap();
+    assert_eq!(ctx.account::<Multisig>(multisig).await.members.len(), 2);
+
+    // An executed proposal cannot be executed again.
+    let result = ctx.send(&[ix], &[]).await;
+    assert_error(result, ErrorCode::InvalidProposalStatus);
+    assert_eq!(ctx.account::<Multisig>(multisig).await.members.len(), 2);
+}
+
+#[tokio::test]
+async fn config_transaction_execute_requires_membership() {
+    let mut ctx = TestContext::with_program_config().await;
+    let payer = ctx.payer();
+    let multisig = autonomous_multisig(&mut ctx).await;
+    let actions = vec![ConfigAction::SetTimeLock { new_time_lock: 60 }];
+    let index = ctx
+        .create_approved(multisig, |index| {
+            instructions::config_transaction_create(multisig, payer, payer, index, actions.clone())
+        })
+        .await;
+
+    let outsider = Keypair::new();
+    let result = ctx
+        .send(
+            &[instructions::config_transaction_execute(
+                multisig,
+                outsider.pubkey(),
+                index,
+                &actions,
+                None,
+                None,
+            )],
+            &[&outsider],
+        )
+        .await;
+    assert_error(result, ErrorCode::NotAMember);
+
+    ctx.send(
+        &[instructions::config_transaction_execute(multisig, payer, index, &actions, None, None)],
+        &[],
+    )
+    .await
+    .unwrap();
+    assert_eq!(ctx.account::<Multisig>(multisig).await.time_lock, 60);
+}
+
+#[tokio::test]
+async fn config_transaction_execute_requires_approval() {
+    let mut ctx = TestContext::with_program_config().await;
+    let payer = ctx.payer();
+    let multisig = autonomous_multisig(&mut ctx).await;
+    let actions = vec![ConfigAction::SetTimeLock { new_time_lock: 60 }];
+
+    ctx.send(
+        &[
+            instructions::config_transaction_create(multisig, payer, payer, 1, actions.clone()),
+            instructions::proposal_create(multisig, payer, payer, 1, false),
+        ],
+        &[],
+    )
+    .await
+    .unwrap();
+
+    let result = ctx
+        .send(
+            &[instructions::config_transaction_execute(multisig, payer, 1, &actions, None, None)],
+            &[],
+        )
+        .await;
+
+    assert_error(result, ErrorCode::InvalidProposalStatus);
+}
+
+#[tokio::test]
+async fn config_transaction_execute_respects_the_time_lock() {
+    let mut ctx = TestContext::with_program_config().await;
+    let payer = ctx.payer();
+    let multisig = autonomous_multisig(&mut ctx).await;
+    execute(
+        &mut ctx,
+        multisig,
+        vec![ConfigAction::SetTimeLock { new_time_lock: 3600 }],
+    )
+    .await
+    .unwrap();
+
+    let actions = vec![ConfigAction::SetTimeLock { new_time_lock: 0 }];
+    let index = ctx
+        .create_approved(multisig, |index| {
+            instructions::config_transaction_create(multisig, payer, payer, index, actions.clone())
+        })
+        .await;
+    let ix = instructions::config_transaction_execute(multisig, payer, index, &actions, None, None);
+
+    let result = ctx.send(std::slice::from_ref(&ix), &[]).await;
+    assert_error(result, ErrorCode::TimeLockNotReleased);
+
+    ctx.warp(3600).await;
+    ctx.send(&[ix], &[]).await.unwrap();
+    assert_eq!(ctx.account::<Multisig>(multisig).await.time_lock, 0);
+}
+
+#[tokio::test]
+async fn config_transaction_execute_rejects_stale_proposals() {
+    let mut ctx = TestContext::with_program_config().await;
+    let payer = ctx.payer();
+    let multisig = autonomous_multisig(&mut ctx).await;
+
+    let first = vec![ConfigAction::SetTimeLock { new_time_lock: 60 }];
+    let second = vec![ConfigAction::ChangeThreshold { new_threshold: 1 }];
+    let first_index = ctx
+        .create_approved(multisig, |index| {
+            instructions::config_transaction_create(multisig, payer, payer, index, first.clone())
+        })
+        .await;
+    let second_index = ctx
+        .create_approved(multisig, |index| {
+            instructions::config_transaction_create(multisig, payer, payer, index, second.clone())
+        })
+        .await;
+
+    ctx.send(
+        &[instructions::config_transaction_execute(multisig, payer, second_index, &second, None, None)],
+        &[],
+    )
+    .await
+    .unwrap();
+
+    let result = ctx
+        .send(
+            &[instructions::config_transaction_execute(multisig, payer, first_index, &first, None, None)],
+            &[],
+        )
+        .await;
+    assert_error(result, ErrorCode::StaleProposal);
+}
+
+#[tokio::test]
+async fn config_transaction_execute_makes_pending_proposals_stale() {
+    let mut ctx = TestContext::with_program_config().await;
+    let payer = ctx.payer();
+    let multisig = autonomous_multisig(&mut ctx).await;
+    let vault = pda::vault(&multisig, 0).0;
+    let message = transfer_message(vault, payer, 1);
+
+    // An active and a draft proposal, created under the current membership.
+    ctx.send(
+        &[
+            instructions::vault_transaction_create(multisig, payer, payer, 1, 0, message.clone()),
+            instructions::proposal_create(multisig, payer, payer, 1, false),
+            instructions::vault_transaction_create(multisig, payer, payer, 2, 0, message.clone()),
+            instructions::proposal_create(multisig, payer, payer, 2, true),
+        ],
+        &[],
+    )
+    .await
+    .unwrap();
+
+    execute(
+        &mut ctx,
+        multisig,
+        vec![ConfigAction::AddMember {
+            new_member: member(Pubkey::new_unique()),
+        }],
+    )
+    .await
+    .unwrap();
+    assert_eq!(ctx.account::<Multisig>(multisig).await.stale_transaction_index, 3);
+
+    let result = ctx
+        .send(&[instructions::proposal_approve(multisig, payer, 1, None)], &[])
+        .await;
+    assert_error(result, ErrorCode::StaleProposal);
+    let result = ctx
+        .send(&[instructions::proposal_activate(multisig, payer, 2)], &[])
+        .await;
+    assert_error(result, ErrorCode::StaleProposal);
+
+    // Transactions created under the new membership are open for voting.
+    ctx.send(
+        &[
+            instructions::vault_transaction_create(multisig, payer, payer, 4, 0, message),
+            instructions::proposal_create(multisig, payer, payer, 4, false),
+            instructions::proposal_approve(multisig, payer, 4, None),
+        ],
+        &[],
+    )
+    .await
+    .unwrap();
+}
+
+#[tokio::test]
+async fn config_transaction_execute_requires_execute_permission() {
+    let mut ctx = TestContext::with_program_config().await;
+    let payer = ctx.payer();
+    let voter = Keypair::new();
+    let multisig = ctx
+        .create_multisig(
+            Pubkey::default(),
+            1,
+            vec![member(payer), member_with(voter.pubkey(), &[Permission::Vote], 1)],
+        )
+        .await;
+    let actions = vec![ConfigAction::SetTimeLock { new_time_lock: 60 }];
+    let index = ctx
+        .create_approved(multisig, |index| {
+            instructions::config_transaction_create(multisig, payer, payer, index, actions.clone())
+        })
+        .await;
+
+    let result = ctx
+        .send(
+            &[instructions::config_transaction_execute(
+                multisig,
+                voter.pubkey(),
+                index,
+                &actions,
+                None,
+                None,
+            )],
+            &[&voter],
+        )
+        .await;
+
+    assert_error(result, ErrorCode::Unauthorized);
+}
+
+#[tokio::test]
+async fn config_transaction_accounts_close() {
+    let mut ctx = TestContext::with_program_config().await;
+    let payer = ctx.payer();
+    let multisig = autonomous_multisig(&mut ctx).await;
+    let rent_collector = Pubkey::new_unique();
+
+    execute(
+        &mut ctx,
+        multisig,
+        vec![ConfigAction::SetRentCollector {
+            new_rent_collector: Some(rent_collector),
+        }],
+    )
+    .await
+    .unwrap();
+
+    let result = ctx
+        .send(&[instructions::config_transaction_accounts_close(multisig, payer, 1, payer)], &[])
+        .await;
+    assert_error(result, ErrorCode::InvalidRentCollector);
+
+    ctx.send(
+        &[instructions::config_transaction_accounts_close(multisig, payer, 1, rent_collector)],
+        &[],
+    )
+    .await
+    .unwrap();
+
+    assert!(!ctx.exists(pda::transaction(&multisig, 1).0).await);
+    assert!(!ctx.exists(pda::proposal(&multisig, 1).0).await);
+    assert!(ctx.lamports(rent_collector).await > 0);
+}
+
+#[tokio::test]
+async fn config_transaction_accounts_close_rejected_cancelled_and_stale_proposals() {
+    let mut ctx = TestContext::with_program_config().await;
+    let payer = ctx.payer();
+    let multisig = autonomous_multisig(&mut ctx).await;
+    let rent_collector = Pubkey::new_unique();
+    execute(
+        &mut ctx,
+        multisig,
+        vec![ConfigAction::SetRentCollector {
+            new_rent_collector: Some(rent_collector),
+        }],
+    )
+    .await
+    .unwrap();
+    let actions = vec![ConfigAction::SetTimeLock { new_time_lock: 60 }];
+
+    // Transaction 2 is rejected, 3 is approved and then cancelled, 4 stays active.
+    ctx.send(
+        &[
+            instructions::config_transaction_create(multisig, payer, payer, 2, actions.clone()),
+            instructions::proposal_create(multisig, payer, payer, 2, false),
+            instructions::proposal_reject(multisig, payer, 2, None),
+            instructions::config_transaction_create(multisig, payer, payer, 3, actions.clone()),
+            instructions::proposal_create(multisig, payer, payer, 3, false),
+            instructions::proposal_approve(multisig, payer, 3, None),
+            instructions::proposal_cancel(multisig, payer, 3, None),
+            instructions::config_transaction_create(multisig, payer, payer, 4, actions.clone()),
+            instructions::proposal_create(multisig, payer, payer, 4, false),
+        ],
+        &[],
+    )
+    .await
+    .unwrap();
+
+    for index in [2, 3] {
+        let transaction = pda::transaction(&multisig, index).0;
+        let proposal = pda::proposal(&multisig, index).0;
+        let rent = ctx.lamports(transaction).await + ctx.lamports(proposal).await;
+        let collected = ctx.lamports(rent_collector).await;
+
+        ctx.send(
+            &[instructions::config_transaction_accounts_close(
+                multisig,
+                payer,
+                index,
+                rent_collector,
+            )],
+            &[],
+        )
+        .await
+        .unwrap();
+
+        assert!(!ctx.exists(transaction).await);
+        assert!(!ctx.exists(proposal).await);
+        assert_eq!(ctx.lamports(rent_collector).await, collected + rent);
+    }
+
+    let result = ctx
+        .send(
+            &[instructions::config_transaction_accounts_close(multisig, payer, 4, rent_collector)],
+            &[],
+        )
+        .await;
+    assert_error(result, ErrorCode::InvalidProposalStatus);
+
+    // An executed config change leaves the active proposal stale.
+    execute(&mut ctx, multisig, actions).await.unwrap();
+    ctx.send(
+        &[instructions::config_transaction_accounts_close(multisig, payer, 4, rent_collector)],
+        &[],
+    )
+    .await
+    .unwrap();
+    assert!(!ctx.exists(pda::transaction(&multisig, 4).0).await);
+    assert!(!ctx.exists(pda::proposal(&multisig, 4).0).await);
+}
+
+#[tokio::test]
+async fn config_transaction_accounts_close_without_a_proposal() {
+    let mut ctx = TestContext::with_program_config().await;
+    let payer = ctx.payer();
+    let multisig = autonomous_multisig(&mut ctx).await;
+
+    // A config transaction that never gets a proposal.
+    let actions = vec![ConfigAction::SetTimeLock { new_time_lock: 60 }];
+    ctx.send(
+        &[instructions::config_transaction_create(multisig, payer, payer, 1, actions)],
+        &[],
+    )
+    .await
+    .unwrap();
+
+    execute(
+        &mut ctx,
+        multisig,
+        vec![ConfigAction::SetRentCollector {
+            new_rent_collector: Some(payer),
+        }],
+    )
+    .await
+    .unwrap();
+
+    // Only stale transactions may be closed without their proposal.
+    let result = ctx
+        .send(&[instructions::config_transaction_accounts_close(multisig, payer, 1, payer)], &[])
+        .await;
+    assert_error(result, ErrorCode::InvalidProposalStatus);
+
+    execute(&mut ctx, multisig, vec![ConfigAction::SetTimeLock { new_time_lock: 0 }])
+        .await
+        .unwrap();
+    assert!(ctx.account::<Multisig>(multisig).await.stale_transaction_index >= 1);
+
+    ctx.send(
+        &[instructions::config_transaction_accounts_close(multisig, payer, 1, payer)],
+        &[],
+    )
+    .await
+    .unwrap();
+    assert!(!ctx.exists(pda::transaction(&multisig, 1).0).await);
+}
+
+#[tokio::test]
+async fn config_transaction_accounts_close_closes_the_proposal_of_a_stale_transaction() {
+    let mut ctx = TestContext::with_program_config().await;
+    let payer = ctx.payer();
+    let multisig = autonomous_multisig(&mut ctx).await;
+    let rent_collector = Pubkey::new_unique();
+    execute(
+        &mut ctx,
+        multisig,
+        vec![ConfigAction::SetRentCollector {
+            new_rent_collector: Some(rent_collector),
+        }],
+    )
+    .await
+    .unwrap();
+
+    // An active proposal, made stale by the next executed config change.
+    let actions = vec![ConfigAction::SetTimeLock { new_time_lock: 60 }];
+    ctx.send(
+        &[
+            instructions::config_transaction_create(multisig, payer, payer, 2, actions.clone()),
+            instructions::proposal_create(multisig, payer, payer, 2, false),
+        ],
+        &[],
+    )
+    .await
+    .unwrap();
+    execute(&mut ctx, multisig, actions).await.unwrap();
+    let transaction = pda::transaction(&multisig, 2).0;
+    let proposal = pda::proposal(&multisig, 2).0;
+
+    // Leaving the proposal out would strand its rent.
+    let mut ix =
+        instructions::config_transaction_accounts_close(multisig, payer, 2, rent_collector);
+    ix.accounts[1].pubkey = collectivex_multisig::ID;
+    let result = ctx.send(&[ix], &[]).await;
+    assert_anchor_error(result, AnchorErrorCode::ConstraintSeeds);
+
+    let rent = ctx.lamports(transaction).await + ctx.lamports(proposal).await;
+    ctx.send(
+        &[instructions::config_transaction_accounts_close(multisig, payer, 2, rent_collector)],
+        &[],
+    )
+    .await
+    .unwrap();
+    assert!(!ctx.exists(transaction).await);
+    assert!(!ctx.exists(proposal).await);
+    assert_eq!(ctx.lamports(rent_collector).await, rent);
+}
+
+#[tokio::test]
+async fn config_transaction_accounts_close_requires_rent_collector() {
+    let mut ctx = TestContext::with_program_config().await;
+    let payer = ctx.payer();
+    let multisig = autonomous_multisig(&mut ctx).await;
+    execute(
+        &mut ctx,
+        multisig,
+        vec![ConfigAction::SetTimeLock { new_time_lock: 0 }],
+    )
+    .await
+    .unwrap();
+
+    let result = ctx
+        .send(&[instructions::config_transaction_accounts_close(multisig, payer, 1, payer)], &[])
+        .await;
+
+    assert_error(result, ErrorCode::RentReclamationDisabled);
+}
+
+#[tokio::test]
+async fn config_transaction_accounts_close_requires_a_final_status() {
+    let mut ctx = TestContext::with_program_config().await;
+    let payer = ctx.payer();
+    let multisig = autonomous_multisig(&mut ctx).await;
+    execute(
+        &mut ctx,
+        multisig,
+        vec![ConfigAction::SetRentCollector {
+            new_rent_collector: Some(payer),
+        }],
+    )
+    .await
+    .unwrap();
+
+    let actions = vec![ConfigAction::SetTimeLock { new_time_lock: 60 }];
+    let index = ctx
+        .create_approved(multisig, |index| {
+            instructions::config_transaction_create(multisig, payer, payer, index, actions)
+        })
+        .await;
+
+    let result = ctx
+        .send(
+            &[instructions::config_transaction_accounts_close(multisig, payer, index, payer)],
+            &[],
+        )
+        .await;
+
+    assert_error(result, ErrorCode::InvalidProposalStatus);
+}
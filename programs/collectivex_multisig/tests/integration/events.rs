@@ -0,0 +1,381 @@
+mod common;
+
+use collectivex_multisig::constants::MAX_MEMO_LEN;
+use collectivex_multisig::events::{
+    ConfigTransactionClosed, ConfigTransactionCreated, MemberAdded, MemberRemoved,
+    MultisigCreated, ProposalActivated, ProposalApproved, ProposalCancelled, ProposalCreated,
+    ProposalExecuted, ProposalExpired, ProposalRejected, SpendingLimitAdded, SpendingLimitRemoved,
+    ThresholdChanged,
+};
+use collectivex_multisig::state::{ConfigAction, Period, ProposalStatus};
+use collectivex_multisig_sdk::{instructions, pda};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+
+use crate::common::{events, member, transfer_message, TestContext};
+
+#[tokio::test]
+async fn multisig_create_emits_an_event() {
+    let mut ctx = TestContext::with_program_config().await;
+    let payer = ctx.payer();
+    let create_key = Keypair::new();
+    let other = Pubkey::new_unique();
+
+    let logs = ctx
+        .send_with_logs(
+            &[instructions::multisig_create(
+                payer,
+                create_key.pubkey(),
+                payer,
+                false,
+                payer,
+                2,
+                vec![member(payer), member(other)],
+                60,
+            )],
+            &[&create_key],
+        )
+        .await
+        .unwrap();
+
+    let [event] = &events::<MultisigCreated>(&logs)[..] else {
+        panic!("expected one MultisigCreated event");
+    };
+    assert_eq!(event.actor, payer);
+    assert_eq!(event.multisig, pda::multisig(&create_key.pubkey()).0);
+    assert_eq!(event.create_key, create_key.pubkey());
+    assert_eq!(event.config_authority, payer);
+    assert_eq!(event.threshold, 2);
+    assert_eq!(event.members, vec![payer, other]);
+    assert_eq!(event.time_lock, 60);
+}
+
+#[tokio::test]
+async fn config_transactions_emit_an_event_per_change() {
+    let mut ctx = TestContext::with_program_config().await;
+    let payer = ctx.payer();
+    let multisig = ctx.create_multisig(Pubkey::default(), 1, vec![member(payer)]).await;
+    let new_member = Pubkey::new_unique();
+    let actions = vec![
+        ConfigAction::AddMember {
+            new_member: member(new_member),
+        },
+        ConfigAction::ChangeThreshold { new_threshold: 2 },
+    ];
+
+    let logs = ctx
+        .send_with_logs(
+            &[
+                instructions::config_transaction_create(multisig, payer, payer, 1, actions.clone()),
+                instructions::proposal_create(multisig, payer, payer, 1, false),
+                instructions::proposal_approve(multisig, payer, 1, None),
+            ],
+            &[],
+        )
+        .await
+        .unwrap();
+
+    let [created] = &events::<ConfigTransactionCreated>(&logs)[..] else {
+        panic!("expected one ConfigTransactionCreated event");
+    };
+    assert_eq!((created.actor, created.multisig, created.transaction_index), (payer, multisig, 1));
+    let [proposal] = &events::<ProposalCreated>(&logs)[..] else {
+        panic!("expected one ProposalCreated event");
+    };
+    assert_eq!(proposal.transaction_index, 1);
+    assert!(matches!(proposal.status, ProposalStatus::Active { .. }));
+    let [approved] = &events::<ProposalApproved>(&logs)[..] else {
+        panic!("expected one ProposalApproved event");
+    };
+    assert_eq!(approved.actor, payer);
+    assert!(matches!(approved.status, ProposalStatus::Approved { .. }));
+
+    let logs = ctx
+        .send_with_logs(
+            &[instructions::config_transaction_execute(
+                multisig,
+                payer,
+                1,
+                &actions,
+                Some(payer),
+                None,
+            )],
+            &[],
+        )
+        .await
+        .unwrap();
+
+    let [added] = &events::<MemberAdded>(&logs)[..] else {
+        panic!("expected one MemberAdded event");
+    };
+    assert_eq!((added.actor, added.multisig, added.member), (payer, multisig, new_member));
+    assert_eq!(added.weight, 1);
+    let [threshold] = &events::<ThresholdChanged>(&logs)[..] else {
+        panic!("expected one ThresholdChanged event");
+    };
+    assert_eq!(threshold.threshold, 2);
+    let [executed] = &events::<ProposalExecuted>(&logs)[..] else {
+        panic!("expected one ProposalExecuted event");
+    };
+    assert_eq!((executed.actor, executed.multisig, executed.transaction_index), (payer, multisig, 1));
+}
+
+#[tokio::test]
+async fn proposal_votes_emit_events() {
+    let mut ctx = TestContext::with_program_config().await;
+    let payer = ctx.payer();
+    let multisig = ctx.create_multisig(payer, 1, vec![member(payer)]).await;
+    ctx.send(&[instructions::multisig_set_voting_period(multisig, payer, Some(3600))], &[])
+        .await
+        .unwrap();
+    let vault = pda::vault(&multisig, 0).0;
+    ctx.send(
+        &[
+            instructions::vault_transaction_create(multisig, payer, payer, 1, 0, transfer_message(vault, payer, 1)),
+            instructions::proposal_create(multisig, payer, payer, 1, true),
+            instructions::vault_transaction_create(multisig, payer, payer, 2, 0, transfer_message(vault, payer, 1)),
+            instructions::proposal_create(multisig, payer, payer, 2, false),
+        ],
+        &[],
+    )
+    .await
+    .unwrap();
+
+    let logs = ctx
+        .send_with_logs(
+            &[
+                instructions::proposal_activate(multisig, payer, 1),
+                instructions::proposal_approve(multisig, payer, 1, None),
+                instructions::proposal_cancel(multisig, payer, 1, None),
+                instructions::proposal_reject(multisig, payer, 2, None),
+            ],
+            &[],
+        )
+        .await
+        .unwrap();
+    let now = ctx.now().await;
+
+    let [activated] = &events::<ProposalActivated>(&logs)[..] else {
+        panic!("expected one ProposalActivated event");
+    };
+    assert_eq!((activated.actor, activated.multisig, activated.transaction_index), (payer, multisig, 1));
+    assert_eq!(activated.expires_at, Some(now + 3600));
+    let [cancelled] = &events::<ProposalCancelled>(&logs)[..] else {
+        panic!("expected one ProposalCancelled event");
+    };
+    assert_eq!((cancelled.actor, cancelled.transaction_index), (payer, 1));
+    assert_eq!(cancelled.status, ProposalStatus::Cancelled { timestamp: now });
+    let [rejected] = &events::<ProposalRejected>(&logs)[..] else {
+        panic!("expected one ProposalRejected event");
+    };
+    assert_eq!((rejected.actor, rejected.transaction_index), (payer, 2));
+    assert_eq!(rejected.status, ProposalStatus::Rejected { timestamp: now });
+}
+
+#[tokio::test]
+async fn multisig_edits_emit_events() {
+    let mut ctx = TestContext::with_program_config().await;
+    let payer = ctx.payer();
+    let other = Pubkey::new_unique();
+    let multisig = ctx.create_multisig(payer, 1, vec![member(payer), member(other)]).await;
+    let create_key = Keypair::new();
+    let spending_limit = pda::spending_limit(&multisig, &create_key.pubkey()).0;
+
+    let logs = ctx
+        .send_with_logs(
+            &[
+                instructions::multisig_remove_member(multisig, payer, None, other),
+                instructions::multisig_add_spending_limit(
+                    multisig,
+                    payer,
+                    create_key.pubkey(),
+                    1,
+                    Pubkey::default(),
+                    1_000,
+                    Period::Week,
+                    vec![payer],
+                    vec![],
+                ),
+            ],
+            &[&create_key],
+        )
+        .await
+        .unwrap();
+
+    let [removed] = &events::<MemberRemoved>(&logs)[..] else {
+        panic!("expected one MemberRemoved event");
+    };
+    assert_eq!((removed.actor, removed.multisig, removed.member), (payer, multisig, other));
+    let [added] = &events::<SpendingLimitAdded>(&logs)[..] else {
+        panic!("expected one SpendingLimitAdded event");
+    };
+    assert_eq!((added.actor, added.multisig, added.spending_limit), (payer, multisig, spending_limit));
+    assert_eq!((added.vault_index, added.mint, added.amount), (1, Pubkey::default(), 1_000));
+    assert_eq!(added.period, Period::Week);
+
+    let logs = ctx
+        .send_with_logs(
+            &[instructions::multisig_remove_spending_limit(multisig, payer, spending_limit, payer, None)],
+            &[],
+        )
+        .await
+        .unwrap();
+
+    let [removed] = &events::<SpendingLimitRemoved>(&logs)[..] else {
+        panic!("expected one SpendingLimitRemoved event");
+    };
+    assert_eq!((removed.actor, removed.multisig, removed.spending_limit), (payer, multisig, spending_limit));
+}
+
+#[tokio::test]
+async fn memos_are_emitted_with_the_actor_and_proposal() {
+    let mut ctx = TestContext::with_program_config().await;
+    let payer = ctx.payer();
+    let multisig = ctx.create_multisig(payer, 1, vec![member(payer)]).await;
+    let vault = pda::vault(&multisig, 0).0;
+    let longest = "m".repeat(MAX_MEMO_LEN);
+    ctx.send(
+        &[
+            instructions::vault_transaction_create(multisig, payer, payer, 1, 0, transfer_message(vault, payer, 1)),
+            instructions::proposal_create(multisig, payer, payer, 1, false),
+            instructions::vault_transaction_create(multisig, payer, payer, 2, 0, transfer_message(vault, payer, 1)),
+            instructions::proposal_create(multisig, payer, payer, 2, false),
+        ],
+        &[],
+    )
+    .await
+    .unwrap();
+
+    let logs = ctx
+        .send_with_logs(
+            &[
+                instructions::proposal_approve(multisig, payer, 1, Some(longest.clone())),
+                instructions::proposal_cancel(multisig, payer, 1, Some("paid elsewhere".to_string())),
+                instructions::proposal_reject(multisig, payer, 2, Some("wrong destination".to_string())),
+            ],
+            &[],
+        )
+        .await
+        .unwrap();
+
+    let [approved] = &events::<ProposalApproved>(&logs)[..] else {
+        panic!("expected one ProposalApproved event");
+    };
+    assert_eq!((approved.actor, approved.multisig, approved.transaction_index), (payer, multisig, 1));
+    assert_eq!(approved.memo.as_ref(), Some(&longest));
+    let [cancelled] = &events::<ProposalCancelled>(&logs)[..] else {
+        panic!("expected one ProposalCancelled event");
+    };
+    assert_eq!((cancelled.actor, cancelled.transaction_index), (payer, 1));
+    assert_eq!(cancelled.memo.as_deref(), Some("paid elsewhere"));
+    let [rejected] = &events::<ProposalRejected>(&logs)[..] else {
+        panic!("expected one ProposalRejected event");
+    };
+    assert_eq!((rejected.actor, rejected.transaction_index), (payer, 2));
+    assert_eq!(rejected.memo.as_deref(), Some("wrong destination"));
+
+    let create_key = Keypair::new();
+    let spending_limit = pda::spending_limit(&multisig, &create_key.pubkey()).0;
+    ctx.send(
+        &[instructions::multisig_add_spending_limit(
+            multisig,
+            payer,
+            create_key.pubkey(),
+            0,
+            Pubkey::default(),
+            1_000,
+            Period::Day,
+            vec![payer],
+            vec![],
+        )],
+        &[&create_key],
+    )
+    .await
+    .unwrap();
+    let logs = ctx
+        .send_with_logs(
+            &[instructions::multisig_remove_spending_limit(
+                multisig,
+                payer,
+                spending_limit,
+                payer,
+                Some("no longer needed".to_string()),
+            )],
+            &[],
+        )
+        .await
+        .unwrap();
+
+    let [removed] = &events::<SpendingLimitRemoved>(&logs)[..] else {
+        panic!("expected one SpendingLimitRemoved event");
+    };
+    assert_eq!(removed.spending_limit, spending_limit);
+    assert_eq!(removed.memo.as_deref(), Some("no longer needed"));
+}
+
+#[tokio::test]
+async fn proposal_expire_emits_an_event_with_the_caller() {
+    let mut ctx = TestContext::with_program_config().await;
+    let payer = ctx.payer();
+    let caller = Keypair::new();
+    let multisig = ctx.create_multisig(payer, 1, vec![member(payer)]).await;
+    let vault = pda::vault(&multisig, 0).0;
+    ctx.send(
+        &[
+            instructions::multisig_set_voting_period(multisig, payer, Some(3600)),
+            instructions::vault_transaction_create(multisig, payer, payer, 1, 0, transfer_message(vault, payer, 1)),
+            instructions::proposal_create(multisig, payer, payer, 1, false),
+        ],
+        &[],
+    )
+    .await
+    .unwrap();
+    ctx.warp(3600).await;
+
+    let logs = ctx
+        .send_with_logs(&[instructions::proposal_expire(multisig, caller.pubkey(), 1)], &[&caller])
+        .await
+        .unwrap();
+
+    let [expired] = &events::<ProposalExpired>(&logs)[..] else {
+        panic!("expected one ProposalExpired event");
+    };
+    assert_eq!((expired.actor, expired.multisig, expired.transaction_index), (caller.pubkey(), multisig, 1));
+}
+
+#[tokio::test]
+async fn config_transaction_accounts_close_emits_an_event_with_the_caller() {
+    let mut ctx = TestContext::with_program_config().await;
+    let payer = ctx.payer();
+    let caller = Keypair::new();
+    let rent_collector = Pubkey::new_unique();
+    let multisig = ctx.create_multisig(Pubkey::default(), 1, vec![member(payer)]).await;
+    let actions = vec![ConfigAction::SetRentCollector {
+        new_rent_collector: Some(rent_collector),
+    }];
+    ctx.send(
+        &[
+            instructions::config_transaction_create(multisig, payer, payer, 1, actions.clone()),
+            instructions::proposal_create(multisig, payer, payer, 1, false),
+            instructions::proposal_approve(multisig, payer, 1, None),
+            instructions::config_transaction_execute(multisig, payer, 1, &actions, None, Some(rent_collector)),
+        ],
+        &[],
+    )
+    .await
+    .unwrap();
+
+    let logs = ctx
+        .send_with_logs(
+            &[instructions::config_transaction_accounts_close(multisig, caller.pubkey(), 1, rent_collector)],
+            &[&caller],
+        )
+        .await
+        .unwrap();
+
+    let [closed] = &events::<ConfigTransactionClosed>(&logs)[..] else {
+        panic!("expected one ConfigTransactionClosed event");
+    };
+    assert_eq!((closed.actor, closed.multisig, closed.transaction_index), (caller.pubkey(), multisig, 1));
+    assert_eq!(closed.rent_collector, rent_collector);
+}
@@ -0,0 +1,241 @@
+mod common;
+
+use anchor_lang::error::ErrorCode as AnchorErrorCode;
+use collectivex_multisig::error::ErrorCode;
+use collectivex_multisig::state::FeeWaiver;
+use collectivex_multisig_sdk::{instructions, pda};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+
+use crate::common::{assert_anchor_error, assert_error, member, TestContext, TestResult};
+
+const FEE: u64 = 1_000_000_000;
+
+/// A runtime with a program config charging `FEE` lamports into a new treasury.
+async fn with_creation_fee() -> (TestContext, Pubkey) {
+    let mut ctx = TestContext::new().await;
+    let payer = ctx.payer();
+    let treasury = Pubkey::new_unique();
+    ctx.send(&[instructions::program_config_init(payer, payer, FEE, treasury)], &[])
+        .await
+        .unwrap();
+
+    (ctx, treasury)
+}
+
+async fn create_waiver(
+    ctx: &mut TestContext,
+    discount_percent: u8,
+    expires_at: Option<i64>,
+    remaining_uses: Option<u32>,
+) -> TestResult {
+    let payer = ctx.payer();
+
+    ctx.send(
+        &[instructions::fee_waiver_create(
+            payer,
+            payer,
+            payer,
+            discount_percent,
+            expires_at,
+            remaining_uses,
+        )],
+        &[],
+    )
+    .await
+}
+
+/// Creates a multisig of the payer, applying its fee waiver.
+async fn create_multisig(ctx: &mut TestContext, treasury: Pubkey) -> TestResult {
+    let payer = ctx.payer();
+    let create_key = Keypair::new();
+
+    ctx.send(
+        &[instructions::multisig_create(
+            payer,
+            create_key.pubkey(),
+            treasury,
+            true,
+            payer,
+            1,
+            vec![member(payer)],
+            0,
+        )],
+        &[&create_key],
+    )
+    .await
+}
+
+#[tokio::test]
+async fn fee_waiver_create_and_remove() {
+    let (mut ctx, _) = with_creation_fee().await;
+    let payer = ctx.payer();
+    let fee_waiver = pda::fee_waiver(&payer).0;
+    let expires_at = ctx.now().await + 3_600;
+
+    create_waiver(&mut ctx, 50, Some(expires_at), Some(3)).await.unwrap();
+
+    let waiver: FeeWaiver = ctx.account(fee_waiver).await;
+    assert_eq!(waiver.version, FeeWaiver::VERSION);
+    assert_eq!(waiver.creator, payer);
+    assert_eq!(waiver.discount_percent, 50);
+    assert_eq!(waiver.expires_at, Some(expires_at));
+    assert_eq!(waiver.remaining_uses, Some(3));
+
+    let rent_collector = Pubkey::new_unique();
+    let rent = ctx.lamports(fee_waiver).await;
+    ctx.send(&[instructions::fee_waiver_remove(payer, payer, rent_collector)], &[])
+        .await
+        .unwrap();
+
+    assert!(!ctx.exists(fee_waiver).await);
+    assert_eq!(ctx.lamports(rent_collector).await, rent);
+}
+
+#[tokio::test]
+async fn fee_waiver_create_validates_the_waiver() {
+    let (mut ctx, _) = with_creation_fee().await;
+    let now = ctx.now().await;
+
+    let result = create_waiver(&mut ctx, 0, None, None).await;
+    assert_error(result, ErrorCode::InvalidDiscount);
+
+    let result = create_waiver(&mut ctx, 101, None, None).await;
+    assert_error(result, ErrorCode::InvalidDiscount);
+
+    let result = create_waiver(&mut ctx, 100, Some(now), None).await;
+    assert_error(result, ErrorCode::FeeWaiverExpired);
+
+    let result = create_waiver(&mut ctx, 100, None, Some(0)).await;
+    assert_error(result, ErrorCode::FeeWaiverUsedUp);
+}
+
+#[tokio::test]
+async fn fee_waiver_requires_the_program_config_authority() {
+    let (mut ctx, _) = with_creation_fee().await;
+    let payer = ctx.payer();
+    let other = Keypair::new();
+
+    let result = ctx
+        .send(
+            &[instructions::fee_waiver_create(
+                other.pubkey(),
+                payer,
+                other.pubkey(),
+                100,
+                None,
+                None,
+            )],
+            &[&other],
+        )
+        .await;
+    assert_error(result, ErrorCode::InvalidAuthority);
+
+    create_waiver(&mut ctx, 100, None, None).await.unwrap();
+
+    let result = ctx
+        .send(
+            &[instructions::fee_waiver_remove(other.pubkey(), payer, other.pubkey())],
+            &[&other],
+        )
+        .await;
+    assert_error(result, ErrorCode::InvalidAuthority);
+}
+
+#[tokio::test]
+async fn multisig_create_applies_a_full_waiver() {
+    let (mut ctx, treasury) = with_creation_fee().await;
+
+    create_waiver(&mut ctx, 100, None, None).await.unwrap();
+    create_multisig(&mut ctx, treasury).await.unwrap();
+
+    assert_eq!(ctx.lamports(treasury).await, 0);
+}
+
+#[tokio::test]
+async fn multisig_create_applies_a_discount() {
+    let (mut ctx, treasury) = with_creation_fee().await;
+
+    create_waiver(&mut ctx, 25, None, None).await.unwrap();
+    create_multisig(&mut ctx, treasury).await.unwrap();
+
+    assert_eq!(ctx.lamports(treasury).await, FEE / 4 * 3);
+}
+
+#[tokio::test]
+async fn multisig_create_counts_the_waiver_uses() {
+    let (mut ctx, treasury) = with_creation_fee().await;
+    let payer = ctx.payer();
+
+    create_waiver(&mut ctx, 100, None, Some(1)).await.unwrap();
+    create_multisig(&mut ctx, treasury).await.unwrap();
+
+    let waiver: FeeWaiver = ctx.account(pda::fee_waiver(&payer).0).await;
+    assert_eq!(waiver.remaining_uses, Some(0));
+
+    let result = create_multisig(&mut ctx, treasury).await;
+    assert_error(result, ErrorCode::FeeWaiverUsedUp);
+    assert_eq!(ctx.lamports(treasury).await, 0);
+}
+
+#[tokio::test]
+async fn multisig_create_keeps_the_waiver_uses_without_a_fee() {
+    let (mut ctx, treasury) = with_creation_fee().await;
+    let payer = ctx.payer();
+    ctx.send(&[instructions::program_config_set_creation_fee(payer, 0)], &[])
+        .await
+        .unwrap();
+
+    create_waiver(&mut ctx, 100, None, Some(1)).await.unwrap();
+    create_multisig(&mut ctx, treasury).await.unwrap();
+
+    // Nothing was discounted, so the use is kept for when fees are back on.
+    let waiver: FeeWaiver = ctx.account(pda::fee_waiver(&payer).0).await;
+    assert_eq!(waiver.remaining_uses, Some(1));
+}
+
+#[tokio::test]
+async fn multisig_create_rejects_an_expired_waiver() {
+    let (mut ctx, treasury) = with_creation_fee().await;
+    let expires_at = ctx.now().await + 60;
+
+    create_waiver(&mut ctx, 100, Some(expires_at), None).await.unwrap();
+    ctx.warp(60).await;
+
+    let result = create_multisig(&mut ctx, treasury).await;
+    assert_error(result, ErrorCode::FeeWaiverExpired);
+}
+
+#[tokio::test]
+async fn multisig_create_rejects_the_waiver_of_another_creator() {
+    let (mut ctx, treasury) = with_creation_fee().await;
+    let payer = ctx.payer();
+    let partner = Pubkey::new_unique();
+
+    ctx.send(
+        &[instructions::fee_waiver_create(payer, payer, partner, 100, None, None)],
+        &[],
+    )
+    .await
+    .unwrap();
+
+    let create_key = Keypair::new();
+    let mut ix = instructions::multisig_create(
+        payer,
+        create_key.pubkey(),
+        treasury,
+        true,
+        payer,
+        1,
+        vec![member(payer)],
+        0,
+    );
+    for meta in &mut ix.accounts {
+        if meta.pubkey == pda::fee_waiver(&payer).0 {
+            meta.pubkey = pda::fee_waiver(&partner).0;
+        }
+    }
+
+    let result = ctx.send(&[ix], &[&create_key]).await;
+    assert_anchor_error(result, AnchorErrorCode::ConstraintSeeds);
+}
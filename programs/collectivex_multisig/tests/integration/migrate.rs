@@ -0,0 +1,285 @@
+//! Upgrades of accounts written by the program before accounts were versioned.
+//!
+//! The fixtures in `tests/fixtures` hold accounts serialized by that program's account types,
+//! allocated with the space it gave them. The multisig has the zeroed `create_key` the original
+//! program left. Its proposals and spending limits are derived from that key, so the proposal and
+//! spending limit fixtures are as written once the program stored it, shortly before versioning.
+
+mod common;
+
+use anchor_lang::error::ErrorCode as AnchorErrorCode;
+use collectivex_multisig::error::ErrorCode;
+use collectivex_multisig::state::{
+    Multisig, PausedCategories, Period, Permission, ProgramConfig, Proposal, ProposalStatus,
+    SpendingLimit,
+};
+use collectivex_multisig_sdk::{instructions, pda};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+use solana_sdk::signer::keypair::keypair_from_seed;
+
+use crate::common::{assert_anchor_error, assert_error, member, member_with, TestContext};
+
+const PROGRAM_CONFIG: &[u8] = include_bytes!("../fixtures/program_config_v0.bin");
+const MULTISIG: &[u8] = include_bytes!("../fixtures/multisig_v0.bin");
+const PROPOSAL: &[u8] = include_bytes!("../fixtures/proposal_v0.bin");
+const SPENDING_LIMIT: &[u8] = include_bytes!("../fixtures/spending_limit_v0.bin");
+
+/// The keys the fixtures were created with.
+fn key(seed: u8) -> Keypair {
+    keypair_from_seed(&[seed; 32]).unwrap()
+}
+
+fn multisig() -> Pubkey {
+    pda::multisig(&key(3).pubkey()).0
+}
+
+fn spending_limit() -> Pubkey {
+    pda::spending_limit(&multisig(), &key(9).pubkey()).0
+}
+
+/// A runtime holding every fixture, with `multisig` as the legacy multisig.
+async fn legacy_context_with(multisig_data: Vec<u8>) -> TestContext {
+    TestContext::with_accounts(&[
+        (pda::program_config().0, PROGRAM_CONFIG.to_vec()),
+        (multisig(), multisig_data),
+        (pda::proposal(&multisig(), 1).0, PROPOSAL.to_vec()),
+        (spending_limit(), SPENDING_LIMIT.to_vec()),
+    ])
+    .await
+}
+
+async fn legacy_context() -> TestContext {
+    legacy_context_with(MULTISIG.to_vec()).await
+}
+
+#[tokio::test]
+async fn migrate_program_config() {
+    let mut ctx = legacy_context().await;
+    let payer = ctx.payer();
+    let authority = key(1);
+    ctx.fund(authority.pubkey(), 1_000_000_000).await;
+
+    // Legacy accounts are rejected until they are migrated.
+    let result = ctx
+        .send(
+            &[instructions::program_config_set_creation_fee(authority.pubkey(), 0)],
+            &[&authority],
+        )
+        .await;
+    assert_anchor_error(result, AnchorErrorCode::AccountDidNotDeserialize);
+
+    ctx.send(&[instructions::migrate_program_config(payer)], &[])
+        .await
+        .unwrap();
+
+    let config: ProgramConfig = ctx.account(pda::program_config().0).await;
+    assert_eq!(config.version, ProgramConfig::VERSION);
+    assert_eq!(config.authority, authority.pubkey());
+    assert_eq!(config.creation_fee, 1_000_000_000);
+    assert_eq!(config.treasury, Pubkey::new_from_array([2; 32]));
+    assert_eq!(config.fee_mint, None);
+    assert_eq!(config.pending_authority, None);
+    assert_eq!(config.paused, PausedCategories::default());
+    assert_eq!(config.reserved, [0; 63]);
+
+    ctx.send(
+        &[instructions::program_config_set_creation_fee(authority.pubkey(), 0)],
+        &[&authority],
+    )
+    .await
+    .unwrap();
+
+    let result = ctx
+        .send(&[instructions::migrate_program_config(payer)], &[])
+        .await;
+    assert_error(result, ErrorCode::AlreadyMigrated);
+}
+
+#[tokio::test]
+async fn migrate_multisig() {
+    let mut ctx = legacy_context().await;
+    let payer = ctx.payer();
+    let config_authority = key(4);
+    let create_key = key(3).pubkey();
+
+    ctx.send(&[instructions::migrate_multisig(multisig(), create_key, payer)], &[])
+        .await
+        .unwrap();
+
+    let account: Multisig = ctx.account(multisig()).await;
+    assert_eq!(account.version, Multisig::VERSION);
+    // The original program never stored the create key.
+    assert_eq!(&MULTISIG[8..40], &[0; 32]);
+    assert_eq!(account.create_key, create_key);
+    assert_eq!(account.config_authority, config_authority.pubkey());
+    assert_eq!(account.threshold, 2);
+    // Legacy members could do everything, with one vote each.
+    assert_eq!(
+        account.members,
+        vec![
+            member(key(5).pubkey()),
+            member(key(6).pubkey()),
+            member(Pubkey::new_from_array([7; 32])),
+        ]
+    );
+    assert_eq!(account.voting_weight(), 3);
+    assert_eq!(account.time_lock, 60);
+    assert_eq!(account.transaction_index, 1);
+    assert_eq!(account.stale_transaction_index, 0);
+    assert_eq!(account.rent_collector, None);
+    assert_eq!(account.voting_period, None);
+    assert_eq!(account.pending_config_authority, None);
+    // Multisigs used to be allocated for 10 members, whatever their actual count.
+    assert_eq!(MULTISIG.len(), 8 + 32 + 32 + 2 + 4 + 10 * 32 + 4 + 8 + 8);
+    assert_eq!(ctx.data_len(multisig()).await, Multisig::size(3));
+    assert!(ctx.lamports(multisig()).await >= ctx.minimum_balance(Multisig::size(3)).await);
+
+    // The migrated multisig can be edited again.
+    ctx.send(
+        &[instructions::multisig_set_time_lock(multisig(), config_authority.pubkey(), 120)],
+        &[&config_authority],
+    )
+    .await
+    .unwrap();
+    assert_eq!(ctx.account::<Multisig>(multisig()).await.time_lock, 120);
+
+    let result = ctx
+        .send(&[instructions::migrate_multisig(multisig(), create_key, payer)], &[])
+        .await;
+    assert_error(result, ErrorCode::AlreadyMigrated);
+}
+
+#[tokio::test]
+async fn migrate_multisig_with_stored_create_key() {
+    let create_key = key(3).pubkey();
+    let mut data = MULTISIG.to_vec();
+    data[8..40].copy_from_slice(create_key.as_ref());
+    let mut ctx = legacy_context_with(data).await;
+    let payer = ctx.payer();
+
+    ctx.send(&[instructions::migrate_multisig(multisig(), create_key, payer)], &[])
+        .await
+        .unwrap();
+    assert_eq!(ctx.account::<Multisig>(multisig()).await.create_key, create_key);
+}
+
+#[tokio::test]
+async fn migrate_multisig_rejects_other_create_keys() {
+    let mut ctx = legacy_context().await;
+    let payer = ctx.payer();
+
+    // The create key must derive the multisig.
+    let result = ctx
+        .send(&[instructions::migrate_multisig(multisig(), key(9).pubkey(), payer)], &[])
+        .await;
+    assert_error(result, ErrorCode::NotMigratable);
+
+    // A stored create key must match the one passed in.
+    let mut data = MULTISIG.to_vec();
+    data[8..40].copy_from_slice(key(9).pubkey().as_ref());
+    let mut ctx = legacy_context_with(data).await;
+    let payer = ctx.payer();
+    let result = ctx
+        .send(&[instructions::migrate_multisig(multisig(), key(3).pubkey(), payer)], &[])
+        .await;
+    assert_error(result, ErrorCode::NotMigratable);
+}
+
+#[tokio::test]
+async fn migrate_spending_limit() {
+    let mut ctx = legacy_context().await;
+    let payer = ctx.payer();
+
+    ctx.send(&[instructions::migrate_spending_limit(spending_limit(), payer)], &[])
+        .await
+        .unwrap();
+
+    let account: SpendingLimit = ctx.account(spending_limit()).await;
+    assert_eq!(account.version, SpendingLimit::VERSION);
+    assert_eq!(account.multisig, multisig());
+    assert_eq!(account.create_key, key(9).pubkey());
+    assert_eq!(account.vault_index, 0);
+    assert_eq!(account.mint, Pubkey::default());
+    assert_eq!(account.amount, 5_000_000);
+    assert_eq!(account.remaining_amount, 3_000_000);
+    assert_eq!(account.last_reset, 1_700_000_000);
+    // Legacy limits were never refilled.
+    assert_eq!(account.period, Period::OneTime);
+    assert_eq!(account.members, vec![key(5).pubkey(), key(6).pubkey()]);
+    assert_eq!(account.destinations, vec![Pubkey::new_from_array([10; 32])]);
+    assert_eq!(ctx.data_len(spending_limit()).await, SpendingLimit::size(2, 1));
+
+    let result = ctx
+        .send(&[instructions::migrate_spending_limit(spending_limit(), payer)], &[])
+        .await;
+    assert_error(result, ErrorCode::AlreadyMigrated);
+}
+
+#[tokio::test]
+async fn migrate_proposal() {
+    let mut ctx = legacy_context().await;
+    let payer = ctx.payer();
+    let proposal = pda::proposal(&multisig(), 1).0;
+    let now = ctx.now().await;
+
+    ctx.send(&[instructions::migrate_proposal(multisig(), 1, payer)], &[])
+        .await
+        .unwrap();
+
+    let account: Proposal = ctx.account(proposal).await;
+    assert_eq!(account.version, Proposal::VERSION);
+    assert_eq!(account.multisig, multisig());
+    assert_eq!(account.transaction_index, 1);
+    // Legacy statuses had no timestamp, so the time lock runs from the migration.
+    assert_eq!(account.status, ProposalStatus::Approved { timestamp: now });
+    assert_eq!(account.approved, vec![key(5).pubkey()]);
+    assert!(account.rejected.is_empty());
+    assert!(account.cancelled.is_empty());
+    assert_eq!(account.expires_at, None);
+    // The proposal keeps room for the votes of all 3 members.
+    assert_eq!(ctx.data_len(proposal).await, Proposal::size(3));
+
+    let result = ctx
+        .send(&[instructions::migrate_proposal(multisig(), 1, payer)], &[])
+        .await;
+    assert_error(result, ErrorCode::AlreadyMigrated);
+}
+
+#[tokio::test]
+async fn migrate_rejects_other_accounts() {
+    // The discriminator must match the migrated type.
+    let mut ctx = legacy_context_with(SPENDING_LIMIT.to_vec()).await;
+    let payer = ctx.payer();
+    let result = ctx
+        .send(&[instructions::migrate_multisig(multisig(), key(3).pubkey(), payer)], &[])
+        .await;
+    assert_error(result, ErrorCode::NotMigratable);
+
+    // Accounts of other programs cannot be migrated.
+    let result = ctx
+        .send(&[instructions::migrate_spending_limit(payer, payer)], &[])
+        .await;
+    assert_error(result, ErrorCode::NotMigratable);
+}
+
+#[tokio::test]
+async fn new_accounts_have_the_current_version() {
+    let mut ctx = TestContext::with_program_config().await;
+    let payer = ctx.payer();
+    let multisig = ctx
+        .create_multisig(payer, 1, vec![member_with(payer, &[Permission::Vote, Permission::Initiate], 1)])
+        .await;
+
+    assert_eq!(
+        ctx.account::<ProgramConfig>(pda::program_config().0).await.version,
+        ProgramConfig::VERSION
+    );
+    let account: Multisig = ctx.account(multisig).await;
+    assert_eq!(account.version, Multisig::VERSION);
+
+    let result = ctx
+        .send(&[instructions::migrate_multisig(multisig, account.create_key, payer)], &[])
+        .await;
+    assert_error(result, ErrorCode::AlreadyMigrated);
+}
@@ -0,0 +1,868 @@
+mod common;
+
+use anchor_lang::error::ErrorCode as AnchorErrorCode;
+use anchor_lang::{system_program, InstructionData, ToAccountMetas};
+use anchor_spl::token::TokenAccount;
+use collectivex_multisig::constants::{MAX_MEMBERS, MAX_TIME_LOCK};
+use collectivex_multisig::error::ErrorCode;
+use collectivex_multisig::state::{Member, Multisig, Permission, Permissions};
+use collectivex_multisig::{accounts, instruction};
+use collectivex_multisig_sdk::{instructions, pda};
+use solana_sdk::instruction::Instruction;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+
+use crate::common::{
+    assert_anchor_error, assert_error, member, member_with, transfer_message, TestContext,
+    TestResult,
+};
+
+/// Sends `multisig_create` with the given configuration.
+async fn create(
+    ctx: &mut TestContext,
+    threshold: u16,
+    members: Vec<Member>,
+    time_lock: u32,
+) -> TestResult {
+    let payer = ctx.payer();
+    let create_key = Keypair::new();
+
+    ctx.send(
+        &[instructions::multisig_create(
+            payer,
+            create_key.pubkey(),
+            payer,
+            false,
+            payer,
+            threshold,
+            members,
+            time_lock,
+        )],
+        &[&create_key],
+    )
+    .await
+}
+
+#[tokio::test]
+async fn multisig_create() {
+    let mut ctx = TestContext::with_program_config().await;
+    let payer = ctx.payer();
+    let members = vec![member(payer), member_with(Pubkey::new_unique(), &[Permission::Vote], 2)];
+
+    let multisig = ctx.create_multisig(payer, 2, members.clone()).await;
+
+    let account: Multisig = ctx.account(multisig).await;
+    assert_eq!(account.config_authority, payer);
+    assert_eq!(account.threshold, 2);
+    assert_eq!(account.members, members);
+    assert_eq!(account.voting_weight(), 3);
+    assert_eq!(account.transaction_index, 0);
+}
+
+#[tokio::test]
+async fn multisig_create_charges_the_creation_fee() {
+    let mut ctx = TestContext::new().await;
+    let payer = ctx.payer();
+    let treasury = Pubkey::new_unique();
+    let fee = 1_000_000_000;
+    ctx.send(&[instructions::program_config_init(payer, payer, fee, treasury)], &[])
+        .await
+        .unwrap();
+
+    ctx.create_multisig(payer, 1, vec![member(payer)]).await;
+
+    assert_eq!(ctx.lamports(treasury).await, fee);
+}
+
+/// Starts a runtime with a program config charging `fee` tokens of a new mint, and creates
+/// the associated token account of the treasury. Returns the mint and the treasury.
+async fn with_token_fee(fee: u64) -> (TestContext, Pubkey, Pubkey) {
+    let mut ctx = TestContext::new().await;
+    let payer = ctx.payer();
+    let treasury = Pubkey::new_unique();
+    let mint = ctx.create_mint().await;
+    ctx.send(
+        &[
+            instructions::program_config_init(payer, payer, fee, treasury),
+            instructions::program_config_set_fee_mint(payer, Some(mint)),
+        ],
+        &[],
+    )
+    .await
+    .unwrap();
+    ctx.create_associated_token_account(mint, treasury).await;
+
+    (ctx, mint, treasury)
+}
+
+/// Sends `multisig_create_with_token_fee` with a single-member configuration.
+async fn create_with_token_fee(
+    ctx: &mut TestContext,
+    treasury: Pubkey,
+    mint: Pubkey,
+    creator_token_account: Pubkey,
+) -> TestResult {
+    let payer = ctx.payer();
+    let create_key = Keypair::new();
+
+    ctx.send(
+        &[instructions::multisig_create_with_token_fee(
+            payer,
+            create_key.pubkey(),
+            treasury,
+            false,
+            mint,
+            creator_token_account,
+            payer,
+            1,
+            vec![member(payer)],
+            0,
+        )],
+        &[&create_key],
+    )
+    .await
+}
+
+#[tokio::test]
+async fn multisig_create_charges_the_creation_fee_in_tokens() {
+    let (mut ctx, mint, treasury) = with_token_fee(1_000).await;
+    let payer = ctx.payer();
+    let creator_token_account = ctx.create_token_account(mint, payer, 5_000).await;
+
+    create_with_token_fee(&mut ctx, treasury, mint, creator_token_account)
+        .await
+        .unwrap();
+
+    let treasury_token_account = pda::associated_token_account(&treasury, &mint);
+    let account: TokenAccount = ctx.account(treasury_token_account).await;
+    assert_eq!(account.amount, 1_000);
+    let account: TokenAccount = ctx.account(creator_token_account).await;
+    assert_eq!(account.amount, 4_000);
+    assert_eq!(ctx.lamports(treasury).await, 0);
+
+    // Without the token accounts, the fee cannot be paid.
+    let create_key = Keypair::new();
+    let result = ctx
+        .send(
+            &[instructions::multisig_create(
+                payer,
+                create_key.pubkey(),
+                treasury,
+                false,
+                payer,
+                1,
+                vec![member(payer)],
+                0,
+            )],
+            &[&create_key],
+        )
+        .await;
+    assert_error(result, ErrorCode::MissingAccount);
+}
+
+#[tokio::test]
+async fn multisig_create_validates_the_token_fee_accounts() {
+    let (mut ctx, mint, treasury) = with_token_fee(1_000).await;
+    let payer = ctx.payer();
+
+    // The fee must be paid in the fee mint.
+    let other_mint = ctx.create_mint().await;
+    ctx.create_associated_token_account(other_mint, treasury).await;
+    let other_token_account = ctx.create_token_account(other_mint, payer, 5_000).await;
+    let result = create_with_token_fee(&mut ctx, treasury, other_mint, other_token_account).await;
+    assert_error(result, ErrorCode::InvalidMint);
+
+    // The fee must be paid from a token account of the creator.
+    let token_account = ctx.create_token_account(mint, Pubkey::new_unique(), 5_000).await;
+    let result = create_with_token_fee(&mut ctx, treasury, mint, token_account).await;
+    assert_anchor_error(result, AnchorErrorCode::ConstraintTokenOwner);
+
+    // The fee must land in the associated token account of the treasury.
+    let creator_token_account = ctx.create_token_account(mint, payer, 5_000).await;
+    let other_treasury_account = ctx.create_token_account(mint, treasury, 0).await;
+    let create_key = Keypair::new();
+    let mut ix = instructions::multisig_create_with_token_fee(
+        payer,
+        create_key.pubkey(),
+        treasury,
+        false,
+        mint,
+        creator_token_account,
+        payer,
+        1,
+        vec![member(payer)],
+        0,
+    );
+    let treasury_token_account = pda::associated_token_account(&treasury, &mint);
+    for meta in &mut ix.accounts {
+        if meta.pubkey == treasury_token_account {
+            meta.pubkey = other_treasury_account;
+        }
+    }
+    let result = ctx.send(&[ix], &[&create_key]).await;
+    assert_error(result, ErrorCode::InvalidTreasury);
+
+    // The creator must hold enough tokens to cover the fee.
+    let poor_token_account = ctx.create_token_account(mint, payer, 999).await;
+    let result = create_with_token_fee(&mut ctx, treasury, mint, poor_token_account).await;
+    assert!(result.is_err());
+
+    create_with_token_fee(&mut ctx, treasury, mint, creator_token_account)
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn multisig_create_rejects_another_treasury() {
+    let mut ctx = TestContext::with_program_config().await;
+    let payer = ctx.payer();
+    let create_key = Keypair::new();
+
+    let result = ctx
+        .send(
+            &[instructions::multisig_create(
+                payer,
+                create_key.pubkey(),
+                Pubkey::new_unique(),
+                false,
+                payer,
+                1,
+                vec![member(payer)],
+                0,
+            )],
+            &[&create_key],
+        )
+        .await;
+
+    assert_error(result, ErrorCode::InvalidTreasury);
+}
+
+#[tokio::test]
+async fn multisig_create_validates_the_configuration() {
+    let mut ctx = TestContext::with_program_config().await;
+    let payer = ctx.payer();
+    let other = Pubkey::new_unique();
+
+    let result = create(&mut ctx, 0, vec![member(payer)], 0).await;
+    assert_error(result, ErrorCode::ZeroThreshold);
+
+    let result = create(&mut ctx, 2, vec![member(payer)], 0).await;
+    assert_error(result, ErrorCode::InvalidThreshold);
+
+    // Only members allowed to vote count towards the threshold.
+    let members = vec![member(payer), member_with(other, &[Permission::Initiate], 5)];
+    let result = create(&mut ctx, 2, members, 0).await;
+    assert_error(result, ErrorCode::InvalidThreshold);
+
+    let result = create(&mut ctx, 0, vec![], 0).await;
+    assert_error(result, ErrorCode::ZeroThreshold);
+
+    let result = create(&mut ctx, 1, vec![member(payer), member(payer)], 0).await;
+    assert_error(result, ErrorCode::DuplicateMember);
+
+    let result = create(&mut ctx, 1, vec![member_with(payer, &[Permission::Vote], 0)], 0).await;
+    assert_error(result, ErrorCode::InvalidWeight);
+
+    let result = create(&mut ctx, 1, vec![member(payer)], MAX_TIME_LOCK + 1).await;
+    assert_error(result, ErrorCode::TimeLockTooLong);
+
+    create(&mut ctx, 1, vec![member(payer)], MAX_TIME_LOCK).await.unwrap();
+}
+
+#[tokio::test]
+async fn multisig_add_member() {
+    let mut ctx = TestContext::with_program_config().await;
+    let payer = ctx.payer();
+    let multisig = ctx.create_multisig(payer, 1, vec![member(payer)]).await;
+    let new_member = member_with(Pubkey::new_unique(), &[Permission::Vote], 3);
+
+    ctx.send(&[instructions::multisig_add_member(multisig, payer, payer, new_member)], &[])
+        .await
+        .unwrap();
+
+    let account: Multisig = ctx.account(multisig).await;
+    assert_eq!(account.members, vec![member(payer), new_member]);
+}
+
+#[tokio::test]
+async fn multisig_add_member_up_to_the_cap() {
+    let mut ctx = TestContext::with_program_config().await;
+    let payer = ctx.payer();
+    let multisig = ctx.create_multisig(payer, 1, vec![member(payer)]).await;
+
+    for _ in 1..MAX_MEMBERS {
+        ctx.send(
+            &[instructions::multisig_add_member(multisig, payer, payer, member(Pubkey::new_unique()))],
+            &[],
+        )
+        .await
+        .unwrap();
+    }
+    assert_eq!(ctx.account::<Multisig>(multisig).await.members.len(), MAX_MEMBERS);
+    assert_eq!(ctx.data_len(multisig).await, Multisig::size(MAX_MEMBERS));
+
+    let result = ctx
+        .send(
+            &[instructions::multisig_add_member(multisig, payer, payer, member(Pubkey::new_unique()))],
+            &[],
+        )
+        .await;
+    assert_error(result, ErrorCode::ExceedsMaxMembers);
+}
+
+#[tokio::test]
+async fn multisig_add_member_grows_the_account() {
+    let mut ctx = TestContext::with_program_config().await;
+    let payer = ctx.payer();
+    let multisig = ctx.create_multisig(payer, 1, vec![member(payer)]).await;
+    assert_eq!(ctx.data_len(multisig).await, Multisig::size(1));
+
+    // Growing the account requires a rent payer.
+    let ix = Instruction {
+        program_id: collectivex_multisig::ID,
+        accounts: accounts::MultisigEdit {
+            multisig,
+            config_authority: payer,
+            rent_payer: None,
+            rent_collector: None,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: instruction::MultisigAddMember {
+            new_member: member(Pubkey::new_unique()),
+        }
+        .data(),
+    };
+    let result = ctx.send(&[ix], &[]).await;
+    assert_error(result, ErrorCode::MissingAccount);
+
+    ctx.send(
+        &[instructions::multisig_add_member(multisig, payer, payer, member(Pubkey::new_unique()))],
+        &[],
+    )
+    .await
+    .unwrap();
+
+    let rent = ctx.minimum_balance(Multisig::size(2)).await;
+    assert_eq!(ctx.data_len(multisig).await, Multisig::size(2));
+    assert_eq!(ctx.lamports(multisig).await, rent);
+}
+
+#[tokio::test]
+async fn multisig_add_member_validates_the_member() {
+    let mut ctx = TestContext::with_program_config().await;
+    let payer = ctx.payer();
+    let multisig = ctx.create_multisig(payer, 1, vec![member(payer)]).await;
+
+    let result = ctx
+        .send(&[instructions::multisig_add_member(multisig, payer, payer, member(payer))], &[])
+        .await;
+    assert_error(result, ErrorCode::DuplicateMember);
+
+    let invalid_permissions = Member {
+        permissions: Permissions { mask: 8 },
+        ..member(Pubkey::new_unique())
+    };
+    let result = ctx
+        .send(
+            &[instructions::multisig_add_member(multisig, payer, payer, invalid_permissions)],
+            &[],
+        )
+        .await;
+    assert_error(result, ErrorCode::InvalidPermissions);
+
+    let zero_weight = member_with(Pubkey::new_unique(), &[Permission::Vote], 0);
+    let result = ctx
+        .send(&[instructions::multisig_add_member(multisig, payer, payer, zero_weight)], &[])
+        .await;
+    assert_error(result, ErrorCode::InvalidWeight);
+}
+
+#[tokio::test]
+async fn multisig_edit_requires_the_config_authority() {
+    let mut ctx = TestContext::with_program_config().await;
+    let payer = ctx.payer();
+    let multisig = ctx.create_multisig(payer, 1, vec![member(payer)]).await;
+    let other = Keypair::new();
+
+    let result = ctx
+        .send(
+            &[instructions::multisig_set_time_lock(multisig, other.pubkey(), 60)],
+            &[&other],
+        )
+        .await;
+
+    assert_error(result, ErrorCode::InvalidAuthority);
+}
+
+#[tokio::test]
+async fn multisig_edit_rejects_autonomous_multisigs() {
+    let mut ctx = TestContext::with_program_config().await;
+    let payer = ctx.payer();
+    let multisig = ctx
+        .create_multisig(Pubkey::default(), 1, vec![member(payer)])
+        .await;
+
+    let result = ctx
+        .send(&[instructions::multisig_set_time_lock(multisig, payer, 60)], &[])
+        .await;
+
+    assert_error(result, ErrorCode::NotSupportedForAutonomous);
+}
+
+#[tokio::test]
+async fn multisig_remove_member() {
+    let mut ctx = TestContext::with_program_config().await;
+    let payer = ctx.payer();
+    let other = Pubkey::new_unique();
+    let multisig = ctx
+        .create_multisig(payer, 1, vec![member(payer), member(other)])
+        .await;
+
+    ctx.send(&[instructions::multisig_remove_member(multisig, payer, None, other)], &[])
+        .await
+        .unwrap();
+    assert_eq!(ctx.account::<Multisig>(multisig).await.members, vec![member(payer)]);
+
+    let result = ctx
+        .send(&[instructions::multisig_remove_member(multisig, payer, None, other)], &[])
+        .await;
+    assert_error(result, ErrorCode::NotAMember);
+}
+
+#[tokio::test]
+async fn multisig_remove_member_refunds_the_rent_collector() {
+    let mut ctx = TestContext::with_program_config().await;
+    let payer = ctx.payer();
+    let other = Pubkey::new_unique();
+    let rent_collector = Pubkey::new_unique();
+    let multisig = ctx
+        .create_multisig(payer, 1, vec![member(payer), member(other)])
+        .await;
+    ctx.fund(rent_collector, 1_000_000).await;
+
+    // The rent collector must be the multisig's.
+    let result = ctx
+        .send(
+            &[instructions::multisig_remove_member(multisig, payer, Some(rent_collector), other)],
+            &[],
+        )
+        .await;
+    assert_error(result, ErrorCode::InvalidRentCollector);
+
+    ctx.send(
+        &[instructions::multisig_set_rent_collector(multisig, payer, Some(rent_collector))],
+        &[],
+    )
+    .await
+    .unwrap();
+    let lamports = ctx.lamports(multisig).await;
+
+    ctx.send(
+        &[instructions::multisig_remove_member(multisig, payer, Some(rent_collector), other)],
+        &[],
+    )
+    .await
+    .unwrap();
+
+    let rent = ctx.minimum_balance(Multisig::size(1)).await;
+    assert_eq!(ctx.data_len(multisig).await, Multisig::size(1));
+    assert_eq!(ctx.lamports(multisig).await, rent);
+    assert_eq!(ctx.lamports(rent_collector).await, 1_000_000 + lamports - rent);
+}
+
+#[tokio::test]
+async fn multisig_remove_member_keeps_the_threshold_reachable() {
+    let mut ctx = TestContext::with_program_config().await;
+    let payer = ctx.payer();
+    let other = Pubkey::new_unique();
+    let multisig = ctx
+        .create_multisig(payer, 2, vec![member(payer), member(other)])
+        .await;
+
+    let result = ctx
+        .send(&[instructions::multisig_remove_member(multisig, payer, None, other)], &[])
+        .await;
+    assert_error(result, ErrorCode::InvalidThreshold);
+
+    // Lowering the threshold first makes the member removable.
+    ctx.send(
+        &[
+            instructions::multisig_change_threshold(multisig, payer, 1),
+            instructions::multisig_remove_member(multisig, payer, None, other),
+        ],
+        &[],
+    )
+    .await
+    .unwrap();
+    assert_eq!(ctx.account::<Multisig>(multisig).await.members, vec![member(payer)]);
+
+    let multisig = ctx.create_multisig(payer, 1, vec![member(payer)]).await;
+    let result = ctx
+        .send(&[instructions::multisig_remove_member(multisig, payer, None, payer)], &[])
+        .await;
+    assert_error(result, ErrorCode::RemoveLastMember);
+}
+
+#[tokio::test]
+async fn multisig_set_time_lock_rejects_above_the_maximum() {
+    let mut ctx = TestContext::with_program_config().await;
+    let payer = ctx.payer();
+    let multisig = ctx.create_multisig(payer, 1, vec![member(payer)]).await;
+
+    let result = ctx
+        .send(
+            &[instructions::multisig_set_time_lock(multisig, payer, MAX_TIME_LOCK + 1)],
+            &[],
+        )
+        .await;
+
+    assert_error(result, ErrorCode::TimeLockTooLong);
+}
+
+#[tokio::test]
+async fn multisig_setters() {
+    let mut ctx = TestContext::with_program_config().await;
+    let payer = ctx.payer();
+    let multisig = ctx.create_multisig(payer, 1, vec![member(payer)]).await;
+    let rent_collector = Pubkey::new_unique();
+
+    ctx.send(
+        &[
+            instructions::multisig_set_time_lock(multisig, payer, 60),
+            instructions::multisig_set_rent_collector(multisig, payer, Some(rent_collector)),
+            instructions::multisig_set_voting_period(multisig, payer, Some(3600)),
+        ],
+        &[],
+    )
+    .await
+    .unwrap();
+
+    let account: Multisig = ctx.account(multisig).await;
+    assert_eq!(account.time_lock, 60);
+    assert_eq!(account.rent_collector, Some(rent_collector));
+    assert_eq!(account.voting_period, Some(3600));
+}
+
+#[tokio::test]
+async fn multisig_change_threshold() {
+    let mut ctx = TestContext::with_program_config().await;
+    let payer = ctx.payer();
+    let other = Pubkey::new_unique();
+    let multisig = ctx
+        .create_multisig(payer, 1, vec![member(payer), member_with(other, &[Permission::Vote], 2)])
+        .await;
+
+    ctx.send(&[instructions::multisig_change_threshold(multisig, payer, 3)], &[])
+        .await
+        .unwrap();
+    assert_eq!(ctx.account::<Multisig>(multisig).await.threshold, 3);
+
+    let result = ctx
+        .send(&[instructions::multisig_change_threshold(multisig, payer, 4)], &[])
+        .await;
+    assert_error(result, ErrorCode::InvalidThreshold);
+
+    let result = ctx
+        .send(&[instructions::multisig_change_threshold(multisig, payer, 0)], &[])
+        .await;
+    assert_error(result, ErrorCode::ZeroThreshold);
+}
+
+#[tokio::test]
+async fn multisig_set_member_permissions() {
+    let mut ctx = TestContext::with_program_config().await;
+    let payer = ctx.payer();
+    let other = Pubkey::new_unique();
+    let multisig = ctx
+        .create_multisig(payer, 1, vec![member(payer), member(other)])
+        .await;
+
+    let permissions = Permissions::from_vec(&[Permission::Initiate, Permission::Execute]);
+    ctx.send(
+        &[instructions::multisig_set_member_permissions(multisig, payer, other, permissions)],
+        &[],
+    )
+    .await
+    .unwrap();
+    let account: Multisig = ctx.account(multisig).await;
+    assert_eq!(account.members[1].permissions, permissions);
+
+    // The last voter cannot lose the Vote permission.
+    let result = ctx
+        .send(
+            &[instructions::multisig_set_member_permissions(
+                multisig,
+                payer,
+                payer,
+                permissions,
+            )],
+            &[],
+        )
+        .await;
+    assert_error(result, ErrorCode::InvalidThreshold);
+
+    let result = ctx
+        .send(
+            &[instructions::multisig_set_member_permissions(
+                multisig,
+                payer,
+                other,
+                Permissions { mask: 1 << 3 },
+            )],
+            &[],
+        )
+        .await;
+    assert_error(result, ErrorCode::InvalidPermissions);
+
+    let result = ctx
+        .send(
+            &[instructions::multisig_set_member_permissions(
+                multisig,
+                payer,
+                Pubkey::new_unique(),
+                permissions,
+            )],
+            &[],
+        )
+        .await;
+    assert_error(result, ErrorCode::NotAMember);
+}
+
+#[tokio::test]
+async fn multisig_set_member_weight() {
+    let mut ctx = TestContext::with_program_config().await;
+    let payer = ctx.payer();
+    let other = Pubkey::new_unique();
+    let multisig = ctx
+        .create_multisig(payer, 2, vec![member(payer), member(other)])
+        .await;
+
+    ctx.send(
+        &[
+            instructions::multisig_set_member_weight(multisig, payer, payer, 2),
+            instructions::multisig_change_threshold(multisig, payer, 3),
+        ],
+        &[],
+    )
+    .await
+    .unwrap();
+    let account: Multisig = ctx.account(multisig).await;
+    assert_eq!(account.members[0].weight, 2);
+    assert_eq!(account.threshold, 3);
+
+    // Lowering a weight must keep the threshold reachable.
+    let result = ctx
+        .send(&[instructions::multisig_set_member_weight(multisig, payer, payer, 1)], &[])
+        .await;
+    assert_error(result, ErrorCode::InvalidThreshold);
+
+    let result = ctx
+        .send(&[instructions::multisig_set_member_weight(multisig, payer, other, 0)], &[])
+        .await;
+    assert_error(result, ErrorCode::InvalidWeight);
+
+    let result = ctx
+        .send(
+            &[instructions::multisig_set_member_weight(multisig, payer, Pubkey::new_unique(), 1)],
+            &[],
+        )
+        .await;
+    assert_error(result, ErrorCode::NotAMember);
+}
+
+#[tokio::test]
+async fn multisig_config_authority_transfer() {
+    let mut ctx = TestContext::with_program_config().await;
+    let payer = ctx.payer();
+    let multisig = ctx.create_multisig(payer, 1, vec![member(payer)]).await;
+    let new_authority = Keypair::new();
+    let other = Keypair::new();
+
+    let result = ctx
+        .send(
+            &[instructions::multisig_propose_config_authority(multisig, payer, Pubkey::default())],
+            &[],
+        )
+        .await;
+    assert_error(result, ErrorCode::InvalidAuthority);
+
+    ctx.send(
+        &[instructions::multisig_propose_config_authority(
+            multisig,
+            payer,
+            new_authority.pubkey(),
+        )],
+        &[],
+    )
+    .await
+    .unwrap();
+
+    // The config authority only changes once the proposed authority accepts.
+    let account: Multisig = ctx.account(multisig).await;
+    assert_eq!(account.config_authority, payer);
+    assert_eq!(account.pending_config_authority, Some(new_authority.pubkey()));
+
+    let result = ctx
+        .send(
+            &[instructions::multisig_accept_config_authority(multisig, other.pubkey())],
+            &[&other],
+        )
+        .await;
+    assert_error(result, ErrorCode::InvalidAuthority);
+
+    ctx.send(&[instructions::multisig_cancel_config_authority(multisig, payer)], &[])
+        .await
+        .unwrap();
+    let result = ctx
+        .send(
+            &[instructions::multisig_accept_config_authority(multisig, new_authority.pubkey())],
+            &[&new_authority],
+        )
+        .await;
+    assert_error(result, ErrorCode::NoPendingAuthority);
+
+    ctx.send(
+        &[
+            instructions::multisig_propose_config_authority(
+                multisig,
+                payer,
+                new_authority.pubkey(),
+            ),
+            instructions::multisig_accept_config_authority(multisig, new_authority.pubkey()),
+        ],
+        &[&new_authority],
+    )
+    .await
+    .unwrap();
+
+    let account: Multisig = ctx.account(multisig).await;
+    assert_eq!(account.config_authority, new_authority.pubkey());
+    assert_eq!(account.pending_config_authority, None);
+
+    let result = ctx
+        .send(&[instructions::multisig_set_time_lock(multisig, payer, 60)], &[])
+        .await;
+    assert_error(result, ErrorCode::InvalidAuthority);
+}
+
+#[tokio::test]
+async fn multisig_renounce_config_authority() {
+    let mut ctx = TestContext::with_program_config().await;
+    let payer = ctx.payer();
+    let multisig = ctx.create_multisig(payer, 1, vec![member(payer)]).await;
+    let new_authority = Keypair::new();
+
+    ctx.send(
+        &[
+            instructions::multisig_propose_config_authority(
+                multisig,
+                payer,
+                new_authority.pubkey(),
+            ),
+            instructions::multisig_renounce_config_authority(multisig, payer),
+        ],
+        &[],
+    )
+    .await
+    .unwrap();
+
+    let account: Multisig = ctx.account(multisig).await;
+    assert!(account.is_autonomous());
+    assert_eq!(account.pending_config_authority, None);
+
+    // A transfer proposed before renouncing can no longer be accepted.
+    let result = ctx
+        .send(
+            &[instructions::multisig_accept_config_authority(multisig, new_authority.pubkey())],
+            &[&new_authority],
+        )
+        .await;
+    assert_error(result, ErrorCode::NoPendingAuthority);
+
+    let result = ctx
+        .send(&[instructions::multisig_set_time_lock(multisig, payer, 60)], &[])
+        .await;
+    assert_error(result, ErrorCode::NotSupportedForAutonomous);
+}
+
+#[tokio::test]
+async fn multisig_set_voting_period_rejects_zero() {
+    let mut ctx = TestContext::with_program_config().await;
+    let payer = ctx.payer();
+    let multisig = ctx.create_multisig(payer, 1, vec![member(payer)]).await;
+
+    let result = ctx
+        .send(&[instructions::multisig_set_voting_period(multisig, payer, Some(0))], &[])
+        .await;
+
+    assert_error(result, ErrorCode::InvalidVotingPeriod);
+}
+
+#[tokio::test]
+async fn multisig_edit_makes_pending_proposals_stale() {
+    let mut ctx = TestContext::with_program_config().await;
+    let payer = ctx.payer();
+    let multisig = ctx.create_multisig(payer, 1, vec![member(payer)]).await;
+    let vault = pda::vault(&multisig, 0).0;
+
+    ctx.send(
+        &[
+            instructions::vault_transaction_create(
+                multisig,
+                payer,
+                payer,
+                1,
+                0,
+                transfer_message(vault, payer, 1),
+            ),
+            instructions::proposal_create(multisig, payer, payer, 1, false),
+        ],
+        &[],
+    )
+    .await
+    .unwrap();
+
+    ctx.send(&[instructions::multisig_set_time_lock(multisig, payer, 60)], &[])
+        .await
+        .unwrap();
+    assert_eq!(ctx.account::<Multisig>(multisig).await.stale_transaction_index, 1);
+
+    let result = ctx
+        .send(&[instructions::proposal_approve(multisig, payer, 1, None)], &[])
+        .await;
+    assert_error(result, ErrorCode::StaleProposal);
+}
+
+#[tokio::test]
+async fn multisig_voting_edits_make_pending_proposals_stale() {
+    let mut ctx = TestContext::with_program_config().await;
+    let payer = ctx.payer();
+    let multisig = ctx.create_multisig(payer, 1, vec![member(payer)]).await;
+    let vault = pda::vault(&multisig, 0).0;
+    let all = Permissions::all();
+
+    let edits = [
+        instructions::multisig_change_threshold(multisig, payer, 1),
+        instructions::multisig_set_member_permissions(multisig, payer, payer, all),
+        instructions::multisig_set_member_weight(multisig, payer, payer, 1),
+    ];
+    for (index, edit) in (1..).zip(edits) {
+        ctx.send(
+            &[instructions::vault_transaction_create(
+                multisig,
+                payer,
+                payer,
+                index,
+                0,
+                transfer_message(vault, payer, 1),
+            )],
+            &[],
+        )
+        .await
+        .unwrap();
+
+        ctx.send(&[edit], &[]).await.unwrap();
+        assert_eq!(ctx.account::<Multisig>(multisig).await.stale_transaction_index, index);
+    }
+}
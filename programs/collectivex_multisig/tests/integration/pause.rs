@@ -0,0 +1,363 @@
+mod common;
+
+use collectivex_multisig::error::ErrorCode;
+use collectivex_multisig::state::{
+    ConfigAction, PauseCategory, PausedCategories, Period, ProgramConfig, Proposal, ProposalStatus,
+};
+use collectivex_multisig_sdk::{instructions, pda};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+
+use crate::common::{assert_error, member, transfer_message, TestContext, TestResult};
+
+/// A controlled multisig with a funded vault, whose only member is the payer.
+struct Fixture {
+    ctx: TestContext,
+    multisig: Pubkey,
+}
+
+impl Fixture {
+    async fn new() -> Self {
+        let mut ctx = TestContext::with_program_config().await;
+        let payer = ctx.payer();
+        let multisig = ctx.create_multisig(payer, 1, vec![member(payer)]).await;
+        ctx.fund(pda::vault(&multisig, 0).0, 10_000_000).await;
+
+        Self { ctx, multisig }
+    }
+
+    async fn set_paused(&mut self, categories: &[PauseCategory]) {
+        let payer = self.ctx.payer();
+        self.ctx
+            .send(
+                &[instructions::program_config_set_paused(
+                    payer,
+                    PausedCategories::from_vec(categories),
+                )],
+                &[],
+            )
+            .await
+            .unwrap();
+    }
+
+    /// Creates vault transaction `index`, sending 1 lamport to the payer.
+    async fn create_transaction(&mut self, index: u64) -> TestResult {
+        let payer = self.ctx.payer();
+        let message = transfer_message(pda::vault(&self.multisig, 0).0, payer, 1);
+
+        self.ctx
+            .send(
+                &[instructions::vault_transaction_create(
+                    self.multisig,
+                    payer,
+                    payer,
+                    index,
+                    0,
+                    message,
+                )],
+                &[],
+            )
+            .await
+    }
+
+    async fn create_proposal(&mut self, index: u64) -> TestResult {
+        let payer = self.ctx.payer();
+        self.ctx
+            .send(
+                &[instructions::proposal_create(self.multisig, payer, payer, index, false)],
+                &[],
+            )
+            .await
+    }
+
+    async fn approve(&mut self, index: u64) -> TestResult {
+        let payer = self.ctx.payer();
+        self.ctx
+            .send(&[instructions::proposal_approve(self.multisig, payer, index, None)], &[])
+            .await
+    }
+
+    async fn execute(&mut self, index: u64) -> TestResult {
+        let payer = self.ctx.payer();
+        let message = transfer_message(pda::vault(&self.multisig, 0).0, payer, 1);
+
+        self.ctx
+            .send(
+                &[instructions::vault_transaction_execute(
+                    self.multisig,
+                    payer,
+                    index,
+                    0,
+                    &message,
+                )],
+                &[],
+            )
+            .await
+    }
+}
+
+#[tokio::test]
+async fn program_config_set_paused() {
+    let mut ctx = TestContext::with_program_config().await;
+    let payer = ctx.payer();
+
+    let paused = PausedCategories::from_vec(&[PauseCategory::Create, PauseCategory::Execute]);
+    ctx.send(&[instructions::program_config_set_paused(payer, paused)], &[])
+        .await
+        .unwrap();
+
+    let config: ProgramConfig = ctx.account(pda::program_config().0).await;
+    assert_eq!(config.paused, paused);
+    assert!(config.paused.has(PauseCategory::Create));
+    assert!(!config.paused.has(PauseCategory::Vote));
+
+    let result = ctx
+        .send(
+            &[instructions::program_config_set_paused(payer, PausedCategories { mask: 1 << 4 })],
+            &[],
+        )
+        .await;
+    assert_error(result, ErrorCode::InvalidPausedCategories);
+
+    let outsider = Keypair::new();
+    let result = ctx
+        .send(
+            &[instructions::program_config_set_paused(
+                outsider.pubkey(),
+                PausedCategories::default(),
+            )],
+            &[&outsider],
+        )
+        .await;
+    assert_error(result, ErrorCode::InvalidAuthority);
+}
+
+#[tokio::test]
+async fn pause_create() {
+    let mut f = Fixture::new().await;
+    let payer = f.ctx.payer();
+    f.set_paused(&[PauseCategory::Create]).await;
+
+    let create_key = Keypair::new();
+    let create = instructions::multisig_create(
+        payer,
+        create_key.pubkey(),
+        payer,
+        false,
+        payer,
+        1,
+        vec![member(payer)],
+        0,
+    );
+    let result = f.ctx.send(std::slice::from_ref(&create), &[&create_key]).await;
+    assert_error(result, ErrorCode::Paused);
+
+    // Existing multisigs keep working.
+    f.create_transaction(1).await.unwrap();
+
+    f.set_paused(&[]).await;
+    f.ctx.send(&[create], &[&create_key]).await.unwrap();
+}
+
+#[tokio::test]
+async fn pause_initiate() {
+    let mut f = Fixture::new().await;
+    let payer = f.ctx.payer();
+    f.create_transaction(1).await.unwrap();
+    f.set_paused(&[PauseCategory::Initiate]).await;
+
+    assert_error(f.create_transaction(2).await, ErrorCode::Paused);
+    assert_error(f.create_proposal(1).await, ErrorCode::Paused);
+
+    let autonomous = f
+        .ctx
+        .create_multisig(Pubkey::default(), 1, vec![member(payer)])
+        .await;
+    let result = f
+        .ctx
+        .send(
+            &[instructions::config_transaction_create(
+                autonomous,
+                payer,
+                payer,
+                1,
+                vec![ConfigAction::SetTimeLock { new_time_lock: 60 }],
+            )],
+            &[],
+        )
+        .await;
+    assert_error(result, ErrorCode::Paused);
+
+    f.set_paused(&[]).await;
+    f.create_proposal(1).await.unwrap();
+}
+
+#[tokio::test]
+async fn pause_vote() {
+    let mut f = Fixture::new().await;
+    let payer = f.ctx.payer();
+    f.create_transaction(1).await.unwrap();
+    f.create_proposal(1).await.unwrap();
+    f.set_paused(&[PauseCategory::Vote]).await;
+
+    assert_error(f.approve(1).await, ErrorCode::Paused);
+    let result = f
+        .ctx
+        .send(&[instructions::proposal_reject(f.multisig, payer, 1, None)], &[])
+        .await;
+    assert_error(result, ErrorCode::Paused);
+
+    f.set_paused(&[]).await;
+    f.approve(1).await.unwrap();
+}
+
+#[tokio::test]
+async fn pause_execute() {
+    let mut f = Fixture::new().await;
+    let payer = f.ctx.payer();
+    let message = transfer_message(pda::vault(&f.multisig, 0).0, payer, 1);
+    let index = f
+        .ctx
+        .create_approved(f.multisig, |index| {
+            instructions::vault_transaction_create(f.multisig, payer, payer, index, 0, message)
+        })
+        .await;
+    f.set_paused(&[PauseCategory::Execute]).await;
+
+    assert_error(f.execute(index).await, ErrorCode::Paused);
+
+    f.set_paused(&[]).await;
+    f.execute(index).await.unwrap();
+    let proposal: Proposal = f.ctx.account(pda::proposal(&f.multisig, index).0).await;
+    assert!(matches!(proposal.status, ProposalStatus::Executed { .. }));
+}
+
+#[tokio::test]
+async fn pause_execute_covers_spending_limits() {
+    let mut f = Fixture::new().await;
+    let payer = f.ctx.payer();
+    let create_key = Keypair::new();
+    let destination = Pubkey::new_unique();
+    f.ctx
+        .send(
+            &[instructions::multisig_add_spending_limit(
+                f.multisig,
+                payer,
+                create_key.pubkey(),
+                0,
+                Pubkey::default(),
+                5_000_000,
+                Period::Day,
+                vec![payer],
+                vec![destination],
+            )],
+            &[&create_key],
+        )
+        .await
+        .unwrap();
+    f.set_paused(&[PauseCategory::Execute]).await;
+
+    let result = f
+        .ctx
+        .send(
+            &[instructions::spending_limit_use_sol(
+                f.multisig,
+                payer,
+                pda::spending_limit(&f.multisig, &create_key.pubkey()).0,
+                0,
+                destination,
+                1_000_000,
+            )],
+            &[],
+        )
+        .await;
+    assert_error(result, ErrorCode::Paused);
+}
+
+#[tokio::test]
+async fn pause_keeps_expiry_and_configuration_open() {
+    let mut f = Fixture::new().await;
+    let payer = f.ctx.payer();
+    f.ctx
+        .send(
+            &[instructions::multisig_set_voting_period(f.multisig, payer, Some(3600))],
+            &[],
+        )
+        .await
+        .unwrap();
+    f.create_transaction(1).await.unwrap();
+    f.create_proposal(1).await.unwrap();
+    f.set_paused(&[
+        PauseCategory::Create,
+        PauseCategory::Initiate,
+        PauseCategory::Vote,
+        PauseCategory::Execute,
+    ])
+    .await;
+
+    // Stale proposals can still be expired.
+    f.ctx.warp(3600).await;
+    f.ctx
+        .send(&[instructions::proposal_expire(f.multisig, payer, 1)], &[])
+        .await
+        .unwrap();
+
+    // The config authority and the program config authority can still act.
+    f.ctx
+        .send(
+            &[
+                instructions::multisig_set_rent_collector(f.multisig, payer, Some(payer)),
+                instructions::program_config_set_creation_fee(payer, 42),
+            ],
+            &[],
+        )
+        .await
+        .unwrap();
+    let config: ProgramConfig = f.ctx.account(pda::program_config().0).await;
+    assert_eq!(config.paused, PausedCategories::all());
+    assert_eq!(config.creation_fee, 42);
+}
+
+#[tokio::test]
+async fn pause_keeps_rent_reclamation_open() {
+    let mut ctx = TestContext::with_program_config().await;
+    let payer = ctx.payer();
+    let multisig = ctx
+        .create_multisig(Pubkey::default(), 1, vec![member(payer)])
+        .await;
+    let actions = vec![ConfigAction::SetRentCollector {
+        new_rent_collector: Some(payer),
+    }];
+
+    let index = ctx
+        .create_approved(multisig, |index| {
+            instructions::config_transaction_create(multisig, payer, payer, index, actions.clone())
+        })
+        .await;
+    ctx.send(
+        &[instructions::config_transaction_execute(
+            multisig,
+            payer,
+            index,
+            &actions,
+            Some(payer),
+            None,
+        )],
+        &[],
+    )
+    .await
+    .unwrap();
+    ctx.send(
+        &[instructions::program_config_set_paused(payer, PausedCategories::all())],
+        &[],
+    )
+    .await
+    .unwrap();
+
+    ctx.send(&[instructions::config_transaction_accounts_close(multisig, payer, index, payer)], &[])
+        .await
+        .unwrap();
+
+    assert!(!ctx.exists(pda::transaction(&multisig, index).0).await);
+    assert!(!ctx.exists(pda::proposal(&multisig, index).0).await);
+}
@@ -0,0 +1,293 @@
+mod common;
+
+use anchor_lang::error::ErrorCode as AnchorErrorCode;
+use collectivex_multisig::error::ErrorCode;
+use collectivex_multisig::state::ProgramConfig;
+use collectivex_multisig_sdk::{instructions, pda};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+
+use crate::common::{assert_anchor_error, assert_error, program_data_account, TestContext};
+
+#[tokio::test]
+async fn program_config_init() {
+    let mut ctx = TestContext::new().await;
+    let payer = ctx.payer();
+    let authority = Pubkey::new_unique();
+    let treasury = Pubkey::new_unique();
+
+    ctx.send(
+        &[instructions::program_config_init(payer, authority, 100, treasury)],
+        &[],
+    )
+    .await
+    .unwrap();
+
+    let config: ProgramConfig = ctx.account(pda::program_config().0).await;
+    assert_eq!(config.authority, authority);
+    assert_eq!(config.creation_fee, 100);
+    assert_eq!(config.treasury, treasury);
+}
+
+#[tokio::test]
+async fn program_config_init_rejects_default_authority() {
+    let mut ctx = TestContext::new().await;
+    let payer = ctx.payer();
+
+    let result = ctx
+        .send(
+            &[instructions::program_config_init(payer, Pubkey::default(), 0, payer)],
+            &[],
+        )
+        .await;
+
+    assert_error(result, ErrorCode::InvalidAuthority);
+}
+
+#[tokio::test]
+async fn program_config_init_rejects_default_treasury() {
+    let mut ctx = TestContext::new().await;
+    let payer = ctx.payer();
+
+    let result = ctx
+        .send(
+            &[instructions::program_config_init(payer, payer, 0, Pubkey::default())],
+            &[],
+        )
+        .await;
+
+    assert_error(result, ErrorCode::InvalidTreasury);
+}
+
+#[tokio::test]
+async fn program_config_init_requires_the_upgrade_authority() {
+    let mut ctx = TestContext::new().await;
+    let payer = ctx.payer();
+    let attacker = Keypair::new();
+    ctx.fund(attacker.pubkey(), 1_000_000_000).await;
+
+    // Someone racing the deployer to install their own authority and treasury.
+    let result = ctx
+        .send(
+            &[instructions::program_config_init(
+                attacker.pubkey(),
+                attacker.pubkey(),
+                0,
+                attacker.pubkey(),
+            )],
+            &[&attacker],
+        )
+        .await;
+    assert_error(result, ErrorCode::Unauthorized);
+    assert!(!ctx.exists(pda::program_config().0).await);
+
+    let treasury = Pubkey::new_unique();
+    ctx.send(
+        &[instructions::program_config_init(payer, payer, 0, treasury)],
+        &[],
+    )
+    .await
+    .unwrap();
+
+    let config: ProgramConfig = ctx.account(pda::program_config().0).await;
+    assert_eq!(config.authority, payer);
+    assert_eq!(config.treasury, treasury);
+}
+
+#[tokio::test]
+async fn program_config_init_rejects_a_forged_program_data_account() {
+    let mut ctx = TestContext::new().await;
+    let attacker = Keypair::new();
+    ctx.fund(attacker.pubkey(), 1_000_000_000).await;
+
+    // The program data of another program, upgradeable by the attacker.
+    let forged = Pubkey::new_unique();
+    ctx.context
+        .set_account(&forged, &program_data_account(Some(attacker.pubkey())));
+
+    let mut init = instructions::program_config_init(
+        attacker.pubkey(),
+        attacker.pubkey(),
+        0,
+        attacker.pubkey(),
+    );
+    let program_data = pda::program_data().0;
+    for meta in &mut init.accounts {
+        if meta.pubkey == program_data {
+            meta.pubkey = forged;
+        }
+    }
+
+    let result = ctx.send(&[init], &[&attacker]).await;
+    assert_anchor_error(result, AnchorErrorCode::ConstraintSeeds);
+}
+
+#[tokio::test]
+async fn program_config_init_rejects_immutable_programs() {
+    let mut ctx = TestContext::new().await;
+    let payer = ctx.payer();
+    ctx.set_upgrade_authority(None);
+
+    let result = ctx
+        .send(&[instructions::program_config_init(payer, payer, 0, payer)], &[])
+        .await;
+
+    assert_error(result, ErrorCode::Unauthorized);
+}
+
+#[tokio::test]
+async fn program_config_setters() {
+    let mut ctx = TestContext::with_program_config().await;
+    let payer = ctx.payer();
+    let new_authority = Keypair::new();
+    let new_treasury = Pubkey::new_unique();
+
+    ctx.send(
+        &[
+            instructions::program_config_set_creation_fee(payer, 42),
+            instructions::program_config_set_treasury(payer, new_treasury),
+            instructions::program_config_propose_authority(payer, new_authority.pubkey()),
+            instructions::program_config_accept_authority(new_authority.pubkey()),
+        ],
+        &[&new_authority],
+    )
+    .await
+    .unwrap();
+
+    let config: ProgramConfig = ctx.account(pda::program_config().0).await;
+    assert_eq!(config.authority, new_authority.pubkey());
+    assert_eq!(config.creation_fee, 42);
+    assert_eq!(config.treasury, new_treasury);
+
+    // The previous authority can no longer edit the config.
+    let result = ctx
+        .send(&[instructions::program_config_set_creation_fee(payer, 0)], &[])
+        .await;
+    assert_error(result, ErrorCode::InvalidAuthority);
+
+    ctx.send(
+        &[instructions::program_config_set_creation_fee(new_authority.pubkey(), 0)],
+        &[&new_authority],
+    )
+    .await
+    .unwrap();
+}
+
+#[tokio::test]
+async fn program_config_set_treasury_rejects_default() {
+    let mut ctx = TestContext::with_program_config().await;
+    let payer = ctx.payer();
+
+    let result = ctx
+        .send(
+            &[instructions::program_config_set_treasury(payer, Pubkey::default())],
+            &[],
+        )
+        .await;
+
+    assert_error(result, ErrorCode::InvalidTreasury);
+}
+
+#[tokio::test]
+async fn program_config_set_fee_mint() {
+    let mut ctx = TestContext::with_program_config().await;
+    let payer = ctx.payer();
+    let fee_mint = Pubkey::new_unique();
+
+    ctx.send(&[instructions::program_config_set_fee_mint(payer, Some(fee_mint))], &[])
+        .await
+        .unwrap();
+    let config: ProgramConfig = ctx.account(pda::program_config().0).await;
+    assert_eq!(config.fee_mint, Some(fee_mint));
+
+    ctx.send(&[instructions::program_config_set_fee_mint(payer, None)], &[])
+        .await
+        .unwrap();
+    let config: ProgramConfig = ctx.account(pda::program_config().0).await;
+    assert_eq!(config.fee_mint, None);
+
+    let result = ctx
+        .send(
+            &[instructions::program_config_set_fee_mint(payer, Some(Pubkey::default()))],
+            &[],
+        )
+        .await;
+    assert_error(result, ErrorCode::InvalidMint);
+
+    let other = Keypair::new();
+    let result = ctx
+        .send(
+            &[instructions::program_config_set_fee_mint(other.pubkey(), Some(fee_mint))],
+            &[&other],
+        )
+        .await;
+    assert_error(result, ErrorCode::InvalidAuthority);
+}
+
+#[tokio::test]
+async fn program_config_authority_transfer() {
+    let mut ctx = TestContext::with_program_config().await;
+    let payer = ctx.payer();
+    let new_authority = Keypair::new();
+    let other = Keypair::new();
+
+    let result = ctx
+        .send(
+            &[instructions::program_config_accept_authority(new_authority.pubkey())],
+            &[&new_authority],
+        )
+        .await;
+    assert_error(result, ErrorCode::NoPendingAuthority);
+
+    let result = ctx
+        .send(&[instructions::program_config_propose_authority(payer, Pubkey::default())], &[])
+        .await;
+    assert_error(result, ErrorCode::InvalidAuthority);
+
+    ctx.send(
+        &[instructions::program_config_propose_authority(payer, new_authority.pubkey())],
+        &[],
+    )
+    .await
+    .unwrap();
+
+    // The authority only changes once the proposed authority accepts.
+    let config: ProgramConfig = ctx.account(pda::program_config().0).await;
+    assert_eq!(config.authority, payer);
+    assert_eq!(config.pending_authority, Some(new_authority.pubkey()));
+
+    let result = ctx
+        .send(&[instructions::program_config_accept_authority(other.pubkey())], &[&other])
+        .await;
+    assert_error(result, ErrorCode::InvalidAuthority);
+
+    ctx.send(&[instructions::program_config_cancel_authority(payer)], &[])
+        .await
+        .unwrap();
+    let result = ctx
+        .send(
+            &[instructions::program_config_accept_authority(new_authority.pubkey())],
+            &[&new_authority],
+        )
+        .await;
+    assert_error(result, ErrorCode::NoPendingAuthority);
+
+    ctx.send(
+        &[
+            instructions::program_config_propose_authority(payer, new_authority.pubkey()),
+            instructions::program_config_accept_authority(new_authority.pubkey()),
+        ],
+        &[&new_authority],
+    )
+    .await
+    .unwrap();
+
+    let config: ProgramConfig = ctx.account(pda::program_config().0).await;
+    assert_eq!(config.authority, new_authority.pubkey());
+    assert_eq!(config.pending_authority, None);
+
+    let result = ctx
+        .send(&[instructions::program_config_cancel_authority(payer)], &[])
+        .await;
+    assert_error(result, ErrorCode::InvalidAuthority);
+}
@@ -0,0 +1,676 @@
+mod common;
+
+use collectivex_multisig::error::ErrorCode;
+use collectivex_multisig::state::{Multisig, Permission, Proposal, ProposalStatus};
+use collectivex_multisig_sdk::{instructions, pda};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+
+use crate::common::{
+    assert_anchor_error, assert_error, member, member_with, transfer_message, TestContext,
+};
+
+/// A multisig with the payer and two voters, each of weight 1, and a threshold of 2.
+struct Fixture {
+    ctx: TestContext,
+    multisig: Pubkey,
+    voters: [Keypair; 2],
+}
+
+impl Fixture {
+    async fn new() -> Self {
+        let mut ctx = TestContext::with_program_config().await;
+        let payer = ctx.payer();
+        let voters = [Keypair::new(), Keypair::new()];
+        let multisig = ctx
+            .create_multisig(
+                payer,
+                2,
+                vec![
+                    member(payer),
+                    member_with(voters[0].pubkey(), &[Permission::Vote], 1),
+                    member_with(voters[1].pubkey(), &[Permission::Vote], 1),
+                ],
+            )
+            .await;
+
+        Self {
+            ctx,
+            multisig,
+            voters,
+        }
+    }
+
+    /// Creates a vault transaction and its proposal, returning the transaction index.
+    async fn create_proposal(&mut self, draft: bool) -> u64 {
+        let payer = self.ctx.payer();
+        let multisig = self.multisig;
+        let index = self.ctx.account::<Multisig>(multisig).await.transaction_index + 1;
+        let vault = pda::vault(&multisig, 0).0;
+
+        self.ctx
+            .send(
+                &[
+                    instructions::vault_transaction_create(
+                        multisig,
+                        payer,
+                        payer,
+                        index,
+                        0,
+                        transfer_message(vault, payer, 1),
+                    ),
+                    instructions::proposal_create(multisig, payer, payer, index, draft),
+                ],
+                &[],
+            )
+            .await
+            .unwrap();
+
+        index
+    }
+
+    async fn proposal(&mut self, index: u64) -> Proposal {
+        self.ctx.account(pda::proposal(&self.multisig, index).0).await
+    }
+}
+
+#[tokio::test]
+async fn proposal_create() {
+    let mut f = Fixture::new().await;
+    let index = f.create_proposal(false).await;
+
+    let proposal = f.proposal(index).await;
+    assert_eq!(proposal.multisig, f.multisig);
+    assert_eq!(proposal.transaction_index, index);
+    assert!(matches!(proposal.status, ProposalStatus::Active { .. }));
+    assert_eq!(proposal.expires_at, None);
+}
+
+#[tokio::test]
+async fn proposal_create_requires_an_existing_transaction() {
+    let mut f = Fixture::new().await;
+    let payer = f.ctx.payer();
+
+    for index in [0, 1] {
+        let result = f
+            .ctx
+            .send(
+                &[instructions::proposal_create(f.multisig, payer, payer, index, false)],
+                &[],
+            )
+            .await;
+        assert_error(result, ErrorCode::InvalidTransactionIndex);
+    }
+
+    let index = f.create_proposal(false).await;
+    let result = f
+        .ctx
+        .send(
+            &[instructions::proposal_create(f.multisig, payer, payer, index + 1, false)],
+            &[],
+        )
+        .await;
+    assert_error(result, ErrorCode::InvalidTransactionIndex);
+}
+
+#[tokio::test]
+async fn proposal_create_requires_initiate_permission() {
+    let mut f = Fixture::new().await;
+    let payer = f.ctx.payer();
+    let vault = pda::vault(&f.multisig, 0).0;
+    f.ctx
+        .send(
+            &[instructions::vault_transaction_create(
+                f.multisig,
+                payer,
+                payer,
+                1,
+                0,
+                transfer_message(vault, payer, 1),
+            )],
+            &[],
+        )
+        .await
+        .unwrap();
+
+    let voter = f.voters[0].insecure_clone();
+    let result = f
+        .ctx
+        .send(
+            &[instructions::proposal_create(f.multisig, voter.pubkey(), payer, 1, false)],
+            &[&voter],
+        )
+        .await;
+
+    assert_error(result, ErrorCode::Unauthorized);
+}
+
+#[tokio::test]
+async fn proposal_activate() {
+    let mut f = Fixture::new().await;
+    let payer = f.ctx.payer();
+    let index = f.create_proposal(true).await;
+    assert!(matches!(f.proposal(index).await.status, ProposalStatus::Draft { .. }));
+
+    // Drafts are not open for voting.
+    let result = f
+        .ctx
+        .send(&[instructions::proposal_approve(f.multisig, payer, index, None)], &[])
+        .await;
+    assert_error(result, ErrorCode::InvalidProposalStatus);
+
+    f.ctx
+        .send(&[instructions::proposal_activate(f.multisig, payer, index)], &[])
+        .await
+        .unwrap();
+    assert!(matches!(f.proposal(index).await.status, ProposalStatus::Active { .. }));
+
+    let result = f
+        .ctx
+        .send(&[instructions::proposal_activate(f.multisig, payer, index)], &[])
+        .await;
+    assert_error(result, ErrorCode::InvalidProposalStatus);
+}
+
+#[tokio::test]
+async fn proposal_activate_requires_initiate_permission() {
+    let mut f = Fixture::new().await;
+    let index = f.create_proposal(true).await;
+
+    let voter = f.voters[0].insecure_clone();
+    let result = f
+        .ctx
+        .send(
+            &[instructions::proposal_activate(f.multisig, voter.pubkey(), index)],
+            &[&voter],
+        )
+        .await;
+
+    assert_error(result, ErrorCode::Unauthorized);
+    assert!(matches!(f.proposal(index).await.status, ProposalStatus::Draft { .. }));
+}
+
+#[tokio::test]
+async fn proposal_approve_reaches_the_threshold() {
+    let mut f = Fixture::new().await;
+    let payer = f.ctx.payer();
+    let index = f.create_proposal(false).await;
+
+    f.ctx
+        .send(
+            &[instructions::proposal_approve(f.multisig, payer, index, Some("lgtm".into()))],
+            &[],
+        )
+        .await
+        .unwrap();
+    let proposal = f.proposal(index).await;
+    assert_eq!(proposal.approved, vec![payer]);
+    assert!(matches!(proposal.status, ProposalStatus::Active { .. }));
+
+    // Approving again changes nothing.
+    f.ctx
+        .send(&[instructions::proposal_approve(f.multisig, payer, index, None)], &[])
+        .await
+        .unwrap();
+    assert_eq!(f.proposal(index).await.approved, vec![payer]);
+
+    let voter = f.voters[0].insecure_clone();
+    f.ctx
+        .send(
+            &[instructions::proposal_approve(f.multisig, voter.pubkey(), index, None)],
+            &[&voter],
+        )
+        .await
+        .unwrap();
+    let proposal = f.proposal(index).await;
+    assert_eq!(proposal.approved, vec![payer, voter.pubkey()]);
+    assert!(matches!(proposal.status, ProposalStatus::Approved { .. }));
+}
+
+#[tokio::test]
+async fn proposal_reject_reaches_the_cutoff() {
+    let mut f = Fixture::new().await;
+    let payer = f.ctx.payer();
+    let index = f.create_proposal(false).await;
+
+    f.ctx
+        .send(&[instructions::proposal_approve(f.multisig, payer, index, None)], &[])
+        .await
+        .unwrap();
+
+    // Changing a vote moves the member from one list to the other.
+    f.ctx
+        .send(&[instructions::proposal_reject(f.multisig, payer, index, None)], &[])
+        .await
+        .unwrap();
+    let proposal = f.proposal(index).await;
+    assert!(proposal.approved.is_empty());
+    assert_eq!(proposal.rejected, vec![payer]);
+
+    f.ctx
+        .send(&[instructions::proposal_reject(f.multisig, payer, index, None)], &[])
+        .await
+        .unwrap();
+    assert_eq!(f.proposal(index).await.rejected, vec![payer]);
+
+    // With a total weight of 3 and a threshold of 2, two rejections are final.
+    let voter = f.voters[0].insecure_clone();
+    f.ctx
+        .send(
+            &[instructions::proposal_reject(f.multisig, voter.pubkey(), index, None)],
+            &[&voter],
+        )
+        .await
+        .unwrap();
+    assert!(matches!(f.proposal(index).await.status, ProposalStatus::Rejected { .. }));
+}
+
+#[tokio::test]
+async fn proposal_cancel() {
+    let mut f = Fixture::new().await;
+    let payer = f.ctx.payer();
+    let voter = f.voters[0].insecure_clone();
+    let index = f.create_proposal(false).await;
+
+    // Only approved proposals can be cancelled.
+    let result = f
+        .ctx
+        .send(&[instructions::proposal_cancel(f.multisig, payer, index, None)], &[])
+        .await;
+    assert_error(result, ErrorCode::InvalidProposalStatus);
+
+    f.ctx
+        .send(
+            &[
+                instructions::proposal_approve(f.multisig, payer, index, None),
+                instructions::proposal_approve(f.multisig, voter.pubkey(), index, None),
+            ],
+            &[&voter],
+        )
+        .await
+        .unwrap();
+
+    f.ctx
+        .send(&[instructions::proposal_cancel(f.multisig, payer, index, None)], &[])
+        .await
+        .unwrap();
+    f.ctx
+        .send(&[instructions::proposal_cancel(f.multisig, payer, index, None)], &[])
+        .await
+        .unwrap();
+    assert_eq!(f.proposal(index).await.cancelled, vec![payer]);
+
+    f.ctx
+        .send(
+            &[instructions::proposal_cancel(f.multisig, voter.pubkey(), index, None)],
+            &[&voter],
+        )
+        .await
+        .unwrap();
+    let proposal = f.proposal(index).await;
+    assert_eq!(proposal.cancelled, vec![payer, voter.pubkey()]);
+    assert!(matches!(proposal.status, ProposalStatus::Cancelled { .. }));
+}
+
+#[tokio::test]
+async fn proposal_votes_are_weighted() {
+    let mut ctx = TestContext::with_program_config().await;
+    let payer = ctx.payer();
+    let voters = [Keypair::new(), Keypair::new()];
+    // A founder worth 3 votes and two advisors worth 1, with a threshold of 3.
+    let multisig = ctx
+        .create_multisig(
+            payer,
+            3,
+            vec![
+                member_with(
+                    payer,
+                    &[Permission::Initiate, Permission::Vote, Permission::Execute],
+                    3,
+                ),
+                member_with(voters[0].pubkey(), &[Permission::Vote], 1),
+                member_with(voters[1].pubkey(), &[Permission::Vote], 1),
+            ],
+        )
+        .await;
+    let mut f = Fixture {
+        ctx,
+        multisig,
+        voters,
+    };
+    let advisors = [f.voters[0].insecure_clone(), f.voters[1].insecure_clone()];
+
+    // The founder alone reaches the threshold.
+    let index = f.create_proposal(false).await;
+    f.ctx
+        .send(&[instructions::proposal_approve(multisig, payer, index, None)], &[])
+        .await
+        .unwrap();
+    assert!(matches!(f.proposal(index).await.status, ProposalStatus::Approved { .. }));
+
+    // Both advisors weigh less than the threshold, so their cancellation is not final.
+    for advisor in &advisors {
+        f.ctx
+            .send(
+                &[instructions::proposal_cancel(multisig, advisor.pubkey(), index, None)],
+                &[advisor],
+            )
+            .await
+            .unwrap();
+    }
+    assert!(matches!(f.proposal(index).await.status, ProposalStatus::Approved { .. }));
+    f.ctx
+        .send(&[instructions::proposal_cancel(multisig, payer, index, None)], &[])
+        .await
+        .unwrap();
+    assert!(matches!(f.proposal(index).await.status, ProposalStatus::Cancelled { .. }));
+
+    // With a total weight of 5 and a threshold of 3, a rejection needs a weight of 3.
+    let index = f.create_proposal(false).await;
+    for advisor in &advisors {
+        f.ctx
+            .send(
+                &[instructions::proposal_reject(multisig, advisor.pubkey(), index, None)],
+                &[advisor],
+            )
+            .await
+            .unwrap();
+    }
+    assert!(matches!(f.proposal(index).await.status, ProposalStatus::Active { .. }));
+    f.ctx
+        .send(&[instructions::proposal_reject(multisig, payer, index, None)], &[])
+        .await
+        .unwrap();
+    assert!(matches!(f.proposal(index).await.status, ProposalStatus::Rejected { .. }));
+}
+
+#[tokio::test]
+async fn proposal_status_records_the_time_of_each_change() {
+    let mut f = Fixture::new().await;
+    let payer = f.ctx.payer();
+    let voter = f.voters[0].insecure_clone();
+
+    let index = f.create_proposal(true).await;
+    let now = f.ctx.now().await;
+    assert_eq!(f.proposal(index).await.status, ProposalStatus::Draft { timestamp: now });
+
+    f.ctx.warp(60).await;
+    f.ctx
+        .send(&[instructions::proposal_activate(f.multisig, payer, index)], &[])
+        .await
+        .unwrap();
+    let now = f.ctx.now().await;
+    assert_eq!(f.proposal(index).await.status, ProposalStatus::Active { timestamp: now });
+
+    f.ctx.warp(60).await;
+    f.ctx
+        .send(
+            &[
+                instructions::proposal_approve(f.multisig, payer, index, None),
+                instructions::proposal_approve(f.multisig, voter.pubkey(), index, None),
+            ],
+            &[&voter],
+        )
+        .await
+        .unwrap();
+    let now = f.ctx.now().await;
+    assert_eq!(f.proposal(index).await.status, ProposalStatus::Approved { timestamp: now });
+
+    f.ctx.warp(60).await;
+    f.ctx
+        .send(
+            &[
+                instructions::proposal_cancel(f.multisig, payer, index, None),
+                instructions::proposal_cancel(f.multisig, voter.pubkey(), index, None),
+            ],
+            &[&voter],
+        )
+        .await
+        .unwrap();
+    let now = f.ctx.now().await;
+    assert_eq!(f.proposal(index).await.status, ProposalStatus::Cancelled { timestamp: now });
+
+    let index = f.create_proposal(false).await;
+    f.ctx.warp(60).await;
+    f.ctx
+        .send(
+            &[
+                instructions::proposal_reject(f.multisig, payer, index, None),
+                instructions::proposal_reject(f.multisig, voter.pubkey(), index, None),
+            ],
+            &[&voter],
+        )
+        .await
+        .unwrap();
+    let now = f.ctx.now().await;
+    assert_eq!(f.proposal(index).await.status, ProposalStatus::Rejected { timestamp: now });
+}
+
+#[tokio::test]
+async fn proposal_vote_requires_membership_and_permission() {
+    let mut f = Fixture::new().await;
+    let payer = f.ctx.payer();
+    let index = f.create_proposal(false).await;
+
+    let outsider = Keypair::new();
+    let result = f
+        .ctx
+        .send(
+            &[instructions::proposal_approve(f.multisig, outsider.pubkey(), index, None)],
+            &[&outsider],
+        )
+        .await;
+    assert_error(result, ErrorCode::NotAMember);
+
+    let initiator = Keypair::new();
+    f.ctx
+        .send(
+            &[instructions::multisig_add_member(
+                f.multisig,
+                payer,
+                payer,
+                member_with(initiator.pubkey(), &[Permission::Initiate], 1),
+            )],
+            &[],
+        )
+        .await
+        .unwrap();
+    let index = f.create_proposal(false).await;
+    let result = f
+        .ctx
+        .send(
+            &[instructions::proposal_approve(f.multisig, initiator.pubkey(), index, None)],
+            &[&initiator],
+        )
+        .await;
+    assert_error(result, ErrorCode::Unauthorized);
+}
+
+#[tokio::test]
+async fn proposal_reject_and_cancel_require_vote_permission() {
+    let mut f = Fixture::new().await;
+    let payer = f.ctx.payer();
+    let initiator = Keypair::new();
+    f.ctx
+        .send(
+            &[instructions::multisig_add_member(
+                f.multisig,
+                payer,
+                payer,
+                member_with(initiator.pubkey(), &[Permission::Initiate], 1),
+            )],
+            &[],
+        )
+        .await
+        .unwrap();
+
+    let index = f.create_proposal(false).await;
+    let result = f
+        .ctx
+        .send(
+            &[instructions::proposal_reject(f.multisig, initiator.pubkey(), index, None)],
+            &[&initiator],
+        )
+        .await;
+    assert_error(result, ErrorCode::Unauthorized);
+
+    let voter = f.voters[0].insecure_clone();
+    f.ctx
+        .send(
+            &[
+                instructions::proposal_approve(f.multisig, payer, index, None),
+                instructions::proposal_approve(f.multisig, voter.pubkey(), index, None),
+            ],
+            &[&voter],
+        )
+        .await
+        .unwrap();
+    let result = f
+        .ctx
+        .send(
+            &[instructions::proposal_cancel(f.multisig, initiator.pubkey(), index, None)],
+            &[&initiator],
+        )
+        .await;
+    assert_error(result, ErrorCode::Unauthorized);
+}
+
+#[tokio::test]
+async fn proposal_vote_requires_an_existing_proposal() {
+    let mut f = Fixture::new().await;
+    let payer = f.ctx.payer();
+
+    let result = f
+        .ctx
+        .send(&[instructions::proposal_approve(f.multisig, payer, 1, None)], &[])
+        .await;
+
+    assert_anchor_error(result, anchor_lang::error::ErrorCode::AccountNotInitialized);
+}
+
+#[tokio::test]
+async fn proposal_vote_rejects_long_memos() {
+    let mut f = Fixture::new().await;
+    let payer = f.ctx.payer();
+    let index = f.create_proposal(false).await;
+
+    let result = f
+        .ctx
+        .send(
+            &[instructions::proposal_approve(f.multisig, payer, index, Some("a".repeat(257)))],
+            &[],
+        )
+        .await;
+
+    assert_error(result, ErrorCode::MemoTooLong);
+}
+
+#[tokio::test]
+async fn proposal_expire() {
+    let mut f = Fixture::new().await;
+    let payer = f.ctx.payer();
+    f.ctx
+        .send(
+            &[instructions::multisig_set_voting_period(f.multisig, payer, Some(3600))],
+            &[],
+        )
+        .await
+        .unwrap();
+    let index = f.create_proposal(false).await;
+    assert!(f.proposal(index).await.expires_at.is_some());
+
+    let result = f
+        .ctx
+        .send(&[instructions::proposal_expire(f.multisig, payer, index)], &[])
+        .await;
+    assert_error(result, ErrorCode::ProposalNotExpired);
+
+    f.ctx.warp(3600).await;
+    let result = f
+        .ctx
+        .send(&[instructions::proposal_approve(f.multisig, payer, index, None)], &[])
+        .await;
+    assert_error(result, ErrorCode::ProposalExpired);
+
+    f.ctx
+        .send(&[instructions::proposal_expire(f.multisig, payer, index)], &[])
+        .await
+        .unwrap();
+    assert!(matches!(f.proposal(index).await.status, ProposalStatus::Expired { .. }));
+
+    let result = f
+        .ctx
+        .send(&[instructions::proposal_expire(f.multisig, payer, index)], &[])
+        .await;
+    assert_error(result, ErrorCode::InvalidProposalStatus);
+}
+
+#[tokio::test]
+async fn proposal_voting_period_starts_at_activation() {
+    let mut f = Fixture::new().await;
+    let payer = f.ctx.payer();
+    f.ctx
+        .send(
+            &[instructions::multisig_set_voting_period(f.multisig, payer, Some(3600))],
+            &[],
+        )
+        .await
+        .unwrap();
+
+    // A draft has no deadline, however long it waits.
+    let index = f.create_proposal(true).await;
+    assert_eq!(f.proposal(index).await.expires_at, None);
+    f.ctx.warp(7200).await;
+
+    f.ctx
+        .send(&[instructions::proposal_activate(f.multisig, payer, index)], &[])
+        .await
+        .unwrap();
+    let proposal = f.proposal(index).await;
+    let ProposalStatus::Active { timestamp } = proposal.status else {
+        panic!("the proposal is not active");
+    };
+    assert_eq!(proposal.expires_at, Some(timestamp + 3600));
+
+    // Rejections are closed by the deadline as well.
+    f.ctx.warp(3600).await;
+    let result = f
+        .ctx
+        .send(&[instructions::proposal_reject(f.multisig, payer, index, None)], &[])
+        .await;
+    assert_error(result, ErrorCode::ProposalExpired);
+}
+
+#[tokio::test]
+async fn proposal_expire_requires_an_active_proposal() {
+    let mut f = Fixture::new().await;
+    let payer = f.ctx.payer();
+    let voter = f.voters[0].insecure_clone();
+    f.ctx
+        .send(
+            &[instructions::multisig_set_voting_period(f.multisig, payer, Some(3600))],
+            &[],
+        )
+        .await
+        .unwrap();
+    let index = f.create_proposal(false).await;
+    f.ctx
+        .send(
+            &[
+                instructions::proposal_approve(f.multisig, payer, index, None),
+                instructions::proposal_approve(f.multisig, voter.pubkey(), index, None),
+            ],
+            &[&voter],
+        )
+        .await
+        .unwrap();
+
+    // An approved proposal stays approved past the deadline.
+    f.ctx.warp(3600).await;
+    let result = f
+        .ctx
+        .send(&[instructions::proposal_expire(f.multisig, payer, index)], &[])
+        .await;
+    assert_error(result, ErrorCode::InvalidProposalStatus);
+    assert!(matches!(f.proposal(index).await.status, ProposalStatus::Approved { .. }));
+}
@@ -0,0 +1,473 @@
+mod common;
+
+use anchor_lang::error::ErrorCode as AnchorErrorCode;
+use anchor_spl::token::TokenAccount;
+use collectivex_multisig::constants::{
+    MAX_SPENDING_LIMIT_DESTINATIONS, MAX_SPENDING_LIMIT_MEMBERS,
+};
+use collectivex_multisig::error::ErrorCode;
+use collectivex_multisig::state::{Multisig, Period, SpendingLimit};
+use collectivex_multisig_sdk::{instructions, pda};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+
+use crate::common::{assert_anchor_error, assert_error, member, TestContext};
+
+/// A controlled multisig with a funded vault and a spending limit of the payer.
+struct Fixture {
+    ctx: TestContext,
+    multisig: Pubkey,
+    spending_limit: Pubkey,
+    destination: Pubkey,
+}
+
+impl Fixture {
+    async fn new(mint: Pubkey, amount: u64, period: Period) -> Self {
+        Self::with_context(TestContext::with_program_config().await, mint, amount, period).await
+    }
+
+    async fn with_context(mut ctx: TestContext, mint: Pubkey, amount: u64, period: Period) -> Self {
+        let payer = ctx.payer();
+        let multisig = ctx.create_multisig(payer, 1, vec![member(payer)]).await;
+        let create_key = Keypair::new();
+        let destination = Pubkey::new_unique();
+
+        ctx.fund(pda::vault(&multisig, 0).0, 10_000_000).await;
+        ctx.send(
+            &[instructions::multisig_add_spending_limit(
+                multisig,
+                payer,
+                create_key.pubkey(),
+                0,
+                mint,
+                amount,
+                period,
+                vec![payer],
+                vec![destination],
+            )],
+            &[&create_key],
+        )
+        .await
+        .unwrap();
+
+        Self {
+            ctx,
+            multisig,
+            spending_limit: pda::spending_limit(&multisig, &create_key.pubkey()).0,
+            destination,
+        }
+    }
+
+    fn use_sol(&self, amount: u64) -> solana_sdk::instruction::Instruction {
+        instructions::spending_limit_use_sol(
+            self.multisig,
+            self.ctx.payer(),
+            self.spending_limit,
+            0,
+            self.destination,
+            amount,
+        )
+    }
+
+    async fn spending_limit(&mut self) -> SpendingLimit {
+        self.ctx.account(self.spending_limit).await
+    }
+}
+
+#[tokio::test]
+async fn multisig_add_spending_limit() {
+    let mut f = Fixture::new(Pubkey::default(), 1_000, Period::Day).await;
+    let payer = f.ctx.payer();
+
+    let spending_limit = f.spending_limit().await;
+    assert_eq!(spending_limit.multisig, f.multisig);
+    assert_eq!(spending_limit.vault_index, 0);
+    assert_eq!(spending_limit.mint, Pubkey::default());
+    assert_eq!(spending_limit.amount, 1_000);
+    assert_eq!(spending_limit.remaining_amount, 1_000);
+    assert_eq!(spending_limit.period, Period::Day);
+    assert_eq!(spending_limit.members, vec![payer]);
+    assert_eq!(spending_limit.destinations, vec![f.destination]);
+}
+
+#[tokio::test]
+async fn multisig_add_spending_limit_validates_inputs() {
+    let mut ctx = TestContext::with_program_config().await;
+    let payer = ctx.payer();
+    let multisig = ctx.create_multisig(payer, 1, vec![member(payer)]).await;
+
+    let create_key = Keypair::new();
+    let result = ctx
+        .send(
+            &[instructions::multisig_add_spending_limit(
+                multisig,
+                payer,
+                create_key.pubkey(),
+                0,
+                Pubkey::default(),
+                1_000,
+                Period::Custom { seconds: -1 },
+                vec![payer],
+                vec![],
+            )],
+            &[&create_key],
+        )
+        .await;
+    assert_error(result, ErrorCode::InvalidPeriod);
+}
+
+#[tokio::test]
+async fn multisig_add_spending_limit_sizes_the_account() {
+    let mut ctx = TestContext::with_program_config().await;
+    let payer = ctx.payer();
+    let multisig = ctx.create_multisig(payer, 1, vec![member(payer)]).await;
+    let create_key = Keypair::new();
+    let members: Vec<Pubkey> = (0..10).map(|_| Pubkey::new_unique()).collect();
+    let destinations: Vec<Pubkey> = (0..5).map(|_| Pubkey::new_unique()).collect();
+
+    ctx.send(
+        &[instructions::multisig_add_spending_limit(
+            multisig,
+            payer,
+            create_key.pubkey(),
+            0,
+            Pubkey::default(),
+            1_000,
+            Period::Day,
+            members.clone(),
+            destinations.clone(),
+        )],
+        &[&create_key],
+    )
+    .await
+    .unwrap();
+
+    let spending_limit = pda::spending_limit(&multisig, &create_key.pubkey()).0;
+    let account: SpendingLimit = ctx.account(spending_limit).await;
+    assert_eq!(account.members, members);
+    assert_eq!(account.destinations, destinations);
+    assert_eq!(ctx.data_len(spending_limit).await, SpendingLimit::size(10, 5));
+}
+
+#[tokio::test]
+async fn multisig_remove_spending_limit() {
+    let mut f = Fixture::new(Pubkey::default(), 1_000, Period::Day).await;
+    let payer = f.ctx.payer();
+    let rent_collector = Pubkey::new_unique();
+
+    let result = f
+        .ctx
+        .send(
+            &[instructions::multisig_remove_spending_limit(
+                f.multisig,
+                payer,
+                f.spending_limit,
+                rent_collector,
+                Some("a".repeat(257)),
+            )],
+            &[],
+        )
+        .await;
+    assert_error(result, ErrorCode::MemoTooLong);
+
+    f.ctx
+        .send(
+            &[instructions::multisig_remove_spending_limit(
+                f.multisig,
+                payer,
+                f.spending_limit,
+                rent_collector,
+                Some("no longer needed".into()),
+            )],
+            &[],
+        )
+        .await
+        .unwrap();
+
+    assert!(!f.ctx.exists(f.spending_limit).await);
+    assert!(f.ctx.lamports(rent_collector).await > 0);
+}
+
+#[tokio::test]
+async fn multisig_remove_spending_limit_of_another_multisig() {
+    let mut f = Fixture::new(Pubkey::default(), 1_000, Period::Day).await;
+    let payer = f.ctx.payer();
+    let other = f.ctx.create_multisig(payer, 1, vec![member(payer)]).await;
+
+    let result = f
+        .ctx
+        .send(
+            &[instructions::multisig_remove_spending_limit(
+                other,
+                payer,
+                f.spending_limit,
+                payer,
+                None,
+            )],
+            &[],
+        )
+        .await;
+
+    assert_error(result, ErrorCode::InvalidSpendingLimit);
+}
+
+#[tokio::test]
+async fn spending_limit_use_sol() {
+    let mut f = Fixture::new(Pubkey::default(), 5_000_000, Period::OneTime).await;
+
+    f.ctx.send(&[f.use_sol(3_000_000)], &[]).await.unwrap();
+    assert_eq!(f.ctx.lamports(f.destination).await, 3_000_000);
+    assert_eq!(f.spending_limit().await.remaining_amount, 2_000_000);
+
+    let result = f.ctx.send(&[f.use_sol(2_000_001)], &[]).await;
+    assert_error(result, ErrorCode::SpendingLimitExceeded);
+
+    let result = f.ctx.send(&[f.use_sol(0)], &[]).await;
+    assert_error(result, ErrorCode::InvalidAmount);
+}
+
+#[tokio::test]
+async fn spending_limit_use_draws_from_the_vault_of_the_limit() {
+    let mut f = Fixture::new(Pubkey::default(), 5_000_000, Period::OneTime).await;
+    let payer = f.ctx.payer();
+    let other_vault = pda::vault(&f.multisig, 1).0;
+    f.ctx.fund(other_vault, 10_000_000).await;
+
+    let result = f
+        .ctx
+        .send(
+            &[instructions::spending_limit_use_sol(
+                f.multisig,
+                payer,
+                f.spending_limit,
+                1,
+                f.destination,
+                1_000_000,
+            )],
+            &[],
+        )
+        .await;
+    assert_anchor_error(result, AnchorErrorCode::ConstraintSeeds);
+
+    // Spending needs no proposal, and leaves the transactions of the multisig untouched.
+    f.ctx.send(&[f.use_sol(1_000_000)], &[]).await.unwrap();
+    assert_eq!(f.ctx.lamports(pda::vault(&f.multisig, 0).0).await, 9_000_000);
+    assert_eq!(f.ctx.lamports(other_vault).await, 10_000_000);
+    assert_eq!(f.ctx.account::<Multisig>(f.multisig).await.transaction_index, 0);
+}
+
+#[tokio::test]
+async fn spending_limit_use_resets_each_period() {
+    let mut f = Fixture::new(Pubkey::default(), 1_000_000, Period::Day).await;
+
+    f.ctx.send(&[f.use_sol(1_000_000)], &[]).await.unwrap();
+    let result = f.ctx.send(&[f.use_sol(1)], &[]).await;
+    assert_error(result, ErrorCode::SpendingLimitExceeded);
+
+    f.ctx.warp(24 * 60 * 60).await;
+    f.ctx.send(&[f.use_sol(1)], &[]).await.unwrap();
+    assert_eq!(f.spending_limit().await.remaining_amount, 999_999);
+}
+
+#[tokio::test]
+async fn spending_limit_use_never_resets_one_time_limits() {
+    let mut f = Fixture::new(Pubkey::default(), 1_000_000, Period::OneTime).await;
+
+    f.ctx.send(&[f.use_sol(1_000_000)], &[]).await.unwrap();
+    f.ctx.warp(365 * 24 * 60 * 60).await;
+
+    let result = f.ctx.send(&[f.use_sol(1)], &[]).await;
+    assert_error(result, ErrorCode::SpendingLimitExceeded);
+}
+
+#[tokio::test]
+async fn spending_limit_use_resets_custom_periods() {
+    let mut f = Fixture::new(Pubkey::default(), 1_000_000, Period::Custom { seconds: 3600 }).await;
+    let created_at = f.spending_limit().await.last_reset;
+
+    f.ctx.send(&[f.use_sol(1_000_000)], &[]).await.unwrap();
+    f.ctx.warp(1800).await;
+    let result = f.ctx.send(&[f.use_sol(1)], &[]).await;
+    assert_error(result, ErrorCode::SpendingLimitExceeded);
+
+    // Resets start from the end of the last full period, not from the time of use.
+    f.ctx.warp(2 * 3600).await;
+    f.ctx.send(&[f.use_sol(1)], &[]).await.unwrap();
+    let spending_limit = f.spending_limit().await;
+    assert_eq!(spending_limit.remaining_amount, 999_999);
+    assert_eq!(spending_limit.last_reset, created_at + 2 * 3600);
+}
+
+#[tokio::test]
+async fn spending_limit_use_requires_a_listed_member_and_destination() {
+    let mut f = Fixture::new(Pubkey::default(), 1_000, Period::Day).await;
+    let payer = f.ctx.payer();
+
+    let outsider = Keypair::new();
+    let result = f
+        .ctx
+        .send(
+            &[instructions::spending_limit_use_sol(
+                f.multisig,
+                outsider.pubkey(),
+                f.spending_limit,
+                0,
+                f.destination,
+                1,
+            )],
+            &[&outsider],
+        )
+        .await;
+    assert_error(result, ErrorCode::Unauthorized);
+
+    let result = f
+        .ctx
+        .send(
+            &[instructions::spending_limit_use_sol(
+                f.multisig,
+                payer,
+                f.spending_limit,
+                0,
+                Pubkey::new_unique(),
+                1,
+            )],
+            &[],
+        )
+        .await;
+    assert_error(result, ErrorCode::InvalidDestination);
+}
+
+/// A spending limit of `mint` with a vault token account holding 1_000 tokens, and
+/// a token account of the destination. Returns the fixture and both token accounts.
+async fn token_fixture(mint_of_limit: Option<Pubkey>) -> (Fixture, Pubkey, Pubkey, Pubkey) {
+    let mut ctx = TestContext::with_program_config().await;
+    let mint = ctx.create_mint().await;
+    let mut f =
+        Fixture::with_context(ctx, mint_of_limit.unwrap_or(mint), 500, Period::Day).await;
+    let vault = pda::vault(&f.multisig, 0).0;
+    let vault_token_account = f.ctx.create_token_account(mint, vault, 1_000).await;
+    let destination_token_account = f.ctx.create_token_account(mint, f.destination, 0).await;
+
+    (f, mint, vault_token_account, destination_token_account)
+}
+
+#[tokio::test]
+async fn spending_limit_use_token() {
+    let (mut f, mint, vault_token_account, destination_token_account) = token_fixture(None).await;
+    let payer = f.ctx.payer();
+
+    f.ctx
+        .send(
+            &[instructions::spending_limit_use_token(
+                f.multisig,
+                payer,
+                f.spending_limit,
+                0,
+                mint,
+                f.destination,
+                vault_token_account,
+                destination_token_account,
+                300,
+            )],
+            &[],
+        )
+        .await
+        .unwrap();
+
+    let destination: TokenAccount = f.ctx.account(destination_token_account).await;
+    assert_eq!(destination.amount, 300);
+    let vault: TokenAccount = f.ctx.account(vault_token_account).await;
+    assert_eq!(vault.amount, 700);
+    assert_eq!(f.spending_limit().await.remaining_amount, 200);
+
+    let result = f
+        .ctx
+        .send(
+            &[instructions::spending_limit_use_token(
+                f.multisig,
+                payer,
+                f.spending_limit,
+                0,
+                mint,
+                f.destination,
+                vault_token_account,
+                destination_token_account,
+                201,
+            )],
+            &[],
+        )
+        .await;
+    assert_error(result, ErrorCode::SpendingLimitExceeded);
+}
+
+#[tokio::test]
+async fn spending_limit_use_token_requires_the_limit_mint() {
+    let (mut f, mint, vault_token_account, destination_token_account) =
+        token_fixture(Some(Pubkey::new_unique())).await;
+    let payer = f.ctx.payer();
+
+    let result = f
+        .ctx
+        .send(
+            &[instructions::spending_limit_use_token(
+                f.multisig,
+                payer,
+                f.spending_limit,
+                0,
+                mint,
+                f.destination,
+                vault_token_account,
+                destination_token_account,
+                1,
+            )],
+            &[],
+        )
+        .await;
+
+    assert_error(result, ErrorCode::InvalidMint);
+}
+
+#[tokio::test]
+async fn multisig_add_spending_limit_caps_the_members_and_destinations() {
+    let mut ctx = TestContext::with_program_config().await;
+    let payer = ctx.payer();
+    let multisig = ctx.create_multisig(payer, 1, vec![member(payer)]).await;
+    let keys = |count: usize| (0..count).map(|_| Pubkey::new_unique()).collect::<Vec<_>>();
+
+    for (members, destinations, error) in [
+        (MAX_SPENDING_LIMIT_MEMBERS, MAX_SPENDING_LIMIT_DESTINATIONS, None),
+        (
+            MAX_SPENDING_LIMIT_MEMBERS + 1,
+            MAX_SPENDING_LIMIT_DESTINATIONS,
+            Some(ErrorCode::ExceedsMaxMembers),
+        ),
+        (
+            MAX_SPENDING_LIMIT_MEMBERS,
+            MAX_SPENDING_LIMIT_DESTINATIONS + 1,
+            Some(ErrorCode::ExceedsMaxDestinations),
+        ),
+    ] {
+        let create_key = Keypair::new();
+        let result = ctx
+            .send(
+                &[instructions::multisig_add_spending_limit(
+                    multisig,
+                    payer,
+                    create_key.pubkey(),
+                    0,
+                    Pubkey::default(),
+                    1_000,
+                    Period::Day,
+                    keys(members),
+                    keys(destinations),
+                )],
+                &[&create_key],
+            )
+            .await;
+
+        match error {
+            None => result.unwrap(),
+            Some(error) => assert_error(result, error),
+        }
+    }
+}
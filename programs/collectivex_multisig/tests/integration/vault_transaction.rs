@@ -0,0 +1,438 @@
+mod common;
+
+use collectivex_multisig::error::ErrorCode;
+use collectivex_multisig::state::{
+    Multisig, Permission, Proposal, ProposalStatus, VaultTransaction, VaultTransactionMessage,
+};
+use collectivex_multisig_sdk::{instructions, pda};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+
+use crate::common::{assert_error, member, member_with, transfer_message, TestContext};
+
+#[tokio::test]
+async fn vault_transaction_create() {
+    let mut ctx = TestContext::with_program_config().await;
+    let payer = ctx.payer();
+    let multisig = ctx.create_multisig(payer, 1, vec![member(payer)]).await;
+    let vault = pda::vault(&multisig, 0).0;
+    let message = transfer_message(vault, payer, 1);
+
+    ctx.send(
+        &[instructions::vault_transaction_create(multisig, payer, payer, 1, 0, message.clone())],
+        &[],
+    )
+    .await
+    .unwrap();
+
+    let transaction: VaultTransaction = ctx.account(pda::transaction(&multisig, 1).0).await;
+    assert_eq!(transaction.creator, payer);
+    assert_eq!(transaction.index, 1);
+    assert_eq!(transaction.vault_index, 0);
+    assert!(transaction.message == message);
+    assert_eq!(ctx.account::<Multisig>(multisig).await.transaction_index, 1);
+}
+
+#[tokio::test]
+async fn vault_transaction_create_requires_initiate_permission() {
+    let mut ctx = TestContext::with_program_config().await;
+    let payer = ctx.payer();
+    let voter = Keypair::new();
+    let multisig = ctx
+        .create_multisig(
+            payer,
+            1,
+            vec![member(payer), member_with(voter.pubkey(), &[Permission::Vote], 1)],
+        )
+        .await;
+    let vault = pda::vault(&multisig, 0).0;
+
+    let result = ctx
+        .send(
+            &[instructions::vault_transaction_create(
+                multisig,
+                voter.pubkey(),
+                payer,
+                1,
+                0,
+                transfer_message(vault, payer, 1),
+            )],
+            &[&voter],
+        )
+        .await;
+    assert_error(result, ErrorCode::Unauthorized);
+
+    let outsider = Keypair::new();
+    let result = ctx
+        .send(
+            &[instructions::vault_transaction_create(
+                multisig,
+                outsider.pubkey(),
+                payer,
+                1,
+                0,
+                transfer_message(vault, payer, 1),
+            )],
+            &[&outsider],
+        )
+        .await;
+    assert_error(result, ErrorCode::NotAMember);
+}
+
+#[tokio::test]
+async fn vault_transaction_create_rejects_empty_message() {
+    let mut ctx = TestContext::with_program_config().await;
+    let payer = ctx.payer();
+    let multisig = ctx.create_multisig(payer, 1, vec![member(payer)]).await;
+
+    let result = ctx
+        .send(
+            &[instructions::vault_transaction_create(
+                multisig,
+                payer,
+                payer,
+                1,
+                0,
+                VaultTransactionMessage { instructions: vec![] },
+            )],
+            &[],
+        )
+        .await;
+
+    assert_error(result, ErrorCode::InvalidTransactionMessage);
+}
+
+#[tokio::test]
+async fn vault_transaction_create_rejects_signers_other_than_the_vault() {
+    let mut ctx = TestContext::with_program_config().await;
+    let payer = ctx.payer();
+    let initiator = Keypair::new();
+    let multisig = ctx
+        .create_multisig(
+            payer,
+            1,
+            vec![member(payer), member_with(initiator.pubkey(), &[Permission::Initiate], 1)],
+        )
+        .await;
+
+    // The executor signs the outer transaction, but must not sign for the message.
+    let result = ctx
+        .send(
+            &[instructions::vault_transaction_create(
+                multisig,
+                initiator.pubkey(),
+                payer,
+                1,
+                0,
+                transfer_message(payer, initiator.pubkey(), 1_000_000),
+            )],
+            &[&initiator],
+        )
+        .await;
+    assert_error(result, ErrorCode::InvalidTransactionMessage);
+
+    // Nor may another vault of the multisig.
+    let result = ctx
+        .send(
+            &[instructions::vault_transaction_create(
+                multisig,
+                initiator.pubkey(),
+                payer,
+                1,
+                0,
+                transfer_message(pda::vault(&multisig, 1).0, initiator.pubkey(), 1_000_000),
+            )],
+            &[&initiator],
+        )
+        .await;
+    assert_error(result, ErrorCode::InvalidTransactionMessage);
+}
+
+#[tokio::test]
+async fn vault_transaction_execute() {
+    let mut ctx = TestContext::with_program_config().await;
+    let payer = ctx.payer();
+    let multisig = ctx.create_multisig(payer, 1, vec![member(payer)]).await;
+    let vault = pda::vault(&multisig, 0).0;
+    let destination = Pubkey::new_unique();
+    ctx.fund(vault, 10_000_000).await;
+    let message = transfer_message(vault, destination, 1_000_000);
+    let index = ctx
+        .create_approved(multisig, |index| {
+            instructions::vault_transaction_create(
+                multisig,
+                payer,
+                payer,
+                index,
+                0,
+                message.clone(),
+            )
+        })
+        .await;
+
+    ctx.send(
+        &[instructions::vault_transaction_execute(multisig, payer, index, 0, &message)],
+        &[],
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(ctx.lamports(destination).await, 1_000_000);
+    assert_eq!(ctx.lamports(vault).await, 9_000_000);
+    let proposal: Proposal = ctx.account(pda::proposal(&multisig, index).0).await;
+    assert!(matches!(proposal.status, ProposalStatus::Executed { .. }));
+
+    // An executed proposal cannot be executed again.
+    let result = ctx
+        .send(
+            &[instructions::vault_transaction_execute(multisig, payer, index, 0, &message)],
+            &[],
+        )
+        .await;
+    assert_error(result, ErrorCode::InvalidProposalStatus);
+}
+
+#[tokio::test]
+async fn vault_transaction_execute_signs_with_the_vault_of_its_index() {
+    let mut ctx = TestContext::with_program_config().await;
+    let payer = ctx.payer();
+    let multisig = ctx.create_multisig(payer, 1, vec![member(payer)]).await;
+    let vaults = [pda::vault(&multisig, 0).0, pda::vault(&multisig, 1).0];
+    let destination = Pubkey::new_unique();
+    for vault in vaults {
+        ctx.fund(vault, 10_000_000).await;
+    }
+
+    let message = transfer_message(vaults[1], destination, 1_000_000);
+    ctx.send(
+        &[
+            instructions::vault_transaction_create(multisig, payer, payer, 1, 1, message.clone()),
+            instructions::proposal_create(multisig, payer, payer, 1, false),
+            instructions::proposal_approve(multisig, payer, 1, None),
+            instructions::vault_transaction_execute(multisig, payer, 1, 1, &message),
+        ],
+        &[],
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(ctx.account::<VaultTransaction>(pda::transaction(&multisig, 1).0).await.vault_index, 1);
+    assert_eq!(ctx.lamports(vaults[0]).await, 10_000_000);
+    assert_eq!(ctx.lamports(vaults[1]).await, 9_000_000);
+    assert_eq!(ctx.lamports(destination).await, 1_000_000);
+}
+
+#[tokio::test]
+async fn vault_transaction_execute_requires_membership() {
+    let mut ctx = TestContext::with_program_config().await;
+    let payer = ctx.payer();
+    let multisig = ctx.create_multisig(payer, 1, vec![member(payer)]).await;
+    let vault = pda::vault(&multisig, 0).0;
+    let message = transfer_message(vault, payer, 1);
+    let index = ctx
+        .create_approved(multisig, |index| {
+            instructions::vault_transaction_create(
+                multisig,
+                payer,
+                payer,
+                index,
+                0,
+                message.clone(),
+            )
+        })
+        .await;
+
+    let outsider = Keypair::new();
+    let result = ctx
+        .send(
+            &[instructions::vault_transaction_execute(multisig, outsider.pubkey(), index, 0, &message)],
+            &[&outsider],
+        )
+        .await;
+
+    assert_error(result, ErrorCode::NotAMember);
+}
+
+#[tokio::test]
+async fn vault_transaction_execute_requires_approval() {
+    let mut ctx = TestContext::with_program_config().await;
+    let payer = ctx.payer();
+    let multisig = ctx.create_multisig(payer, 1, vec![member(payer)]).await;
+    let vault = pda::vault(&multisig, 0).0;
+    let message = transfer_message(vault, payer, 1);
+
+    ctx.send(
+        &[
+            instructions::vault_transaction_create(multisig, payer, payer, 1, 0, message.clone()),
+            instructions::proposal_create(multisig, payer, payer, 1, false),
+        ],
+        &[],
+    )
+    .await
+    .unwrap();
+
+    let result = ctx
+        .send(
+            &[instructions::vault_transaction_execute(multisig, payer, 1, 0, &message)],
+            &[],
+        )
+        .await;
+
+    assert_error(result, ErrorCode::InvalidProposalStatus);
+}
+
+#[tokio::test]
+async fn vault_transaction_execute_respects_the_time_lock() {
+    let mut ctx = TestContext::with_program_config().await;
+    let payer = ctx.payer();
+    let multisig = ctx.create_multisig(payer, 1, vec![member(payer)]).await;
+    ctx.send(&[instructions::multisig_set_time_lock(multisig, payer, 3600)], &[])
+        .await
+        .unwrap();
+    let vault = pda::vault(&multisig, 0).0;
+    let destination = Pubkey::new_unique();
+    ctx.fund(vault, 10_000_000).await;
+    let message = transfer_message(vault, destination, 1_000_000);
+    let index = ctx
+        .create_approved(multisig, |index| {
+            instructions::vault_transaction_create(
+                multisig,
+                payer,
+                payer,
+                index,
+                0,
+                message.clone(),
+            )
+        })
+        .await;
+
+    let result = ctx
+        .send(
+            &[instructions::vault_transaction_execute(multisig, payer, index, 0, &message)],
+            &[],
+        )
+        .await;
+    assert_error(result, ErrorCode::TimeLockNotReleased);
+
+    ctx.warp(3600).await;
+    ctx.send(
+        &[instructions::vault_transaction_execute(multisig, payer, index, 0, &message)],
+        &[],
+    )
+    .await
+    .unwrap();
+    assert_eq!(ctx.lamports(destination).await, 1_000_000);
+}
+
+#[tokio::test]
+async fn vault_transaction_execute_measures_the_time_lock_from_approval() {
+    let mut ctx = TestContext::with_program_config().await;
+    let payer = ctx.payer();
+    let multisig = ctx.create_multisig(payer, 1, vec![member(payer)]).await;
+    ctx.send(&[instructions::multisig_set_time_lock(multisig, payer, 3600)], &[])
+        .await
+        .unwrap();
+    let vault = pda::vault(&multisig, 0).0;
+    ctx.fund(vault, 10_000_000).await;
+    let message = transfer_message(vault, Pubkey::new_unique(), 1_000_000);
+
+    // The time the proposal spends waiting for votes does not count.
+    ctx.send(
+        &[
+            instructions::vault_transaction_create(multisig, payer, payer, 1, 0, message.clone()),
+            instructions::proposal_create(multisig, payer, payer, 1, false),
+        ],
+        &[],
+    )
+    .await
+    .unwrap();
+    ctx.warp(3600).await;
+    ctx.send(&[instructions::proposal_approve(multisig, payer, 1, None)], &[])
+        .await
+        .unwrap();
+
+    let result = ctx
+        .send(
+            &[instructions::vault_transaction_execute(multisig, payer, 1, 0, &message)],
+            &[],
+        )
+        .await;
+    assert_error(result, ErrorCode::TimeLockNotReleased);
+
+    ctx.warp(3600).await;
+    ctx.send(
+        &[instructions::vault_transaction_execute(multisig, payer, 1, 0, &message)],
+        &[],
+    )
+    .await
+    .unwrap();
+    let now = ctx.now().await;
+    let proposal: Proposal = ctx.account(pda::proposal(&multisig, 1).0).await;
+    assert_eq!(proposal.status, ProposalStatus::Executed { timestamp: now });
+}
+
+#[tokio::test]
+async fn vault_transaction_execute_requires_execute_permission() {
+    let mut ctx = TestContext::with_program_config().await;
+    let payer = ctx.payer();
+    let voter = Keypair::new();
+    let multisig = ctx
+        .create_multisig(
+            payer,
+            1,
+            vec![member(payer), member_with(voter.pubkey(), &[Permission::Vote], 1)],
+        )
+        .await;
+    let vault = pda::vault(&multisig, 0).0;
+    let message = transfer_message(vault, payer, 1);
+    let index = ctx
+        .create_approved(multisig, |index| {
+            instructions::vault_transaction_create(
+                multisig,
+                payer,
+                payer,
+                index,
+                0,
+                message.clone(),
+            )
+        })
+        .await;
+
+    let result = ctx
+        .send(
+            &[instructions::vault_transaction_execute(multisig, voter.pubkey(), index, 0, &message)],
+            &[&voter],
+        )
+        .await;
+
+    assert_error(result, ErrorCode::Unauthorized);
+}
+
+#[tokio::test]
+async fn vault_transaction_execute_requires_every_account() {
+    let mut ctx = TestContext::with_program_config().await;
+    let payer = ctx.payer();
+    let multisig = ctx.create_multisig(payer, 1, vec![member(payer)]).await;
+    let vault = pda::vault(&multisig, 0).0;
+    let destination = Pubkey::new_unique();
+    ctx.fund(vault, 10_000_000).await;
+    let message = transfer_message(vault, destination, 1_000_000);
+    let index = ctx
+        .create_approved(multisig, |index| {
+            instructions::vault_transaction_create(
+                multisig,
+                payer,
+                payer,
+                index,
+                0,
+                message.clone(),
+            )
+        })
+        .await;
+
+    let mut ix = instructions::vault_transaction_execute(multisig, payer, index, 0, &message);
+    ix.accounts.retain(|meta| meta.pubkey != destination);
+    let result = ctx.send(&[ix], &[]).await;
+
+    assert_error(result, ErrorCode::InvalidAccount);
+}
@@ -0,0 +1,288 @@
+//! Property tests of the proposal voting state machine.
+//!
+//! Random multisigs are voted on by random sequences of approvals, rejections
+//! and cancellations, gated the way `ProposalVote::validate` gates them.
+
+use anchor_lang::prelude::Pubkey;
+use collectivex_multisig::constants::MAX_MEMBERS;
+use collectivex_multisig::state::{
+    Member, Multisig, Permission, Permissions, Proposal, ProposalStatus,
+};
+use proptest::prelude::*;
+
+#[derive(Clone, Copy, Debug)]
+enum Vote {
+    Approve,
+    Reject,
+    Cancel,
+}
+
+fn vote() -> impl Strategy<Value = Vote> {
+    prop_oneof![Just(Vote::Approve), Just(Vote::Reject), Just(Vote::Cancel)]
+}
+
+/// Up to `MAX_MEMBERS` members with random permissions and weights, at least one
+/// of which can vote, and a threshold reachable by the voters.
+fn multisig() -> impl Strategy<Value = Multisig> {
+    prop::collection::vec((0..8u8, 1..=5u16), 1..=MAX_MEMBERS)
+        .prop_map(|members| {
+            members
+                .into_iter()
+                .enumerate()
+                .map(|(i, (mask, weight))| Member {
+                    key: Pubkey::new_from_array([i as u8 + 1; 32]),
+                    // The first member always votes, so the threshold is reachable.
+                    permissions: Permissions {
+                        mask: if i == 0 { mask | Permission::Vote as u8 } else { mask },
+                    },
+                    weight,
+                })
+                .collect::<Vec<_>>()
+        })
+        .prop_flat_map(|members| {
+            let multisig = Multisig {
+                version: Multisig::VERSION,
+                create_key: Pubkey::default(),
+                config_authority: Pubkey::default(),
+                threshold: 1,
+                members,
+                time_lock: 0,
+                transaction_index: 1,
+                stale_transaction_index: 0,
+                rent_collector: None,
+                voting_period: None,
+                pending_config_authority: None,
+                reserved: [0; 31],
+            };
+            let voting_weight = multisig.voting_weight() as u16;
+
+            (Just(multisig), 1..=voting_weight)
+        })
+        .prop_map(|(mut multisig, threshold)| {
+            multisig.threshold = threshold;
+            multisig
+        })
+}
+
+/// Random votes, as indexes into the members and vote kinds.
+fn votes() -> impl Strategy<Value = Vec<(prop::sample::Index, Vote)>> {
+    prop::collection::vec((any::<prop::sample::Index>(), vote()), 0..100)
+}
+
+fn active_proposal() -> Proposal {
+    Proposal {
+        version: Proposal::VERSION,
+        multisig: Pubkey::default(),
+        transaction_index: 1,
+        status: ProposalStatus::Active { timestamp: 0 },
+        bump: 0,
+        approved: vec![],
+        rejected: vec![],
+        cancelled: vec![],
+        expires_at: None,
+        reserved: [0; 64],
+    }
+}
+
+/// Whether `ProposalVote::validate` lets `member` cast `vote` on `proposal`.
+fn is_allowed(multisig: &Multisig, proposal: &Proposal, member: Pubkey, vote: Vote) -> bool {
+    let status_allowed = match vote {
+        Vote::Approve | Vote::Reject => matches!(proposal.status, ProposalStatus::Active { .. }),
+        Vote::Cancel => matches!(proposal.status, ProposalStatus::Approved { .. }),
+    };
+
+    status_allowed && multisig.has_permission(member, Permission::Vote)
+}
+
+fn cast(
+    proposal: &mut Proposal,
+    multisig: &Multisig,
+    member: Pubkey,
+    vote: Vote,
+    now: i64,
+) -> anchor_lang::Result<()> {
+    match vote {
+        Vote::Approve => proposal.approve(member, multisig, now),
+        Vote::Reject => proposal.reject(member, multisig, now),
+        Vote::Cancel => proposal.cancel(member, multisig, now),
+    }
+}
+
+fn vote_lists(proposal: &Proposal) -> [Vec<Pubkey>; 3] {
+    [
+        proposal.approved.clone(),
+        proposal.rejected.clone(),
+        proposal.cancelled.clone(),
+    ]
+}
+
+/// Whether `to` can follow `from`: `Active` leads to `Approved` or `Rejected`,
+/// `Approved` leads to `Cancelled`, and `Rejected` and `Cancelled` are final.
+fn is_valid_transition(from: &ProposalStatus, to: &ProposalStatus) -> bool {
+    use ProposalStatus::*;
+
+    matches!(
+        (from, to),
+        (Active { .. }, Active { .. } | Approved { .. } | Rejected { .. })
+            | (Approved { .. }, Approved { .. } | Cancelled { .. })
+            | (Rejected { .. }, Rejected { .. })
+            | (Cancelled { .. }, Cancelled { .. })
+    )
+}
+
+fn assert_invariants(multisig: &Multisig, proposal: &Proposal) {
+    let lists = [&proposal.approved, &proposal.rejected, &proposal.cancelled];
+
+    // No member is listed twice, whether in the same list or in two lists.
+    let mut voters: Vec<Pubkey> = lists.iter().flat_map(|list| list.iter().copied()).collect();
+    let count = voters.len();
+    voters.sort();
+    voters.dedup();
+    assert_eq!(voters.len(), count, "a member is in two vote lists: {lists:?}");
+
+    let threshold = u32::from(multisig.threshold);
+    match proposal.status {
+        // Reaching the threshold or the cutoff ends the voting.
+        ProposalStatus::Active { .. } => {
+            assert!(multisig.vote_weight(&proposal.approved) < threshold);
+            assert!(multisig.vote_weight(&proposal.rejected) < multisig.cutoff());
+            assert!(proposal.cancelled.is_empty());
+        }
+        ProposalStatus::Approved { .. } => {
+            assert!(multisig.vote_weight(&proposal.cancelled) < threshold);
+        }
+        ProposalStatus::Rejected { .. } => {
+            assert!(multisig.vote_weight(&proposal.rejected) >= multisig.cutoff());
+        }
+        ProposalStatus::Cancelled { .. } => {
+            assert!(multisig.vote_weight(&proposal.cancelled) >= threshold);
+        }
+        ref status => panic!("voting cannot lead to {status:?}"),
+    }
+}
+
+proptest! {
+    #[test]
+    fn voting_preserves_invariants(multisig in multisig(), votes in votes()) {
+        let mut proposal = active_proposal();
+
+        for (now, (index, vote)) in (1..).zip(votes) {
+            let member = index.get(&multisig.members).key;
+            if !is_allowed(&multisig, &proposal, member, vote) {
+                continue;
+            }
+
+            let before = proposal.status.clone();
+            let lists = vote_lists(&proposal);
+            let already_voted = match vote {
+                Vote::Approve => proposal.approved.contains(&member),
+                Vote::Reject => proposal.rejected.contains(&member),
+                Vote::Cancel => proposal.cancelled.contains(&member),
+            };
+
+            cast(&mut proposal, &multisig, member, vote, now).unwrap();
+            if already_voted {
+                // Repeating a vote changes nothing.
+                prop_assert_eq!(&proposal.status, &before);
+                prop_assert_eq!(vote_lists(&proposal), lists);
+            }
+
+            prop_assert!(
+                is_valid_transition(&before, &proposal.status),
+                "{:?} cannot lead to {:?}", before, proposal.status
+            );
+            if proposal.status != before {
+                // The status records when the vote changing it was cast.
+                let timestamp = match proposal.status {
+                    ProposalStatus::Approved { timestamp }
+                    | ProposalStatus::Rejected { timestamp }
+                    | ProposalStatus::Cancelled { timestamp } => timestamp,
+                    _ => unreachable!(),
+                };
+                prop_assert_eq!(timestamp, now);
+            }
+            assert_invariants(&multisig, &proposal);
+        }
+    }
+
+    #[test]
+    fn approvals_reaching_the_threshold_approve(multisig in multisig()) {
+        let mut proposal = active_proposal();
+        let voters: Vec<&Member> = multisig
+            .members
+            .iter()
+            .filter(|member| member.permissions.has(Permission::Vote))
+            .collect();
+
+        let mut weight = 0;
+        for member in voters {
+            proposal.approve(member.key, &multisig, 1).unwrap();
+            weight += member.weight;
+
+            let approved = matches!(proposal.status, ProposalStatus::Approved { .. });
+            prop_assert_eq!(approved, weight >= multisig.threshold);
+            if approved {
+                break;
+            }
+        }
+        let approved = matches!(proposal.status, ProposalStatus::Approved { .. });
+        prop_assert!(approved);
+    }
+
+    #[test]
+    fn rejections_reaching_the_cutoff_reject(multisig in multisig()) {
+        let mut proposal = active_proposal();
+        let voters: Vec<&Member> = multisig
+            .members
+            .iter()
+            .filter(|member| member.permissions.has(Permission::Vote))
+            .collect();
+
+        let mut weight = 0;
+        for member in voters {
+            proposal.reject(member.key, &multisig, 1).unwrap();
+            weight += u32::from(member.weight);
+
+            let rejected = matches!(proposal.status, ProposalStatus::Rejected { .. });
+            prop_assert_eq!(rejected, weight >= multisig.cutoff());
+            if rejected {
+                break;
+            }
+        }
+        // Once every voter rejected, the threshold can no longer be reached.
+        let rejected = matches!(proposal.status, ProposalStatus::Rejected { .. });
+        prop_assert!(rejected);
+    }
+}
+
+/// When the threshold equals the voting weight, a single rejection is final.
+#[test]
+fn unanimous_threshold_rejects_on_first_rejection() {
+    let members: Vec<Member> = (1..=3)
+        .map(|i| Member {
+            key: Pubkey::new_from_array([i; 32]),
+            permissions: Permissions::all(),
+            weight: 1,
+        })
+        .collect();
+    let multisig = Multisig {
+        version: Multisig::VERSION,
+        create_key: Pubkey::default(),
+        config_authority: Pubkey::default(),
+        threshold: 3,
+        members: members.clone(),
+        time_lock: 0,
+        transaction_index: 1,
+        stale_transaction_index: 0,
+        rent_collector: None,
+        voting_period: None,
+        pending_config_authority: None,
+        reserved: [0; 31],
+    };
+    let mut proposal = active_proposal();
+
+    proposal.approve(members[0].key, &multisig, 1).unwrap();
+    proposal.reject(members[1].key, &multisig, 2).unwrap();
+
+    assert_eq!(proposal.status, ProposalStatus::Rejected { timestamp: 2 });
+}
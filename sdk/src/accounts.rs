@@ -0,0 +1,66 @@
+//! Fetching and deserialization of the program accounts.
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::AccountDeserialize;
+use collectivex_multisig::state::{
+    ConfigTransaction, FeeWaiver, Multisig, ProgramConfig, Proposal, SpendingLimit,
+    VaultTransaction,
+};
+use solana_rpc_client::rpc_client::RpcClient;
+
+use crate::error::ClientResult;
+use crate::pda;
+
+/// Deserializes the data of a program account, checking its discriminator.
+pub fn deserialize<T: AccountDeserialize>(data: &[u8]) -> ClientResult<T> {
+    Ok(T::try_deserialize(&mut &data[..])?)
+}
+
+/// Fetches and deserializes the program account at `address`.
+pub fn fetch<T: AccountDeserialize>(client: &RpcClient, address: &Pubkey) -> ClientResult<T> {
+    let data = client.get_account_data(address)?;
+    deserialize(&data)
+}
+
+pub fn fetch_program_config(client: &RpcClient) -> ClientResult<ProgramConfig> {
+    fetch(client, &pda::program_config().0)
+}
+
+pub fn fetch_fee_waiver(client: &RpcClient, creator: &Pubkey) -> ClientResult<FeeWaiver> {
+    fetch(client, &pda::fee_waiver(creator).0)
+}
+
+pub fn fetch_multisig(client: &RpcClient, multisig: &Pubkey) -> ClientResult<Multisig> {
+    fetch(client, multisig)
+}
+
+pub fn fetch_spending_limit(
+    client: &RpcClient,
+    spending_limit: &Pubkey,
+) -> ClientResult<SpendingLimit> {
+    fetch(client, spending_limit)
+}
+
+pub fn fetch_config_transaction(
+    client: &RpcClient,
+    multisig: &Pubkey,
+    transaction_index: u64,
+) -> ClientResult<ConfigTransaction> {
+    fetch(client, &pda::transaction(multisig, transaction_index).0)
+}
+
+pub fn fetch_vault_transaction(
+    client: &RpcClient,
+    multisig: &Pubkey,
+    transaction_index: u64,
+) -> ClientResult<VaultTransaction> {
+    fetch(client, &pda::transaction(multisig, transaction_index).0)
+}
+
+pub fn fetch_proposal(
+    client: &RpcClient,
+    multisig: &Pubkey,
+    transaction_index: u64,
+) -> ClientResult<Proposal> {
+    fetch(client, &pda::proposal(multisig, transaction_index).0)
+}
@@ -0,0 +1,17 @@
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum ClientError {
+    #[error("RPC request failed: {0}")]
+    Rpc(Box<solana_rpc_client_api::client_error::Error>),
+    #[error("Failed to deserialize account: {0}")]
+    Deserialize(#[from] anchor_lang::error::Error),
+}
+
+impl From<solana_rpc_client_api::client_error::Error> for ClientError {
+    fn from(err: solana_rpc_client_api::client_error::Error) -> Self {
+        ClientError::Rpc(Box::new(err))
+    }
+}
+
+pub type ClientResult<T> = Result<T, ClientError>;
@@ -0,0 +1,782 @@
+//! Instruction builders for every entry point of the program.
+//!
+//! Program accounts are derived from their seeds, so callers only provide
+//! the signers, the instruction arguments and the accounts chosen by the user.
+
+use anchor_lang::prelude::{AccountMeta, Pubkey};
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::{system_program, InstructionData, ToAccountMetas};
+use collectivex_multisig::state::{
+    ConfigAction, Member, PausedCategories, Period, Permissions, VaultTransactionMessage,
+};
+use collectivex_multisig::{accounts, instruction, ID};
+
+use crate::pda;
+
+fn build(accounts: impl ToAccountMetas, data: impl InstructionData) -> Instruction {
+    Instruction {
+        program_id: ID,
+        accounts: accounts.to_account_metas(None),
+        data: data.data(),
+    }
+}
+
+/// `initializer` must be the upgrade authority of the program.
+pub fn program_config_init(
+    initializer: Pubkey,
+    authority: Pubkey,
+    creation_fee: u64,
+    treasury: Pubkey,
+) -> Instruction {
+    build(
+        accounts::ProgramConfigInit {
+            program_config: pda::program_config().0,
+            initializer,
+            program_data: pda::program_data().0,
+            system_program: system_program::ID,
+        },
+        instruction::ProgramConfigInit {
+            authority,
+            creation_fee,
+            treasury,
+        },
+    )
+}
+
+fn program_config_edit(current_authority: Pubkey) -> accounts::ProgramConfigEdit {
+    accounts::ProgramConfigEdit {
+        program_config: pda::program_config().0,
+        current_authority,
+    }
+}
+
+pub fn program_config_propose_authority(
+    current_authority: Pubkey,
+    new_authority: Pubkey,
+) -> Instruction {
+    build(
+        program_config_edit(current_authority),
+        instruction::ProgramConfigProposeAuthority { new_authority },
+    )
+}
+
+/// Signed by the pending authority of the program config.
+pub fn program_config_accept_authority(new_authority: Pubkey) -> Instruction {
+    build(
+        accounts::ProgramConfigAcceptAuthority {
+            program_config: pda::program_config().0,
+            new_authority,
+        },
+        instruction::ProgramConfigAcceptAuthority {},
+    )
+}
+
+pub fn program_config_cancel_authority(current_authority: Pubkey) -> Instruction {
+    build(
+        program_config_edit(current_authority),
+        instruction::ProgramConfigCancelAuthority {},
+    )
+}
+
+pub fn program_config_set_creation_fee(
+    current_authority: Pubkey,
+    new_creation_fee: u64,
+) -> Instruction {
+    build(
+        program_config_edit(current_authority),
+        instruction::ProgramConfigSetCreationFee { new_creation_fee },
+    )
+}
+
+pub fn program_config_set_treasury(current_authority: Pubkey, new_treasury: Pubkey) -> Instruction {
+    build(
+        program_config_edit(current_authority),
+        instruction::ProgramConfigSetTreasury { new_treasury },
+    )
+}
+
+pub fn program_config_set_fee_mint(
+    current_authority: Pubkey,
+    new_fee_mint: Option<Pubkey>,
+) -> Instruction {
+    build(
+        program_config_edit(current_authority),
+        instruction::ProgramConfigSetFeeMint { new_fee_mint },
+    )
+}
+
+pub fn program_config_set_paused(
+    current_authority: Pubkey,
+    new_paused: PausedCategories,
+) -> Instruction {
+    build(
+        program_config_edit(current_authority),
+        instruction::ProgramConfigSetPaused { new_paused },
+    )
+}
+
+/// `authority` must be the program config authority.
+pub fn fee_waiver_create(
+    authority: Pubkey,
+    rent_payer: Pubkey,
+    creator: Pubkey,
+    discount_percent: u8,
+    expires_at: Option<i64>,
+    remaining_uses: Option<u32>,
+) -> Instruction {
+    build(
+        accounts::FeeWaiverCreate {
+            program_config: pda::program_config().0,
+            fee_waiver: pda::fee_waiver(&creator).0,
+            authority,
+            rent_payer,
+            system_program: system_program::ID,
+        },
+        instruction::FeeWaiverCreate {
+            creator,
+            discount_percent,
+            expires_at,
+            remaining_uses,
+        },
+    )
+}
+
+pub fn fee_waiver_remove(authority: Pubkey, creator: Pubkey, rent_collector: Pubkey) -> Instruction {
+    build(
+        accounts::FeeWaiverRemove {
+            program_config: pda::program_config().0,
+            fee_waiver: pda::fee_waiver(&creator).0,
+            authority,
+            rent_collector,
+        },
+        instruction::FeeWaiverRemove {},
+    )
+}
+
+fn multisig_create_accounts(
+    creator: Pubkey,
+    create_key: Pubkey,
+    treasury: Pubkey,
+    fee_waiver: bool,
+) -> accounts::MultisigCreate {
+    accounts::MultisigCreate {
+        multisig: pda::multisig(&create_key).0,
+        program_config: pda::program_config().0,
+        treasury,
+        create_key,
+        creator,
+        system_program: system_program::ID,
+        fee_mint: None,
+        creator_token_account: None,
+        treasury_token_account: None,
+        token_program: None,
+        fee_waiver: fee_waiver.then(|| pda::fee_waiver(&creator).0),
+    }
+}
+
+/// `treasury` must be the treasury of the program config, which receives the creation fee.
+/// For program configs without a fee mint; see [`multisig_create_with_token_fee`] otherwise.
+/// With `fee_waiver`, the fee waiver of `creator` is applied to the creation fee.
+#[allow(clippy::too_many_arguments)]
+pub fn multisig_create(
+    creator: Pubkey,
+    create_key: Pubkey,
+    treasury: Pubkey,
+    fee_waiver: bool,
+    config_authority: Pubkey,
+    threshold: u16,
+    members: Vec<Member>,
+    time_lock: u32,
+) -> Instruction {
+    build(
+        multisig_create_accounts(creator, create_key, treasury, fee_waiver),
+        instruction::MultisigCreate {
+            config_authority,
+            threshold,
+            members,
+            time_lock,
+        },
+    )
+}
+
+/// For program configs charging the creation fee in `fee_mint` tokens, paid from
+/// `creator_token_account` into the associated token account of `treasury`.
+#[allow(clippy::too_many_arguments)]
+pub fn multisig_create_with_token_fee(
+    creator: Pubkey,
+    create_key: Pubkey,
+    treasury: Pubkey,
+    fee_waiver: bool,
+    fee_mint: Pubkey,
+    creator_token_account: Pubkey,
+    config_authority: Pubkey,
+    threshold: u16,
+    members: Vec<Member>,
+    time_lock: u32,
+) -> Instruction {
+    let mut accounts = multisig_create_accounts(creator, create_key, treasury, fee_waiver);
+    accounts.fee_mint = Some(fee_mint);
+    accounts.creator_token_account = Some(creator_token_account);
+    accounts.treasury_token_account = Some(pda::associated_token_account(&treasury, &fee_mint));
+    accounts.token_program = Some(anchor_spl::token::ID);
+
+    build(
+        accounts,
+        instruction::MultisigCreate {
+            config_authority,
+            threshold,
+            members,
+            time_lock,
+        },
+    )
+}
+
+fn multisig_edit(multisig: Pubkey, config_authority: Pubkey) -> accounts::MultisigEdit {
+    accounts::MultisigEdit {
+        multisig,
+        config_authority,
+        rent_payer: None,
+        rent_collector: None,
+        system_program: system_program::ID,
+    }
+}
+
+/// `rent_payer` must sign; it pays for the multisig account growing.
+pub fn multisig_add_member(
+    multisig: Pubkey,
+    config_authority: Pubkey,
+    rent_payer: Pubkey,
+    new_member: Member,
+) -> Instruction {
+    build(
+        accounts::MultisigEdit {
+            rent_payer: Some(rent_payer),
+            ..multisig_edit(multisig, config_authority)
+        },
+        instruction::MultisigAddMember { new_member },
+    )
+}
+
+/// The rent freed by the multisig account shrinking goes to `rent_collector`,
+/// which must be the multisig's, or stays in the multisig when `None`.
+pub fn multisig_remove_member(
+    multisig: Pubkey,
+    config_authority: Pubkey,
+    rent_collector: Option<Pubkey>,
+    old_member: Pubkey,
+) -> Instruction {
+    build(
+        accounts::MultisigEdit {
+            rent_collector,
+            ..multisig_edit(multisig, config_authority)
+        },
+        instruction::MultisigRemoveMember { old_member },
+    )
+}
+
+pub fn multisig_set_time_lock(
+    multisig: Pubkey,
+    config_authority: Pubkey,
+    new_time_lock: u32,
+) -> Instruction {
+    build(
+        multisig_edit(multisig, config_authority),
+        instruction::MultisigSetTimeLock { new_time_lock },
+    )
+}
+
+pub fn multisig_change_threshold(
+    multisig: Pubkey,
+    config_authority: Pubkey,
+    new_threshold: u16,
+) -> Instruction {
+    build(
+        multisig_edit(multisig, config_authority),
+        instruction::MultisigChangeThreshold { new_threshold },
+    )
+}
+
+pub fn multisig_set_member_permissions(
+    multisig: Pubkey,
+    config_authority: Pubkey,
+    member: Pubkey,
+    permissions: Permissions,
+) -> Instruction {
+    build(
+        multisig_edit(multisig, config_authority),
+        instruction::MultisigSetMemberPermissions {
+            member,
+            permissions,
+        },
+    )
+}
+
+pub fn multisig_set_member_weight(
+    multisig: Pubkey,
+    config_authority: Pubkey,
+    member: Pubkey,
+    weight: u16,
+) -> Instruction {
+    build(
+        multisig_edit(multisig, config_authority),
+        instruction::MultisigSetMemberWeight { member, weight },
+    )
+}
+
+pub fn multisig_propose_config_authority(
+    multisig: Pubkey,
+    config_authority: Pubkey,
+    new_config_authority: Pubkey,
+) -> Instruction {
+    build(
+        multisig_edit(multisig, config_authority),
+        instruction::MultisigProposeConfigAuthority {
+            new_config_authority,
+        },
+    )
+}
+
+/// Signed by the pending config authority of the multisig.
+pub fn multisig_accept_config_authority(
+    multisig: Pubkey,
+    new_config_authority: Pubkey,
+) -> Instruction {
+    build(
+        accounts::MultisigAcceptConfigAuthority {
+            multisig,
+            new_config_authority,
+        },
+        instruction::MultisigAcceptConfigAuthority {},
+    )
+}
+
+pub fn multisig_cancel_config_authority(multisig: Pubkey, config_authority: Pubkey) -> Instruction {
+    build(
+        multisig_edit(multisig, config_authority),
+        instruction::MultisigCancelConfigAuthority {},
+    )
+}
+
+pub fn multisig_renounce_config_authority(
+    multisig: Pubkey,
+    config_authority: Pubkey,
+) -> Instruction {
+    build(
+        multisig_edit(multisig, config_authority),
+        instruction::MultisigRenounceConfigAuthority {},
+    )
+}
+
+pub fn multisig_set_rent_collector(
+    multisig: Pubkey,
+    config_authority: Pubkey,
+    new_rent_collector: Option<Pubkey>,
+) -> Instruction {
+    build(
+        multisig_edit(multisig, config_authority),
+        instruction::MultisigSetRentCollector { new_rent_collector },
+    )
+}
+
+pub fn multisig_set_voting_period(
+    multisig: Pubkey,
+    config_authority: Pubkey,
+    new_voting_period: Option<u32>,
+) -> Instruction {
+    build(
+        multisig_edit(multisig, config_authority),
+        instruction::MultisigSetVotingPeriod { new_voting_period },
+    )
+}
+
+/// `create_key` must sign; the spending limit address is derived from it.
+#[allow(clippy::too_many_arguments)]
+pub fn multisig_add_spending_limit(
+    multisig: Pubkey,
+    config_authority: Pubkey,
+    create_key: Pubkey,
+    vault_index: u8,
+    mint: Pubkey,
+    amount: u64,
+    period: Period,
+    members: Vec<Pubkey>,
+    destinations: Vec<Pubkey>,
+) -> Instruction {
+    build(
+        accounts::MultisigAddSpendingLimit {
+            multisig,
+            config_authority,
+            create_key,
+            spending_limit: pda::spending_limit(&multisig, &create_key).0,
+            system_program: system_program::ID,
+        },
+        instruction::MultisigAddSpendingLimit {
+            vault_index,
+            mint,
+            amount,
+            period,
+            members,
+            destinations,
+        },
+    )
+}
+
+pub fn multisig_remove_spending_limit(
+    multisig: Pubkey,
+    config_authority: Pubkey,
+    spending_limit: Pubkey,
+    rent_collector: Pubkey,
+    memo: Option<String>,
+) -> Instruction {
+    build(
+        accounts::MultisigRemoveSpendingLimit {
+            multisig,
+            config_authority,
+            spending_limit,
+            rent_collector,
+        },
+        instruction::MultisigRemoveSpendingLimit { memo },
+    )
+}
+
+/// Transfers SOL from a vault within the bounds of a spending limit.
+pub fn spending_limit_use_sol(
+    multisig: Pubkey,
+    member: Pubkey,
+    spending_limit: Pubkey,
+    vault_index: u8,
+    destination: Pubkey,
+    amount: u64,
+) -> Instruction {
+    build(
+        accounts::SpendingLimitUse {
+            multisig,
+            program_config: pda::program_config().0,
+            member,
+            spending_limit,
+            vault: pda::vault(&multisig, vault_index).0,
+            destination,
+            system_program: Some(system_program::ID),
+            mint: None,
+            vault_token_account: None,
+            destination_token_account: None,
+            token_program: None,
+        },
+        instruction::SpendingLimitUse { amount },
+    )
+}
+
+/// Transfers SPL tokens from a vault within the bounds of a spending limit.
+#[allow(clippy::too_many_arguments)]
+pub fn spending_limit_use_token(
+    multisig: Pubkey,
+    member: Pubkey,
+    spending_limit: Pubkey,
+    vault_index: u8,
+    mint: Pubkey,
+    destination: Pubkey,
+    vault_token_account: Pubkey,
+    destination_token_account: Pubkey,
+    amount: u64,
+) -> Instruction {
+    build(
+        accounts::SpendingLimitUse {
+            multisig,
+            program_config: pda::program_config().0,
+            member,
+            spending_limit,
+            vault: pda::vault(&multisig, vault_index).0,
+            destination,
+            system_program: None,
+            mint: Some(mint),
+            vault_token_account: Some(vault_token_account),
+            destination_token_account: Some(destination_token_account),
+            token_program: Some(anchor_spl::token::ID),
+        },
+        instruction::SpendingLimitUse { amount },
+    )
+}
+
+/// `transaction_index` is the index of the new transaction, i.e. the
+/// `transaction_index` of the multisig plus one.
+pub fn config_transaction_create(
+    multisig: Pubkey,
+    creator: Pubkey,
+    rent_payer: Pubkey,
+    transaction_index: u64,
+    actions: Vec<ConfigAction>,
+) -> Instruction {
+    build(
+        accounts::ConfigTransactionCreate {
+            multisig,
+            program_config: pda::program_config().0,
+            transaction: pda::transaction(&multisig, transaction_index).0,
+            creator,
+            rent_payer,
+            system_program: system_program::ID,
+        },
+        instruction::ConfigTransactionCreate { actions },
+    )
+}
+
+/// `actions` are the actions of the transaction, used to pass the spending
+/// limits they create or close. `rent_payer` is required when they create
+/// spending limits or add members. The rent freed by removing members or
+/// spending limits goes to `rent_collector`, which must be the multisig's.
+pub fn config_transaction_execute(
+    multisig: Pubkey,
+    member: Pubkey,
+    transaction_index: u64,
+    actions: &[ConfigAction],
+    rent_payer: Option<Pubkey>,
+    rent_collector: Option<Pubkey>,
+) -> Instruction {
+    let mut ix = build(
+        accounts::ConfigTransactionExecute {
+            multisig,
+            program_config: pda::program_config().0,
+            member,
+            proposal: pda::proposal(&multisig, transaction_index).0,
+            transaction: pda::transaction(&multisig, transaction_index).0,
+            rent_payer,
+            rent_collector,
+            system_program: rent_payer.map(|_| system_program::ID),
+        },
+        instruction::ConfigTransactionExecute {},
+    );
+
+    for action in actions {
+        match action {
+            ConfigAction::AddSpendingLimit { create_key, .. } => {
+                let spending_limit = pda::spending_limit(&multisig, create_key).0;
+                ix.accounts.push(AccountMeta::new(spending_limit, false));
+            }
+            ConfigAction::RemoveSpendingLimit { spending_limit } => {
+                ix.accounts.push(AccountMeta::new(*spending_limit, false));
+            }
+            _ => {}
+        }
+    }
+
+    ix
+}
+
+pub fn config_transaction_accounts_close(
+    multisig: Pubkey,
+    caller: Pubkey,
+    transaction_index: u64,
+    rent_collector: Pubkey,
+) -> Instruction {
+    build(
+        accounts::ConfigTransactionAccountsClose {
+            multisig,
+            proposal: pda::proposal(&multisig, transaction_index).0,
+            transaction: pda::transaction(&multisig, transaction_index).0,
+            rent_collector,
+            caller,
+            system_program: system_program::ID,
+        },
+        instruction::ConfigTransactionAccountsClose {},
+    )
+}
+
+/// `transaction_index` is the index of the new transaction, i.e. the
+/// `transaction_index` of the multisig plus one.
+pub fn vault_transaction_create(
+    multisig: Pubkey,
+    creator: Pubkey,
+    rent_payer: Pubkey,
+    transaction_index: u64,
+    vault_index: u8,
+    message: VaultTransactionMessage,
+) -> Instruction {
+    build(
+        accounts::VaultTransactionCreate {
+            multisig,
+            program_config: pda::program_config().0,
+            transaction: pda::transaction(&multisig, transaction_index).0,
+            creator,
+            rent_payer,
+            system_program: system_program::ID,
+        },
+        instruction::VaultTransactionCreate {
+            vault_index,
+            message,
+        },
+    )
+}
+
+/// `vault_index` and `message` must match the vault transaction; every
+/// account and program they reference is passed to the instruction.
+pub fn vault_transaction_execute(
+    multisig: Pubkey,
+    member: Pubkey,
+    transaction_index: u64,
+    vault_index: u8,
+    message: &VaultTransactionMessage,
+) -> Instruction {
+    let mut ix = build(
+        accounts::VaultTransactionExecute {
+            multisig,
+            program_config: pda::program_config().0,
+            member,
+            proposal: pda::proposal(&multisig, transaction_index).0,
+            transaction: pda::transaction(&multisig, transaction_index).0,
+        },
+        instruction::VaultTransactionExecute {},
+    );
+
+    // The vault signs through the program, not the transaction.
+    let vault = pda::vault(&multisig, vault_index).0;
+    let mut remaining_accounts: Vec<AccountMeta> = Vec::new();
+    let mut add_account = |pubkey: Pubkey, is_signer: bool, is_writable: bool| {
+        let is_signer = is_signer && pubkey != vault;
+        match remaining_accounts.iter_mut().find(|meta| meta.pubkey == pubkey) {
+            Some(meta) => {
+                meta.is_signer |= is_signer;
+                meta.is_writable |= is_writable;
+            }
+            None => remaining_accounts.push(AccountMeta {
+                pubkey,
+                is_signer,
+                is_writable,
+            }),
+        }
+    };
+
+    for vault_instruction in message.instructions.iter() {
+        for meta in vault_instruction.accounts.iter() {
+            add_account(meta.pubkey, meta.is_signer, meta.is_writable);
+        }
+        add_account(vault_instruction.program_id, false, false);
+    }
+
+    ix.accounts.extend(remaining_accounts);
+    ix
+}
+
+pub fn proposal_create(
+    multisig: Pubkey,
+    creator: Pubkey,
+    rent_payer: Pubkey,
+    transaction_index: u64,
+    draft: bool,
+) -> Instruction {
+    build(
+        accounts::ProposalCreate {
+            multisig,
+            program_config: pda::program_config().0,
+            proposal: pda::proposal(&multisig, transaction_index).0,
+            creator,
+            rent_payer,
+            system_program: system_program::ID,
+        },
+        instruction::ProposalCreate {
+            transaction_index,
+            draft,
+        },
+    )
+}
+
+pub fn proposal_activate(multisig: Pubkey, member: Pubkey, transaction_index: u64) -> Instruction {
+    build(
+        accounts::ProposalActivate {
+            multisig,
+            program_config: pda::program_config().0,
+            member,
+            proposal: pda::proposal(&multisig, transaction_index).0,
+        },
+        instruction::ProposalActivate {},
+    )
+}
+
+fn proposal_vote(multisig: Pubkey, member: Pubkey, transaction_index: u64) -> accounts::ProposalVote {
+    accounts::ProposalVote {
+        multisig,
+        program_config: pda::program_config().0,
+        member,
+        proposal: pda::proposal(&multisig, transaction_index).0,
+    }
+}
+
+pub fn proposal_approve(
+    multisig: Pubkey,
+    member: Pubkey,
+    transaction_index: u64,
+    memo: Option<String>,
+) -> Instruction {
+    build(
+        proposal_vote(multisig, member, transaction_index),
+        instruction::ProposalApprove { memo },
+    )
+}
+
+pub fn proposal_reject(
+    multisig: Pubkey,
+    member: Pubkey,
+    transaction_index: u64,
+    memo: Option<String>,
+) -> Instruction {
+    build(
+        proposal_vote(multisig, member, transaction_index),
+        instruction::ProposalReject { memo },
+    )
+}
+
+pub fn proposal_cancel(
+    multisig: Pubkey,
+    member: Pubkey,
+    transaction_index: u64,
+    memo: Option<String>,
+) -> Instruction {
+    build(
+        proposal_vote(multisig, member, transaction_index),
+        instruction::ProposalCancel { memo },
+    )
+}
+
+pub fn proposal_expire(multisig: Pubkey, caller: Pubkey, transaction_index: u64) -> Instruction {
+    build(
+        accounts::ProposalExpire {
+            multisig,
+            proposal: pda::proposal(&multisig, transaction_index).0,
+            caller,
+        },
+        instruction::ProposalExpire {},
+    )
+}
+
+fn migrate(account: Pubkey, rent_payer: Pubkey) -> accounts::Migrate {
+    accounts::Migrate {
+        account,
+        rent_payer,
+        system_program: system_program::ID,
+    }
+}
+
+pub fn migrate_program_config(rent_payer: Pubkey) -> Instruction {
+    build(
+        migrate(pda::program_config().0, rent_payer),
+        instruction::MigrateProgramConfig {},
+    )
+}
+
+/// `create_key` must derive `multisig`. The original program never stored it, so it is
+/// written by the migration.
+pub fn migrate_multisig(multisig: Pubkey, create_key: Pubkey, rent_payer: Pubkey) -> Instruction {
+    build(migrate(multisig, rent_payer), instruction::MigrateMultisig { create_key })
+}
+
+pub fn migrate_spending_limit(spending_limit: Pubkey, rent_payer: Pubkey) -> Instruction {
+    build(
+        migrate(spending_limit, rent_payer),
+        instruction::MigrateSpendingLimit {},
+    )
+}
+
+pub fn migrate_proposal(multisig: Pubkey, transaction_index: u64, rent_payer: Pubkey) -> Instruction {
+    build(
+        migrate(pda::proposal(&multisig, transaction_index).0, rent_payer),
+        instruction::MigrateProposal {},
+    )
+}
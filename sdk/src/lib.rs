@@ -0,0 +1,14 @@
+//! Rust client for the `collectivex_multisig` program.
+//!
+//! - [`pda`] derives the addresses of the program accounts.
+//! - [`instructions`] builds an `Instruction` for every entry point of the program.
+//! - [`accounts`] fetches and deserializes the program accounts.
+
+pub mod accounts;
+pub mod error;
+pub mod instructions;
+pub mod pda;
+
+pub use collectivex_multisig::state;
+pub use collectivex_multisig::ID as PROGRAM_ID;
+pub use error::{ClientError, ClientResult};
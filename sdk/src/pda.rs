@@ -0,0 +1,85 @@
+//! Address derivation for the program accounts, mirroring the seeds in `constants.rs`.
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::bpf_loader_upgradeable;
+use collectivex_multisig::constants::{
+    FEE_WAIVER_SEED, MULTISIG_SEED, PROGRAM_CONFIG_SEED, PROPOSAL_SEED, SPENDING_LIMIT_SEED,
+    TRANSACTION_SEED, VAULT_SEED,
+};
+use collectivex_multisig::ID;
+
+/// The global program config.
+pub fn program_config() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[PROGRAM_CONFIG_SEED], &ID)
+}
+
+/// The fee waiver of `creator`.
+pub fn fee_waiver(creator: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[PROGRAM_CONFIG_SEED, FEE_WAIVER_SEED, creator.as_ref()], &ID)
+}
+
+/// The multisig created with `create_key`.
+pub fn multisig(create_key: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[PROGRAM_CONFIG_SEED, MULTISIG_SEED, create_key.as_ref()],
+        &ID,
+    )
+}
+
+/// The config or vault transaction at `transaction_index` of a multisig.
+pub fn transaction(multisig: &Pubkey, transaction_index: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            PROGRAM_CONFIG_SEED,
+            multisig.as_ref(),
+            TRANSACTION_SEED,
+            &transaction_index.to_le_bytes(),
+        ],
+        &ID,
+    )
+}
+
+/// The proposal of the transaction at `transaction_index` of a multisig.
+pub fn proposal(multisig: &Pubkey, transaction_index: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            PROGRAM_CONFIG_SEED,
+            multisig.as_ref(),
+            TRANSACTION_SEED,
+            &transaction_index.to_le_bytes(),
+            PROPOSAL_SEED,
+        ],
+        &ID,
+    )
+}
+
+/// The spending limit of a multisig created with `create_key`.
+pub fn spending_limit(multisig: &Pubkey, create_key: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            PROGRAM_CONFIG_SEED,
+            multisig.as_ref(),
+            SPENDING_LIMIT_SEED,
+            create_key.as_ref(),
+        ],
+        &ID,
+    )
+}
+
+/// The vault at `vault_index` of a multisig.
+pub fn vault(multisig: &Pubkey, vault_index: u8) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[PROGRAM_CONFIG_SEED, multisig.as_ref(), VAULT_SEED, &[vault_index]],
+        &ID,
+    )
+}
+
+/// The program data account of the program, holding its upgrade authority.
+pub fn program_data() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[ID.as_ref()], &bpf_loader_upgradeable::ID)
+}
+
+/// The associated token account of `owner` for `mint`, where token creation fees are paid.
+pub fn associated_token_account(owner: &Pubkey, mint: &Pubkey) -> Pubkey {
+    collectivex_multisig::utils::associated_token_address(owner, mint)
+}
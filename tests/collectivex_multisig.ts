@@ -1,16 +1,228 @@
 import * as anchor from "@coral-xyz/anchor";
-import { Program } from "@coral-xyz/anchor";
+import { Program, BN, AnchorError } from "@coral-xyz/anchor";
+import { assert } from "chai";
 import { CollectivexMultisig } from "../target/types/collectivex_multisig";
 
+const { Keypair, PublicKey } = anchor.web3;
+type PublicKey = anchor.web3.PublicKey;
+
+const ALL_PERMISSIONS = { mask: 7 };
+const BPF_LOADER_UPGRADEABLE = new PublicKey("BPFLoaderUpgradeab1e11111111111111111111111");
+
 describe("collectivex_multisig", () => {
   // Configure the client to use the local cluster.
-  anchor.setProvider(anchor.AnchorProvider.env());
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
 
   const program = anchor.workspace.CollectivexMultisig as Program<CollectivexMultisig>;
+  const wallet = provider.wallet.publicKey;
+
+  const [programConfig] = PublicKey.findProgramAddressSync(
+    [Buffer.from("program_config")],
+    program.programId
+  );
+
+  // `anchor test` deploys the program with the provider wallet as its upgrade authority.
+  const [programData] = PublicKey.findProgramAddressSync(
+    [program.programId.toBuffer()],
+    BPF_LOADER_UPGRADEABLE
+  );
+
+  const multisigPda = (createKey: PublicKey) =>
+    PublicKey.findProgramAddressSync(
+      [Buffer.from("program_config"), Buffer.from("multisig"), createKey.toBuffer()],
+      program.programId
+    )[0];
+
+  const transactionPda = (multisig: PublicKey, index: number) =>
+    PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("program_config"),
+        multisig.toBuffer(),
+        Buffer.from("transaction"),
+        new BN(index).toArrayLike(Buffer, "le", 8),
+      ],
+      program.programId
+    )[0];
+
+  const proposalPda = (multisig: PublicKey, index: number) =>
+    PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("program_config"),
+        multisig.toBuffer(),
+        Buffer.from("transaction"),
+        new BN(index).toArrayLike(Buffer, "le", 8),
+        Buffer.from("proposal"),
+      ],
+      program.programId
+    )[0];
+
+  const createMultisig = async (configAuthority: PublicKey) => {
+    const createKey = Keypair.generate();
+    const multisig = multisigPda(createKey.publicKey);
+
+    await program.methods
+      .multisigCreate(configAuthority, 1, [{ key: wallet, permissions: ALL_PERMISSIONS, weight: 1 }], 0)
+      .accountsPartial({
+        multisig,
+        programConfig,
+        treasury: wallet,
+        createKey: createKey.publicKey,
+        creator: wallet,
+        feeMint: null,
+        creatorTokenAccount: null,
+        treasuryTokenAccount: null,
+        tokenProgram: null,
+        feeWaiver: null,
+      })
+      .signers([createKey])
+      .rpc();
+
+    return multisig;
+  };
+
+  const expectError = async (promise: Promise<unknown>, code: string) => {
+    try {
+      await promise;
+      assert.fail(`expected ${code}`);
+    } catch (err) {
+      assert.instanceOf(err, AnchorError);
+      assert.equal((err as AnchorError).error.errorCode.code, code);
+    }
+  };
+
+  before(async () => {
+    if (!(await program.account.programConfig.fetchNullable(programConfig))) {
+      await program.methods
+        .programConfigInit(wallet, new BN(0), wallet)
+        .accountsPartial({ programConfig, initializer: wallet, programData })
+        .rpc();
+    }
+  });
+
+  describe("controlled multisig", () => {
+    let multisig: PublicKey;
+
+    before(async () => {
+      multisig = await createMultisig(wallet);
+    });
+
+    it("is edited directly by the config authority", async () => {
+      await program.methods
+        .multisigSetTimeLock(60)
+        .accountsPartial({
+          multisig,
+          configAuthority: wallet,
+          rentPayer: null,
+          rentCollector: null,
+        })
+        .rpc();
+
+      const account = await program.account.multisig.fetch(multisig);
+      assert.equal(account.timeLock, 60);
+    });
+
+    it("rejects config transactions", async () => {
+      await expectError(
+        program.methods
+          .configTransactionCreate([{ setTimeLock: { newTimeLock: 0 } }])
+          .accountsPartial({
+            multisig,
+            transaction: transactionPda(multisig, 1),
+            creator: wallet,
+            rentPayer: wallet,
+          })
+          .rpc(),
+        "NotSupportedForControlled"
+      );
+    });
+
+    it("hands the config authority over once the new authority accepts", async () => {
+      const handedOver = await createMultisig(wallet);
+      const newAuthority = Keypair.generate();
+
+      await program.methods
+        .multisigProposeConfigAuthority(newAuthority.publicKey)
+        .accountsPartial({
+          multisig: handedOver,
+          configAuthority: wallet,
+          rentPayer: null,
+          rentCollector: null,
+        })
+        .rpc();
+
+      let account = await program.account.multisig.fetch(handedOver);
+      assert.ok(account.configAuthority.equals(wallet));
+      assert.ok(account.pendingConfigAuthority.equals(newAuthority.publicKey));
+
+      await program.methods
+        .multisigAcceptConfigAuthority()
+        .accountsPartial({ multisig: handedOver, newConfigAuthority: newAuthority.publicKey })
+        .signers([newAuthority])
+        .rpc();
+
+      account = await program.account.multisig.fetch(handedOver);
+      assert.ok(account.configAuthority.equals(newAuthority.publicKey));
+      assert.isNull(account.pendingConfigAuthority);
+    });
+  });
+
+  describe("autonomous multisig", () => {
+    let multisig: PublicKey;
+
+    before(async () => {
+      multisig = await createMultisig(PublicKey.default);
+    });
+
+    it("rejects direct edits", async () => {
+      await expectError(
+        program.methods
+          .multisigSetTimeLock(60)
+          .accountsPartial({
+            multisig,
+            configAuthority: wallet,
+            rentPayer: null,
+            rentCollector: null,
+          })
+          .rpc(),
+        "NotSupportedForAutonomous"
+      );
+    });
+
+    it("is changed through an approved config transaction", async () => {
+      const transaction = transactionPda(multisig, 1);
+      const proposal = proposalPda(multisig, 1);
+
+      await program.methods
+        .configTransactionCreate([{ setTimeLock: { newTimeLock: 60 } }])
+        .accountsPartial({ multisig, transaction, creator: wallet, rentPayer: wallet })
+        .rpc();
+
+      await program.methods
+        .proposalCreate(new BN(1), false)
+        .accountsPartial({ multisig, proposal, creator: wallet, rentPayer: wallet })
+        .rpc();
+
+      await program.methods
+        .proposalApprove(null)
+        .accountsPartial({ multisig, member: wallet, proposal })
+        .rpc();
+
+      await program.methods
+        .configTransactionExecute()
+        .accountsPartial({
+          multisig,
+          member: wallet,
+          proposal,
+          transaction,
+          rentPayer: null,
+          rentCollector: null,
+          systemProgram: null,
+        })
+        .rpc();
 
-  it("Is initialized!", async () => {
-    // Add your test here.
-    const tx = await program.methods.initialize().rpc();
-    console.log("Your transaction signature", tx);
+      const account = await program.account.multisig.fetch(multisig);
+      assert.equal(account.timeLock, 60);
+    });
   });
 });